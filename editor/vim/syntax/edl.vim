@@ -11,7 +11,7 @@ syn keyword dataRegionKeywords WITH
 syn keyword basicTypes INT BOOL FLOAT TEXT contained
 syn keyword columnKeywords REF PRIMARY KEY CHILD OF UNIQUE CHECK contained
 
-syn region celTableBlock transparent fold matchgroup=outerStatement start="TABLE\s\+[a-z0-9_]\+\s\+{" end="}" contains=basicTypes,columnKeywords
+syn region celTableBlock transparent fold matchgroup=outerStatement start="TABLE\s\+[a-z0-9_]\+\s\+\(CONSTANTS\s\+\)\?{" end="}" contains=basicTypes,columnKeywords
 syn region celMViewBlock transparent fold matchgroup=outerStatement start="MATERIALIZED\s\+VIEW\s\+[a-z0-9_]\+\s\+{" end="}\s\+AS\s\+" contains=basicTypes,columnKeywords nextGroup=embeddedSql
 
 syn region embeddedLua fold transparent matchgroup=outerStatement start="INCLUDE\s\+LUA\s\+{" end="}" contains=@LUA
@@ -350,6 +350,20 @@ pub enum DatabaseValidationError {
         error: String,
         source_file: String,
     },
+    LuaFunctionExportNotFound {
+        function_name: String,
+    },
+    LuaFunctionExportDuplicate {
+        function_name: String,
+    },
+    LuaFunctionExportShadowsBuiltinFunction {
+        function_name: String,
+        builtin_functions: Vec<String>,
+    },
+    LuaFunctionExportRegistrationError {
+        function_name: String,
+        error: String,
+    },
     SqlProofTableNotFound {
         table_name: String,
         comment: String,
@@ -388,6 +402,81 @@ pub enum DatabaseValidationError {
         proof_expression: String,
         // pretty printed json
         offending_columns: Vec<String>,
+        // pretty printed json of extra proof output columns per offender,
+        // empty if proof outputs only offender rows
+        offender_explanations: Vec<String>,
+    },
+    LuaProofTableNotFound {
+        table_name: String,
+        comment: String,
+        proof_expression: String,
+    },
+    LuaProofTableHasNoPrimaryKey {
+        table_name: String,
+        comment: String,
+    },
+    LuaProofEvaluationError {
+        table_name: String,
+        comment: String,
+        proof_expression: String,
+        error: String,
+    },
+    LuaProofUnexpectedReturnType {
+        table_name: String,
+        comment: String,
+        proof_expression: String,
+        error: String,
+    },
+    LuaProofOffenderKeyNotFound {
+        table_name: String,
+        comment: String,
+        offender_key: String,
+    },
+    LuaProofOffendersFound {
+        table_name: String,
+        comment: String,
+        proof_expression: String,
+        // pretty printed json
+        offending_columns: Vec<String>,
+    },
+    ProofRowCountMismatch {
+        table_name: String,
+        comment: String,
+        proof_expression: String,
+        expected_rows: String,
+        found_rows: usize,
+        // pretty printed json
+        matching_rows: Vec<String>,
+    },
+    ProofRowsMissing {
+        table_name: String,
+        comment: String,
+        proof_expression: String,
+        // pretty printed json
+        missing_rows: Vec<String>,
+    },
+    ProofExceptionsOnlyAllowedForNoneOrAll {
+        table_name: String,
+        comment: String,
+    },
+    ProofExceptionsTableHasNoPrimaryKey {
+        table_name: String,
+        comment: String,
+    },
+    ProofExceptionsInvalidDate {
+        table_name: String,
+        comment: String,
+        date: String,
+    },
+    ProofExceptionsExpired {
+        table_name: String,
+        comment: String,
+        until: String,
+    },
+    ProofExceptionKeysNotOffending {
+        table_name: String,
+        comment: String,
+        keys: Vec<String>,
     },
     #[cfg(feature = "datalog")]
     DatalogProofTableNotFound {
@@ -451,6 +540,9 @@ pub enum DatabaseValidationError {
         proof_expression: String,
         // pretty printed json
         offending_columns: Vec<String>,
+        // pretty printed json of extra proof output columns per offender,
+        // empty if proof outputs only offender rows
+        offender_explanations: Vec<String>,
     },
     #[cfg(not(feature = "datalog"))]
     DatalogIsDisabled {
@@ -491,6 +583,76 @@ pub enum DatabaseValidationError {
         actual_column_type: String,
         expected_column_type: DBType,
     },
+    #[cfg(feature = "datalog")]
+    DatalogMatViewOutputRuleNotFound {
+        error: String,
+        table_name: String,
+        datalog_expression: String,
+    },
+    #[cfg(feature = "datalog")]
+    DatalogMatViewQueryParseError {
+        error: String,
+        table_name: String,
+        datalog_expression: String,
+    },
+    #[cfg(feature = "datalog")]
+    DatalogMatViewBadOutputRuleFormat {
+        error: String,
+        table_name: String,
+        datalog_expression: String,
+    },
+    #[cfg(feature = "datalog")]
+    DatalogMatViewWrongColumnCount {
+        table_name: String,
+        datalog_expression: String,
+        expected_columns: usize,
+        actual_columns: usize,
+    },
+    #[cfg(feature = "datalog")]
+    DatalogMatViewQueryingFailure {
+        error: String,
+        table_name: String,
+        datalog_expression: String,
+    },
+    #[cfg(feature = "datalog")]
+    DatalogMatViewWrongColumnTypeReturned {
+        table_name: String,
+        datalog_expression: String,
+        column_name: String,
+        return_row_index: usize,
+        actual_column_type: String,
+        expected_column_type: DBType,
+    },
+    LuaMatViewEvaluationError {
+        table_name: String,
+        lua_expression: String,
+        error: String,
+    },
+    LuaMatViewUnexpectedReturnType {
+        table_name: String,
+        lua_expression: String,
+        error: String,
+    },
+    LuaMatViewColumnNotFound {
+        table_name: String,
+        lua_expression: String,
+        column_name: String,
+        return_row_index: usize,
+    },
+    LuaMatViewUnknownColumn {
+        table_name: String,
+        lua_expression: String,
+        column_name: String,
+        return_row_index: usize,
+    },
+    LuaMatViewWrongColumnTypeReturned {
+        table_name: String,
+        lua_expression: String,
+        column_name: String,
+        return_row_index: usize,
+        actual_column_type: String,
+        expected_column_type: DBType,
+    },
     FailureReadingExternalFile {
         target_file_path: String,
         error: String,
@@ -605,6 +767,14 @@ pub enum DatabaseValidationError {
         table: String,
         replacement_primary_key: String,
     },
+    ReplacementOverIncludedDocumentValuesIsNotSupported {
+        table: String,
+        replacement_primary_key: String,
+    },
+    ReplacementOverImportedValuesIsNotSupported {
+        table: String,
+        replacement_primary_key: String,
+    },
     OCamlDataModulePathIsFileAndNotDirectory {
         path: String,
         explanation: String,
@@ -639,6 +809,59 @@ pub enum DatabaseValidationError {
         row_value: String,
         explanation: String,
     },
+    RowConstantsTableHasNoPrimaryKey {
+        table_name: String,
+    },
+    RowConstantsDuplicateName {
+        table_name: String,
+        constant_name: String,
+    },
+    AncestorDescendantColumnConflict {
+        table_name: String,
+        column_name: String,
+        descendant_table: String,
+    },
+    RowConstantsNameCollisionBetweenTables {
+        constant_name: String,
+        first_table_name: String,
+        second_table_name: String,
+    },
+    CsvParsingError {
+        csv_file: String,
+        line: usize,
+        error: String,
+    },
+    CsvHeaderNotFound {
+        csv_file: String,
+        header: String,
+    },
+    CsvDataError {
+        csv_file: String,
+        line: usize,
+        error: Box<DatabaseValidationError>,
+    },
+    StructuredDataFileError {
+        path: String,
+        error: String,
+    },
+    SqliteImportError {
+        sqlite_file: String,
+        sqlite_table: String,
+        error: String,
+    },
+    SqliteImportUnsupportedValue {
+        sqlite_file: String,
+        sqlite_table: String,
+        rowid: i64,
+        column_name: String,
+        value_type: String,
+    },
+    SqliteImportDataError {
+        sqlite_file: String,
+        sqlite_table: String,
+        rowid: i64,
+        error: Box<DatabaseValidationError>,
+    },
 }
 
 impl std::fmt::Display for DatabaseValidationError {
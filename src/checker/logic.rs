@@ -1,8 +1,8 @@
 #![allow(clippy::needless_range_loop)]
 
 use std::{
-    collections::{HashMap, HashSet, BTreeMap},
-    sync::Mutex, path::PathBuf, str::FromStr, io::BufRead,
+    collections::{HashMap, HashSet, BTreeMap, BTreeSet},
+    sync::{Arc, Mutex}, path::PathBuf, str::FromStr, io::BufRead,
 };
 
 use mlua::Function;
@@ -20,14 +20,17 @@ use crate::{
     db_parser::{
         SourceOutputs, TableColumn, TableData, TableDataSegment, TableDataStruct,
         TableDataStructField, TableDataStructFields, TableDefinition, ValueWithPos, valid_unquoted_data_char, DataModules,
+        CsvImport, SqliteImport, ProofSeverity, ExpressionProof, ProofQuantifier,
+        ValidExpressions, STRUCTURED_DOCUMENT_SOURCE_FILE_ID, IMPORTED_ROW_SOURCE_FILE_ID,
     }, codegen::write_file_check_if_different,
 };
 
 use super::{
     errors::DatabaseValidationError,
+    sql_functions::{register_builtin_sql_functions, BUILTIN_SQL_FUNCTIONS},
     types::{
-        DBIdentifier, DataColumn, DataTable, ForeignKey, SerializationVector, SerializedVector,
-        UniqConstraint,
+        DBIdentifier, DataColumn, DataTable, ForeignKey, ImportedRowSource, SerializationVector,
+        SerializedVector, UniqConstraint,
     },
 };
 
@@ -104,9 +107,14 @@ pub struct AllData {
     pub(crate) foreign_to_native_child_keys_map:
         HashMap<ForeignKeyToNativeChildRelationship, ForeignKeyToNativeChildRelationshipData>,
     pub(crate) parent_child_keys_map: HashMap<ParentKeyRelationship, ParentKeyRelationshipData>,
+    // same as parent child keys but for ancestors above direct parent
+    pub(crate) ancestor_descendant_keys_map: HashMap<ParentKeyRelationship, ParentKeyRelationshipData>,
     pub(crate) table_replacements: HashMap<usize, HashMap<String, SingleRowReplacement>>,
     pub(crate) source_replacements: Vec<ScheduledValueReplacementInSource>,
-    pub(crate) lua_runtime: Lazy<Mutex<mlua::Lua>>,
+    // offenders of proofs marked as WARN
+    pub(crate) proof_warnings: Vec<DatabaseValidationError>,
+    // shared with sqlite functions exported from lua
+    pub(crate) lua_runtime: Lazy<Arc<Mutex<mlua::Lua>>>,
     pub(crate) sqlite_db: Lazy<SqliteDBs>,
     #[cfg(feature = "datalog")]
     pub(crate) datalog_db: Lazy<Mutex<asdi::Program>>,
@@ -120,47 +128,38 @@ impl AllData {
             foreign_to_foreign_child_keys_map: HashMap::new(),
             foreign_to_native_child_keys_map: HashMap::new(),
             parent_child_keys_map: HashMap::new(),
+            ancestor_descendant_keys_map: HashMap::new(),
             table_replacements: HashMap::new(),
             source_replacements: Vec::new(),
-            lua_runtime: Lazy::new(|| Mutex::new(mlua::Lua::new())),
+            proof_warnings: Vec::new(),
+            lua_runtime: Lazy::new(|| Arc::new(Mutex::new(mlua::Lua::new()))),
             sqlite_db: Lazy::new(|| {
                 let this_counter = rand::thread_rng().gen::<usize>();
                 let this_db_name = format!("file:edendb_{this_counter}?mode=memory&cache=shared");
 
                 let conn = rusqlite::Connection::open(this_db_name.as_str()).unwrap();
+                register_builtin_sql_functions(&conn).unwrap();
                 let rw = Mutex::new(conn);
 
                 let conn = rusqlite::Connection::open(this_db_name.as_str()).unwrap();
                 let _ = conn
                     .execute("PRAGMA query_only = true;", rusqlite::params![])
                     .unwrap();
+                register_builtin_sql_functions(&conn).unwrap();
                 let ro = Mutex::new(conn);
 
                 SqliteDBs { rw, ro }
             }),
             #[cfg(feature = "datalog")]
-            datalog_db: Lazy::new(|| {
-                let mut features = asdi::features::FeatureSet::default();
-                features.add_support_for(&asdi::features::FEATURE_COMPARISONS);
-                features.add_support_for(&asdi::features::FEATURE_CONSTRAINTS);
-                features.add_support_for(&asdi::features::FEATURE_NEGATION);
-                Mutex::new(asdi::Program::new_with_features(features))
-            }),
+            datalog_db: Lazy::new(|| Mutex::new(new_datalog_program())),
         }
     }
 
-    #[cfg(test)]
     pub fn new(outputs: SourceOutputs) -> Result<AllData, DatabaseValidationError> {
-        Self::new_with_flags(outputs, false)
-    }
-
-    pub fn new_with_flags(
-        outputs: SourceOutputs,
-        sqlite_needed: bool,
-    ) -> Result<AllData, DatabaseValidationError> {
         let mut res = AllData::init_all_data();
 
         maybe_load_lua_runtime(&mut res, &outputs)?;
+        register_lua_sqlite_functions(&mut res, &outputs)?;
 
         crunch_tables_metadata(&mut res, &outputs)?;
         check_exclusive_data_violations(outputs.table_data_segments())?;
@@ -173,9 +172,10 @@ impl AllData {
         data_modules(&mut res, &outputs)?;
         check_unused_replacements(&mut res)?;
         compute_generated_columns(&mut res)?;
-        maybe_insert_sqlite_data(&mut res, &outputs, sqlite_needed)?;
+        maybe_insert_sqlite_data(&mut res, &outputs)?;
         compute_materialized_views(&mut res)?;
         validate_data(&mut res)?;
+        compute_ancestor_descendant_keys(&mut res);
 
         run_sqlite_proofs(&mut res, &outputs)?;
 
@@ -183,6 +183,8 @@ impl AllData {
         #[cfg(feature = "datalog")]
         run_datalog_proofs(&mut res, &outputs)?;
 
+        run_lua_proofs(&mut res, &outputs)?;
+
         // after all checks have passed process replacements if they exist
         process_source_replacements(&mut res, &outputs);
 
@@ -285,6 +287,20 @@ impl AllData {
         res
     }
 
+    /// Tables which have this table as ancestor above their direct parent
+    pub fn descendant_tables(&self, ancestor_table: &DataTable) -> Vec<&DataTable> {
+        let mut res = Vec::new();
+
+        for maybe_descendant in self.tables_sorted() {
+            let ancestors = self.all_parent_tables(maybe_descendant);
+            if ancestors.len() > 1 && ancestors[0..ancestors.len() - 1].contains(&ancestor_table.name) {
+                res.push(maybe_descendant);
+            }
+        }
+
+        res
+    }
+
     pub fn referee_columns(&self, referred_table: &DataTable) -> Vec<(&DataTable, &DataColumn)> {
         let mut res = Vec::new();
 
@@ -392,6 +408,27 @@ impl AllData {
                     },
                     foreign_table: maybe_parent.as_str().to_string(),
                 });
+
+                let ancestors = self.all_parent_tables(t);
+                for ancestor in &ancestors[0..ancestors.len() - 1] {
+                    let fk_vec = self
+                        .ancestor_descendant_keys_map
+                        .get(&ParentKeyRelationship {
+                            parent_table: ancestor.clone(),
+                            child_table: t.name.clone(),
+                        })
+                        .unwrap();
+
+                    res.push(SerializationVector::Fkeys {
+                        sv: SerializedVector {
+                            table_name: t.name.as_str(),
+                            column_name: format!("ancestor_{}", ancestor.as_str()),
+                            v: &fk_vec.parents_for_children_index,
+                            last_for_table: false,
+                        },
+                        foreign_table: ancestor.as_str().to_string(),
+                    });
+                }
             }
 
             for child in self.children_tables(t) {
@@ -414,6 +451,26 @@ impl AllData {
                 });
             }
 
+            for descendant in self.descendant_tables(t) {
+                let fk_vec = self
+                    .ancestor_descendant_keys_map
+                    .get(&ParentKeyRelationship {
+                        parent_table: t.name.clone(),
+                        child_table: descendant.name.clone(),
+                    })
+                    .unwrap();
+
+                res.push(SerializationVector::FkeysOneToMany {
+                    sv: SerializedVector {
+                        table_name: t.name.as_str(),
+                        column_name: format!("descendants_{}", descendant.name.as_str()),
+                        v: &fk_vec.children_for_parents_index,
+                        last_for_table: false,
+                    },
+                    foreign_table: descendant.name.as_str().to_string(),
+                });
+            }
+
             for (ref_tbl, ref_col) in self.referee_columns(t) {
                 let fk_vec = self
                     .foreign_keys_map
@@ -474,6 +531,11 @@ impl AllData {
         res.sort_by_key(|t| t.name.as_str());
         res
     }
+
+    /// Offenders of proofs marked with WARN which didn't stop compilation
+    pub fn proof_warnings(&self) -> &[DatabaseValidationError] {
+        &self.proof_warnings
+    }
 }
 
 fn check_replacements(
@@ -695,6 +757,120 @@ fn maybe_load_lua_runtime(
     Ok(())
 }
 
+fn register_lua_sqlite_functions(
+    res: &mut AllData,
+    outputs: &SourceOutputs,
+) -> Result<(), DatabaseValidationError> {
+    let mut registered = HashSet::new();
+    for export in outputs.lua_function_exports() {
+        let function_name = export.function_name.as_str();
+        // sqlite function names are case insensitive
+        if BUILTIN_SQL_FUNCTIONS.iter().any(|f| f.eq_ignore_ascii_case(function_name)) {
+            return Err(DatabaseValidationError::LuaFunctionExportShadowsBuiltinFunction {
+                function_name: function_name.to_string(),
+                builtin_functions: BUILTIN_SQL_FUNCTIONS.iter().map(|f| f.to_string()).collect(),
+            });
+        }
+
+        if !registered.insert(function_name.to_lowercase()) {
+            return Err(DatabaseValidationError::LuaFunctionExportDuplicate {
+                function_name: function_name.to_string(),
+            });
+        }
+
+        {
+            let lua = res.lua_runtime.lock().unwrap();
+            let value: mlua::Value = lua.globals().get(function_name).unwrap_or(mlua::Value::Nil);
+            if !matches!(value, mlua::Value::Function(_)) {
+                return Err(DatabaseValidationError::LuaFunctionExportNotFound {
+                    function_name: function_name.to_string(),
+                });
+            }
+        }
+
+        let flags = rusqlite::functions::FunctionFlags::SQLITE_UTF8
+            | rusqlite::functions::FunctionFlags::SQLITE_DETERMINISTIC;
+        for conn in [&res.sqlite_db.rw, &res.sqlite_db.ro] {
+            let lua = Arc::clone(&res.lua_runtime);
+            let argument_types = export.argument_types.clone();
+            let return_type = export.return_type.clone();
+            let fname = function_name.to_string();
+            conn.lock()
+                .unwrap()
+                .create_scalar_function(
+                    function_name,
+                    export.argument_types.len() as i32,
+                    flags,
+                    move |ctx| {
+                        let lua = lua.lock().unwrap();
+                        call_lua_sqlite_function(&lua, &fname, &argument_types, &return_type, ctx)
+                    },
+                )
+                .map_err(|e| DatabaseValidationError::LuaFunctionExportRegistrationError {
+                    function_name: function_name.to_string(),
+                    error: e.to_string(),
+                })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts sqlite arguments to lua values by declared types,
+/// NULL arguments are passed as nil and nil is returned as NULL
+fn call_lua_sqlite_function(
+    lua: &mlua::Lua,
+    function_name: &str,
+    argument_types: &[String],
+    return_type: &str,
+    ctx: &rusqlite::functions::Context<'_>,
+) -> rusqlite::Result<rusqlite::types::Value> {
+    use rusqlite::types::{Value as SqlValue, ValueRef};
+
+    let user_err = |e: String| rusqlite::Error::UserFunctionError(e.into());
+    let lua_err = |e: mlua::Error| user_err(format!("Lua function {function_name} failed: {e}"));
+
+    let mut args = Vec::with_capacity(argument_types.len());
+    for (idx, arg_type) in argument_types.iter().enumerate() {
+        let arg = match (arg_type.as_str(), ctx.get_raw(idx)) {
+            (_, ValueRef::Null) => mlua::Value::Nil,
+            ("INT", ValueRef::Integer(i)) => mlua::Value::Integer(i),
+            ("FLOAT", ValueRef::Integer(i)) => mlua::Value::Number(i as f64),
+            ("FLOAT", ValueRef::Real(f)) => mlua::Value::Number(f),
+            ("BOOL", ValueRef::Integer(i)) => mlua::Value::Boolean(i != 0),
+            ("TEXT", ValueRef::Text(t)) => {
+                mlua::Value::String(lua.create_string(t).map_err(lua_err)?)
+            }
+            (_, other) => {
+                return Err(user_err(format!(
+                    "Lua function {function_name} argument {} must be {arg_type}, got {}",
+                    idx + 1,
+                    other.data_type()
+                )));
+            }
+        };
+        args.push(arg);
+    }
+
+    let func: mlua::Function = lua.globals().get(function_name).map_err(lua_err)?;
+    let output: mlua::Value = func.call(mlua::MultiValue::from_vec(args)).map_err(lua_err)?;
+
+    match (return_type, &output) {
+        (_, mlua::Value::Nil) => Ok(SqlValue::Null),
+        ("INT", mlua::Value::Integer(i)) => Ok(SqlValue::Integer(*i)),
+        ("FLOAT", mlua::Value::Integer(i)) => Ok(SqlValue::Real(*i as f64)),
+        ("FLOAT", mlua::Value::Number(f)) => Ok(SqlValue::Real(*f)),
+        ("BOOL", mlua::Value::Boolean(b)) => Ok(SqlValue::Integer(*b as i64)),
+        ("TEXT", mlua::Value::String(s)) => {
+            Ok(SqlValue::Text(s.to_str().map_err(lua_err)?.to_string()))
+        }
+        _ => Err(user_err(format!(
+            "Lua function {function_name} must return {return_type}, got {}",
+            lua_value_to_string_descriptive(&output)
+        ))),
+    }
+}
+
 fn lua_internal_library() -> &'static str {
     r#"
 
@@ -731,17 +907,161 @@ fn crunch_tables_metadata(
 fn validate_data(res: &mut AllData) -> Result<(), DatabaseValidationError> {
     assert_row_vector_lengths_are_equal_for_all_tables(res);
 
+    // relations computed for datalog materialized views don't have view rows
+    res.foreign_keys_map.clear();
+    res.parent_child_keys_map.clear();
+
     ensure_child_foreign_keys_are_restricted(res)?;
     ensure_no_nan_or_infinity_floats(res)?;
-    ensure_primary_keys_unique_per_table_and_fkeys_exist(res)?;
-    ensure_parent_primary_keys_exist_for_children(res)?;
-    ensure_child_primary_keys_unique_per_table_and_fkeys_exist(res)?;
+    ensure_primary_keys_unique_per_table_and_fkeys_exist(res, true)?;
+    ensure_parent_primary_keys_exist_for_children(res, true)?;
+    ensure_child_primary_keys_unique_per_table_and_fkeys_exist(res, true)?;
     ensure_uniq_constaints_are_not_violated(res)?;
     ensure_row_checks(res)?;
+    ensure_row_constant_names_unique(res)?;
+    ensure_ancestor_descendant_columns_dont_conflict(res)?;
+
+    Ok(())
+}
+
+/// Row relations for datalog materialized views, computed from tables
+/// before views and computed again in validation once views have rows,
+/// relations to views are skipped because views don't have rows yet
+#[cfg(feature = "datalog")]
+fn compute_row_relations(res: &mut AllData) -> Result<(), DatabaseValidationError> {
+    ensure_child_foreign_keys_are_restricted(res)?;
+    ensure_primary_keys_unique_per_table_and_fkeys_exist(res, false)?;
+    ensure_parent_primary_keys_exist_for_children(res, false)?;
+    ensure_child_primary_keys_unique_per_table_and_fkeys_exist(res, false)
+}
+
+fn ensure_row_constant_names_unique(res: &AllData) -> Result<(), DatabaseValidationError> {
+    // constants of all tables share one namespace in generated code,
+    // table disk with key manufacturer_intel collides with
+    // table disk_manufacturer with key intel
+    let mut all_names: HashMap<String, &DBIdentifier> = HashMap::new();
+    for t in &res.tables {
+        let mut names = HashSet::new();
+        for name in t.row_constant_names() {
+            if !names.insert(name.clone()) {
+                return Err(DatabaseValidationError::RowConstantsDuplicateName {
+                    table_name: t.name.as_str().to_string(),
+                    constant_name: name,
+                });
+            }
+        }
+
+        for name in names {
+            if let Some(other) = all_names.insert(name.clone(), &t.name) {
+                return Err(DatabaseValidationError::RowConstantsNameCollisionBetweenTables {
+                    constant_name: name,
+                    first_table_name: other.as_str().to_string(),
+                    second_table_name: t.name.as_str().to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Tables with ancestors above their parent get ancestor_<table> columns and their
+/// ancestors get descendants_<table> columns, user columns must not clash
+fn ensure_ancestor_descendant_columns_dont_conflict(
+    res: &AllData,
+) -> Result<(), DatabaseValidationError> {
+    for t in &res.tables {
+        let ancestors = res.all_parent_tables(t);
+        if ancestors.len() < 2 {
+            continue;
+        }
+
+        for ancestor in &ancestors[0..ancestors.len() - 1] {
+            let generated = format!("ancestor_{}", ancestor.as_str());
+            if t.columns.iter().any(|c| c.column_name.as_str() == generated) {
+                return Err(DatabaseValidationError::AncestorDescendantColumnConflict {
+                    table_name: t.name.as_str().to_string(),
+                    column_name: generated,
+                    descendant_table: t.name.as_str().to_string(),
+                });
+            }
+
+            let generated = format!("descendants_{}", t.name.as_str());
+            let ancestor_table = &res.tables[res.find_table_named_idx(ancestor)[0]];
+            if ancestor_table.columns.iter().any(|c| c.column_name.as_str() == generated) {
+                return Err(DatabaseValidationError::AncestorDescendantColumnConflict {
+                    table_name: ancestor.as_str().to_string(),
+                    column_name: generated,
+                    descendant_table: t.name.as_str().to_string(),
+                });
+            }
+        }
+    }
 
     Ok(())
 }
 
+fn compute_ancestor_descendant_keys(res: &mut AllData) {
+    let mut computed = HashMap::new();
+
+    for t in &res.tables {
+        let ancestors = res.all_parent_tables(t);
+        if ancestors.len() < 2 {
+            continue;
+        }
+
+        let direct_parent = ancestors.last().unwrap();
+        let mut ancestor_indexes = res
+            .parent_child_keys_map
+            .get(&ParentKeyRelationship {
+                parent_table: direct_parent.clone(),
+                child_table: t.name.clone(),
+            })
+            .unwrap()
+            .parents_for_children_index
+            .clone();
+
+        // walk up the hierarchy one level at a time from the direct parent
+        for level in (0..ancestors.len() - 1).rev() {
+            let upper_index = &res
+                .parent_child_keys_map
+                .get(&ParentKeyRelationship {
+                    parent_table: ancestors[level].clone(),
+                    child_table: ancestors[level + 1].clone(),
+                })
+                .unwrap()
+                .parents_for_children_index;
+            for idx in ancestor_indexes.iter_mut() {
+                *idx = upper_index[*idx];
+            }
+
+            let ancestor_len = res
+                .tables
+                .iter()
+                .find(|i| i.name == ancestors[level])
+                .unwrap()
+                .len();
+            let mut descendant_indexes = vec![Vec::new(); ancestor_len];
+            for (descendant_idx, ancestor_idx) in ancestor_indexes.iter().enumerate() {
+                descendant_indexes[*ancestor_idx].push(descendant_idx);
+            }
+
+            let _ = computed.insert(
+                ParentKeyRelationship {
+                    parent_table: ancestors[level].clone(),
+                    child_table: t.name.clone(),
+                },
+                ParentKeyRelationshipData {
+                    parents_for_children_index: ancestor_indexes.clone(),
+                    children_for_parents_index: descendant_indexes,
+                },
+            );
+        }
+    }
+
+    res.ancestor_descendant_keys_map = computed;
+}
+
 fn assert_row_vector_lengths_are_equal_for_all_tables(res: &AllData) {
     // internal error, if this triggers we screwed up, not the client
     for t in &res.tables {
@@ -772,14 +1092,67 @@ fn maybe_prepare_datalog_data(
     res: &mut AllData,
     so: &SourceOutputs,
 ) -> Result<(), DatabaseValidationError> {
-    use asdi::edb::{Attribute, AttributeKind, Constant, Number};
-
     if so.datalog_proofs().is_empty() {
         return Ok(());
     }
 
     let mut db = res.datalog_db.lock().unwrap();
 
+    datalog_column_facts(&mut db, &res.tables);
+    datalog_relation_facts(&mut db, res);
+
+    Ok(())
+}
+
+/// Row relation facts and prelude rules over them
+#[cfg(feature = "datalog")]
+fn datalog_relation_facts(db: &mut asdi::Program, res: &AllData) {
+    use asdi::edb::{Attribute, Constant, Number};
+
+    // relations between rows:
+    // fk_<table_name>__<column_name>(<tuple_index>, <referred_tuple_index>)
+    // parent_<table_name>(<tuple_index>, <parent_tuple_index>)
+    let row_pairs = |db: &mut asdi::Program, fact_name: &str, pairs: &[usize]| {
+        let pred = db.predicates().fetch(fact_name).unwrap();
+        let fact = db
+            .add_new_intensional_relation(pred, vec![Attribute::integer(), Attribute::integer()])
+            .unwrap();
+        for (idx, other) in pairs.iter().enumerate() {
+            fact.add_as_fact(vec![
+                Constant::Number(Number::from_i64(idx as i64)),
+                Constant::Number(Number::from_i64(*other as i64)),
+            ])
+            .unwrap();
+        }
+    };
+
+    for (k, v) in &res.foreign_keys_map {
+        let fact_name = format!("fk_{}__{}", k.referee_table.as_str(), k.referee_column.as_str());
+        row_pairs(db, &fact_name, &v.foreign_keys_data);
+    }
+
+    for (k, v) in &res.parent_child_keys_map {
+        let fact_name = format!("parent_{}", k.child_table.as_str());
+        row_pairs(db, &fact_name, &v.parents_for_children_index);
+    }
+
+    asdi::parse::parser::extend_program(datalog_prelude(res).as_str(), db)
+        .expect("Datalog prelude has bugs");
+}
+
+#[cfg(feature = "datalog")]
+fn new_datalog_program() -> asdi::Program {
+    let mut features = asdi::features::FeatureSet::default();
+    features.add_support_for(&asdi::features::FEATURE_COMPARISONS);
+    features.add_support_for(&asdi::features::FEATURE_CONSTRAINTS);
+    features.add_support_for(&asdi::features::FEATURE_NEGATION);
+    asdi::Program::new_with_features(features)
+}
+
+#[cfg(feature = "datalog")]
+fn datalog_column_facts(db: &mut asdi::Program, tables: &[DataTable]) {
+    use asdi::edb::{Attribute, AttributeKind, Constant, Number};
+
     // output data format in datalog:
     // t_<table_name>__<column_name>(<column_value>, <tuple_index>)
     //
@@ -800,7 +1173,7 @@ fn maybe_prepare_datalog_data(
     // t_person__name(doofus, 1).
     // ```
 
-    for table in &res.tables {
+    for table in tables {
         for column in &table.columns {
             let fact_name = format!("t_{}__{}", table.name.as_str(), column.column_name.as_str());
             let table_pred = db.predicates().fetch(fact_name.as_str()).unwrap();
@@ -875,8 +1248,61 @@ fn maybe_prepare_datalog_data(
             }
         }
     }
+}
 
-    Ok(())
+/// Relation predicates whose first term is row index of the table
+#[cfg(feature = "datalog")]
+fn datalog_row_relations(res: &AllData, table: &DataTable) -> HashSet<String> {
+    let tname = table.name.as_str();
+    let mut output = HashSet::new();
+    for k in res.foreign_keys_map.keys() {
+        if k.referee_table == table.name {
+            let _ = output.insert(format!("fk_{tname}__{}", k.referee_column.as_str()));
+        }
+    }
+
+    let mut parent = table.parent_table();
+    if parent.is_some() {
+        let _ = output.insert(format!("parent_{tname}"));
+    }
+    while let Some(p) = parent {
+        let _ = output.insert(format!("ancestor_{tname}_{}", p.as_str()));
+        parent = res.tables[res.find_table_named_idx(&p)[0]].parent_table();
+    }
+
+    output
+}
+
+/// Standard rules available to every datalog proof, every table
+/// gets ancestor_<table_name>_<ancestor_table>(<tuple_index>, <ancestor_tuple_index>)
+/// for all tables above it
+#[cfg(feature = "datalog")]
+fn datalog_prelude(res: &AllData) -> String {
+    let mut output = String::new();
+    for t in res.tables_sorted() {
+        let tname = t.name.as_str();
+        let mut prev: Option<DBIdentifier> = None;
+        let mut parent = t.parent_table();
+        while let Some(p) = parent {
+            let pname = p.as_str();
+            match &prev {
+                None => {
+                    output += &format!("ancestor_{tname}_{pname}(R, A) :- parent_{tname}(R, A).\n");
+                }
+                Some(prev) => {
+                    let prev = prev.as_str();
+                    output += &format!(
+                        "ancestor_{tname}_{pname}(R, A) :- ancestor_{tname}_{prev}(R, P), parent_{prev}(P, A).\n"
+                    );
+                }
+            }
+            let next = res.tables[res.find_table_named_idx(&p)[0]].parent_table();
+            prev = Some(p);
+            parent = next;
+        }
+    }
+
+    output
 }
 
 #[cfg(feature = "datalog")]
@@ -894,6 +1320,7 @@ fn run_datalog_proofs(
     }
 
     let mut db = res.datalog_db.lock().unwrap();
+    let mut warnings = Vec::new();
 
     for (idx, proof) in so.datalog_proofs().iter().enumerate() {
         let dbi = DBIdentifier::new(proof.output_table_name.as_str())?;
@@ -911,9 +1338,11 @@ fn run_datalog_proofs(
         let pre_extension_rules_count = db.rules_ordered().len();
 
         let offender_rule_header = "OUTPUT(Offender)";
+        // explanation variables may follow offender like OUTPUT(Offender, Reason)
+        let offender_rule_prefix = "OUTPUT(Offender";
         let outputs_count = proof
             .expression
-            .match_indices(offender_rule_header)
+            .match_indices(offender_rule_prefix)
             .collect::<Vec<_>>();
 
         if outputs_count.is_empty() {
@@ -937,13 +1366,14 @@ fn run_datalog_proofs(
         }
 
         let offender_rule_label = format!("datalog_proof_{idx}");
-        let offender_rule_full_header = format!("{offender_rule_label}(Offender)");
+        let offender_rule_full_prefix = format!("{offender_rule_label}(Offender");
         let target_table_prefix_name = format!("t_{}__", proof.output_table_name);
+        let target_row_relations = datalog_row_relations(res, &res.tables[target_table]);
 
         let replaced_rule = proof
             .expression
             .as_str()
-            .replace(offender_rule_header, &offender_rule_full_header);
+            .replace(offender_rule_prefix, &offender_rule_full_prefix);
 
         asdi::parse::parser::extend_program(replaced_rule.as_str(), &mut db).map_err(|e| {
             DatabaseValidationError::DatalogProofQueryParseError {
@@ -972,6 +1402,7 @@ fn run_datalog_proofs(
             &rules_post_parse[pre_extension_rules_count..post_extension_rules_count];
 
         let mut the_query: Vec<asdi::idb::query::Query> = Vec::with_capacity(1);
+        let mut explanation_variables: Vec<String> = Vec::new();
         for nr in new_rule_slice {
             let head = nr.head().collect::<Vec<_>>();
             if head.len() == 1 {
@@ -981,12 +1412,12 @@ fn run_datalog_proofs(
 
                     let vars = head[0].variables().collect::<Vec<_>>();
                     if nr.form() != RuleForm::Pure
-                        || vars.len() != 1
+                        || vars.is_empty()
                         || vars[0].to_string() != "Offender"
                     {
                         // probably no one will ever get here but who knows?
                         return Err(DatabaseValidationError::DatalogProofBadOutputRuleFormat {
-                            error: "There must be only one variable in output rule named Offender."
+                            error: "First variable in output rule must be named Offender."
                                 .to_string(),
                             table_name: proof.output_table_name.as_str().to_string(),
                             comment: proof.comment.clone(),
@@ -1011,6 +1442,15 @@ fn run_datalog_proofs(
                                             found_expected_table = true;
                                         }
                                     }
+                                } else if target_row_relations.contains(&t_name) {
+                                    // relations are in form `parent_<table_name>(Index, ParentIndex)`
+                                    let terms = r.terms();
+                                    if terms[0].is_variable() {
+                                        let vname = terms[0].as_variable().unwrap().to_string();
+                                        if vname == "Offender" {
+                                            found_expected_table = true;
+                                        }
+                                    }
                                 }
                             }
                             asdi::idb::LiteralInner::Arithmetic(_) => {}
@@ -1026,11 +1466,16 @@ fn run_datalog_proofs(
                         });
                     }
 
+                    explanation_variables = vars[1..].iter().map(|v| v.to_string()).collect();
                     let atom = Atom::new(
                         head[0].label_ref(),
-                        vec![asdi::idb::Term::Variable(
-                            db.variables().fetch("Offender").unwrap(),
-                        )],
+                        vars.iter()
+                            .map(|v| {
+                                asdi::idb::Term::Variable(
+                                    db.variables().fetch(v.to_string()).unwrap(),
+                                )
+                            })
+                            .collect::<Vec<_>>(),
                     );
                     let query = Query::from(atom);
                     the_query.push(query.clone());
@@ -1058,14 +1503,21 @@ fn run_datalog_proofs(
             Some(view) => {
                 let rows = asdi::Collection::iter(&view).collect::<Vec<_>>();
                 for i in &rows {
-                    assert_eq!(i.values().len(), 1);
+                    assert_eq!(i.values().len(), 1 + explanation_variables.len());
                     match &i.values()[0] {
                         asdi::edb::Constant::Number(n) => {
                             let idx = *n.as_integer().unwrap();
                             let output = res.tables[target_table]
                                 .row_as_pretty_json(idx as usize)
                                 .unwrap();
-                            offenders.push(output);
+                            let mut explanation = serde_json::Map::default();
+                            for (vname, value) in
+                                explanation_variables.iter().zip(&i.values()[1..])
+                            {
+                                let _ = explanation
+                                    .insert(vname.clone(), datalog_constant_to_json(value));
+                            }
+                            offenders.push((idx as usize, output, explanation));
                         }
                         _ => {
                             panic!("Only number should appear here as data is generated.")
@@ -1080,200 +1532,550 @@ fn run_datalog_proofs(
             }
         }
 
+        let offender_idxs = offenders.iter().map(|(idx, ..)| *idx).collect::<Vec<_>>();
+        if proof.quantifier != ProofQuantifier::None {
+            let failure =
+                quantified_proof_failure(proof, &res.tables[target_table], &offender_idxs)?;
+            if let Some(err) = failure {
+                match proof.severity {
+                    ProofSeverity::Error => return Err(err),
+                    ProofSeverity::Warn => warnings.push(err),
+                }
+            }
+            continue;
+        }
+
+        let excused =
+            proof_excused_offenders(proof, &res.tables[target_table], &offender_idxs)?;
+        offenders.retain(|(idx, ..)| !excused.contains(idx));
+
         if !offenders.is_empty() {
+            let mut offenders = offenders
+                .into_iter()
+                .map(|(_, o, e)| {
+                    let e = if explanation_variables.is_empty() {
+                        None
+                    } else {
+                        Some(serde_json::to_string_pretty(&e).unwrap())
+                    };
+                    (o, e)
+                })
+                .collect::<Vec<_>>();
             offenders.sort();
-            return Err(DatabaseValidationError::DatalogProofOffendersFound {
+            let (offending_columns, offender_explanations): (Vec<_>, Vec<_>) =
+                offenders.into_iter().unzip();
+            let err = DatabaseValidationError::DatalogProofOffendersFound {
                 table_name: proof.output_table_name.as_str().to_string(),
                 comment: proof.comment.clone(),
                 proof_expression: proof.expression.clone(),
-                offending_columns: offenders,
-            });
+                offending_columns,
+                offender_explanations: offender_explanations.into_iter().flatten().collect(),
+            };
+            match proof.severity {
+                ProofSeverity::Error => return Err(err),
+                ProofSeverity::Warn => warnings.push(err),
+            }
         }
     }
 
+    drop(db);
+    res.proof_warnings.extend(warnings);
+
     Ok(())
 }
 
-fn compute_materialized_views(res: &mut AllData) -> Result<(), DatabaseValidationError> {
-    let no_mat_views = !res.tables.iter().any(|i| i.mat_view_expression.is_some());
-    if no_mat_views {
-        return Ok(());
+#[cfg(feature = "datalog")]
+fn datalog_constant_to_json(c: &asdi::edb::Constant) -> Value {
+    match c {
+        asdi::edb::Constant::String(s) => Value::String(s.clone()),
+        asdi::edb::Constant::Boolean(b) => Value::Bool(*b),
+        asdi::edb::Constant::Number(n) => match n.as_integer() {
+            Some(i) => Value::Number((*i).into()),
+            None => serde_json::Number::from_f64(*n.as_float().unwrap())
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+        },
     }
+}
 
-    let conn_ro = res.sqlite_db.ro.lock().unwrap();
-    let mut conn_rw = res.sqlite_db.rw.lock().unwrap();
+#[cfg(not(feature = "datalog"))]
+fn datalog_mat_view_rows(
+    _res: &AllData,
+    _mview: &DataTable,
+    _expr: &str,
+) -> Result<Vec<Vec<String>>, DatabaseValidationError> {
+    Err(DatabaseValidationError::DatalogIsDisabled {
+        explanation: "EdenDB was compiled without datalog support, please recompile EdenDB with 'datalog' feature if you need it.".to_string(),
+    })
+}
 
-    for mview in &mut res.tables {
-        if let Some(expr) = &mview.mat_view_expression {
-            let mut stmt = conn_ro.prepare(expr.as_str()).map_err(|e| {
-                DatabaseValidationError::SqlMatViewStatementPrepareException {
-                    table_name: mview.name.as_str().to_string(),
-                    sql_expression: expr.clone(),
-                    error: e.to_string(),
-                }
-            })?;
+/// Evaluate datalog materialized view in a separate program which
+/// sees column facts and row relations of all the tables computed so far
+#[cfg(feature = "datalog")]
+fn datalog_mat_view_rows(
+    res: &AllData,
+    mview: &DataTable,
+    expr: &str,
+) -> Result<Vec<Vec<String>>, DatabaseValidationError> {
+    use asdi::{
+        edb::Constant,
+        idb::{query::Query, Atom, RuleForm},
+        Labeled,
+    };
 
-            let column_count = stmt.column_count();
-            if column_count != mview.columns.len() {
-                return Err(DatabaseValidationError::SqlMatViewWrongColumnCount {
-                    table_name: mview.name.as_str().to_string(),
-                    sql_expression: expr.clone(),
-                    expected_columns: mview.columns.len(),
-                    actual_columns: column_count,
-                });
-            }
+    let output_rule_prefix = "OUTPUT(";
+    let output_rule_label = "mat_view_output";
+    if !expr.contains(output_rule_prefix) {
+        return Err(DatabaseValidationError::DatalogMatViewOutputRuleNotFound {
+            error: "Datalog materialized view must contain output rule in the format like 'OUTPUT(A, B) :- t_some_table__a(A, Row), t_some_table__b(B, Row).'".to_string(),
+            table_name: mview.name.as_str().to_string(),
+            datalog_expression: expr.to_string(),
+        });
+    }
 
-            let mut rows = stmt.query(rusqlite::params![]).map_err(|e| {
-                DatabaseValidationError::SqlMatViewStatementInitException {
-                    table_name: mview.name.as_str().to_string(),
-                    sql_expression: expr.clone(),
-                    error: e.to_string(),
-                }
-            })?;
+    let mut db = new_datalog_program();
+    datalog_column_facts(&mut db, &res.tables);
+    datalog_relation_facts(&mut db, res);
 
-            let mut outputs: Vec<Vec<String>> = Vec::new();
-            while let Some(output) = rows.next().map_err(|e| {
-                DatabaseValidationError::SqlMatViewStatementQueryException {
-                    table_name: mview.name.as_str().to_string(),
-                    sql_expression: expr.clone(),
-                    error: e.to_string(),
-                }
-            })? {
-                let mut this_row = Vec::with_capacity(column_count);
-                for column in 0..column_count {
-                    let ctype = mview.columns[column].data.column_type();
-                    match output.get_ref_unwrap(column) {
-                        rusqlite::types::ValueRef::Null => {
-                            return Err(DatabaseValidationError::SqlMatViewNullReturnsUnsupported {
-                                table_name: mview.name.as_str().to_string(),
-                                sql_expression: expr.clone(),
-                                column_name: mview.columns[column].column_name.as_str().to_string(),
-                                return_row_index: outputs.len() + 1,
-                            })
-                        }
-                        rusqlite::types::ValueRef::Integer(i) => {
-                            let accepted_types =
-                                [DBType::Int, DBType::Float, DBType::Text, DBType::Bool];
-                            if !accepted_types.contains(&ctype) {
-                                return Err(
-                                    DatabaseValidationError::SqlMatViewWrongColumnTypeReturned {
-                                        table_name: mview.name.as_str().to_string(),
-                                        sql_expression: expr.clone(),
-                                        column_name: mview.columns[column]
-                                            .column_name
-                                            .as_str()
-                                            .to_string(),
-                                        return_row_index: outputs.len() + 1,
-                                        actual_column_type: "INT".to_string(),
-                                        expected_column_type: ctype,
-                                    },
-                                );
-                            }
-                            this_row.push(i.to_string())
-                        }
-                        rusqlite::types::ValueRef::Real(i) => {
-                            let accepted_types = [DBType::Float, DBType::Text];
-                            if !accepted_types.contains(&ctype) {
-                                return Err(
-                                    DatabaseValidationError::SqlMatViewWrongColumnTypeReturned {
-                                        table_name: mview.name.as_str().to_string(),
-                                        sql_expression: expr.clone(),
-                                        column_name: mview.columns[column]
-                                            .column_name
-                                            .as_str()
-                                            .to_string(),
-                                        return_row_index: outputs.len() + 1,
-                                        actual_column_type: "FLOAT".to_string(),
-                                        expected_column_type: ctype,
-                                    },
-                                );
-                            }
-                            this_row.push(i.to_string())
-                        }
-                        rusqlite::types::ValueRef::Text(i) => {
-                            let accepted_types = [DBType::Text];
-                            if !accepted_types.contains(&ctype) {
-                                return Err(
-                                    DatabaseValidationError::SqlMatViewWrongColumnTypeReturned {
-                                        table_name: mview.name.as_str().to_string(),
-                                        sql_expression: expr.clone(),
-                                        column_name: mview.columns[column]
-                                            .column_name
-                                            .as_str()
-                                            .to_string(),
-                                        return_row_index: outputs.len() + 1,
-                                        actual_column_type: "TEXT".to_string(),
-                                        expected_column_type: mview.columns[column]
-                                            .data
-                                            .column_type(),
-                                    },
-                                );
-                            }
-                            this_row.push(String::from_utf8(i.to_vec()).unwrap())
-                        }
-                        rusqlite::types::ValueRef::Blob(_) => {
-                            panic!("Binary blobs of sqlite are not supported");
+    let replaced_rule = expr.replace(output_rule_prefix, &format!("{output_rule_label}("));
+    asdi::parse::parser::extend_program(replaced_rule.as_str(), &mut db).map_err(|e| {
+        DatabaseValidationError::DatalogMatViewQueryParseError {
+            error: e.to_string(),
+            table_name: mview.name.as_str().to_string(),
+            datalog_expression: expr.to_string(),
+        }
+    })?;
+
+    let mut the_query: Option<Query> = None;
+    for nr in db.rules_ordered() {
+        let head = nr.head().collect::<Vec<_>>();
+        if head.len() != 1 || head[0].label().to_string() != output_rule_label {
+            continue;
+        }
+
+        let vars = head[0].variables().collect::<Vec<_>>();
+        let distinct_vars = vars.iter().map(|v| v.to_string()).collect::<HashSet<_>>();
+        if nr.form() != RuleForm::Pure
+            || vars.len() != head[0].terms().len()
+            || distinct_vars.len() != vars.len()
+        {
+            return Err(DatabaseValidationError::DatalogMatViewBadOutputRuleFormat {
+                error: "Output rule must have only distinct variables in its head.".to_string(),
+                table_name: mview.name.as_str().to_string(),
+                datalog_expression: expr.to_string(),
+            });
+        }
+
+        if vars.len() != mview.columns.len() {
+            return Err(DatabaseValidationError::DatalogMatViewWrongColumnCount {
+                table_name: mview.name.as_str().to_string(),
+                datalog_expression: expr.to_string(),
+                expected_columns: mview.columns.len(),
+                actual_columns: vars.len(),
+            });
+        }
+
+        // rest of the output rules, if any, must have the same arity
+        // and are unioned into the same relation
+        if the_query.is_none() {
+            let atom = Atom::new(
+                head[0].label_ref(),
+                vars.iter()
+                    .map(|v| {
+                        asdi::idb::Term::Variable(db.variables().fetch(v.to_string()).unwrap())
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            the_query = Some(Query::from(atom));
+        }
+    }
+
+    let the_query = the_query.ok_or_else(|| {
+        DatabaseValidationError::DatalogMatViewOutputRuleNotFound {
+            error: "No OUTPUT rule found in datalog materialized view".to_string(),
+            table_name: mview.name.as_str().to_string(),
+            datalog_expression: expr.to_string(),
+        }
+    })?;
+
+    let evaluator = asdi::idb::eval::NaiveEvaluator {};
+    let view = db.eval_query_with(&the_query, evaluator).map_err(|e| {
+        DatabaseValidationError::DatalogMatViewQueryingFailure {
+            error: e.to_string(),
+            table_name: mview.name.as_str().to_string(),
+            datalog_expression: expr.to_string(),
+        }
+    })?;
+
+    let mut outputs: Vec<Vec<String>> = Vec::new();
+    let rows = match &view {
+        Some(view) => asdi::Collection::iter(view).collect::<Vec<_>>(),
+        None => Vec::new(),
+    };
+    for row in rows {
+        let mut this_row = Vec::with_capacity(mview.columns.len());
+        for (column, value) in row.values().iter().enumerate() {
+            let ctype = mview.columns[column].data.column_type();
+            let (accepted_types, actual_column_type, output): (&[DBType], &str, String) =
+                match value {
+                    Constant::String(s) => (&[DBType::Text], "TEXT", s.clone()),
+                    // bools are stored as in sqlite
+                    Constant::Boolean(b) => {
+                        (&[DBType::Bool], "BOOL", if *b { "1" } else { "0" }.to_string())
+                    }
+                    Constant::Number(n) => match n.as_integer() {
+                        Some(i) => {
+                            (&[DBType::Int, DBType::Float, DBType::Text], "INT", i.to_string())
                         }
+                        None => (
+                            &[DBType::Float, DBType::Text],
+                            "FLOAT",
+                            n.as_float().unwrap().to_string(),
+                        ),
+                    },
+                };
+
+            if !accepted_types.contains(&ctype) {
+                return Err(DatabaseValidationError::DatalogMatViewWrongColumnTypeReturned {
+                    table_name: mview.name.as_str().to_string(),
+                    datalog_expression: expr.to_string(),
+                    column_name: mview.columns[column].column_name.as_str().to_string(),
+                    return_row_index: outputs.len() + 1,
+                    actual_column_type: actual_column_type.to_string(),
+                    expected_column_type: ctype,
+                });
+            }
+
+            this_row.push(output);
+        }
+        outputs.push(this_row);
+    }
+
+    Ok(outputs)
+}
+
+fn compute_materialized_views(res: &mut AllData) -> Result<(), DatabaseValidationError> {
+    let no_mat_views = !res.tables.iter().any(|i| i.mat_view_expression.is_some());
+    if no_mat_views {
+        return Ok(());
+    }
+
+    #[cfg(feature = "datalog")]
+    if res.tables.iter().any(|i| {
+        i.mat_view_expression.is_some() && matches!(i.mat_view_language, ValidExpressions::Datalog)
+    }) {
+        compute_row_relations(res)?;
+    }
+
+    let conn_ro = res.sqlite_db.ro.lock().unwrap();
+    let mut conn_rw = res.sqlite_db.rw.lock().unwrap();
+
+    for idx in 0..res.tables.len() {
+        let mview = &res.tables[idx];
+        let mut outputs = match (&mview.mat_view_expression, mview.mat_view_language) {
+            (None, _) => continue,
+            (Some(expr), ValidExpressions::Datalog) => {
+                datalog_mat_view_rows(res, mview, expr)?
+            }
+            (Some(expr), ValidExpressions::Lua) => {
+                let lua = res.lua_runtime.lock().unwrap();
+                lua_mat_view_rows(&lua, res, mview, expr)?
+            }
+            (Some(expr), ValidExpressions::Sql) => sql_mat_view_rows(mview, expr, &conn_ro)?,
+        };
+
+        let output_rows_count = outputs.len();
+        outputs.sort();
+
+        let mview = &mut res.tables[idx];
+        // we assume mat view is empty now
+        assert_eq!(mview.len(), 0);
+
+        for column in 0..mview.columns.len() {
+            match &mut mview.columns[column].data {
+                ColumnVector::Strings(v) => {
+                    v.v.reserve_exact(output_rows_count);
+                    for row in 0..output_rows_count {
+                        v.v.push(outputs[row][column].clone());
+                    }
+                }
+                ColumnVector::Ints(v) => {
+                    v.v.reserve_exact(output_rows_count);
+                    for row in 0..output_rows_count {
+                        v.v.push(outputs[row][column].parse::<i64>().unwrap());
+                    }
+                }
+                ColumnVector::Floats(v) => {
+                    v.v.reserve_exact(output_rows_count);
+                    for row in 0..output_rows_count {
+                        v.v.push(outputs[row][column].parse::<f64>().unwrap());
+                    }
+                }
+                ColumnVector::Bools(v) => {
+                    v.v.reserve_exact(output_rows_count);
+                    for row in 0..output_rows_count {
+                        // in sqlite 1 is true and 0 is false
+                        let to_insert = match outputs[row][column].as_str() {
+                            "1" => true,
+                            "0" => false,
+                            v => {
+                                panic!(
+                                    "Unexpected sqlite value returned when wanting a bool: {}",
+                                    v
+                                )
+                            }
+                        };
+                        v.v.push(to_insert);
                     }
                 }
-                outputs.push(this_row);
             }
+        }
+
+        // other mat views may depend on this mat view
+        insert_sqlite_data(mview, &mut conn_rw)?;
+    }
 
-            let output_rows_count = outputs.len();
-            outputs.sort();
+    Ok(())
+}
 
-            drop(rows);
-            drop(stmt);
+fn sql_mat_view_rows(
+    mview: &DataTable,
+    expr: &str,
+    conn_ro: &rusqlite::Connection,
+) -> Result<Vec<Vec<String>>, DatabaseValidationError> {
+    let mut stmt = conn_ro.prepare(expr).map_err(|e| {
+        DatabaseValidationError::SqlMatViewStatementPrepareException {
+            table_name: mview.name.as_str().to_string(),
+            sql_expression: expr.to_string(),
+            error: e.to_string(),
+        }
+    })?;
 
-            // we assume mat view is empty now
-            assert_eq!(mview.len(), 0);
+    let column_count = stmt.column_count();
+    if column_count != mview.columns.len() {
+        return Err(DatabaseValidationError::SqlMatViewWrongColumnCount {
+            table_name: mview.name.as_str().to_string(),
+            sql_expression: expr.to_string(),
+            expected_columns: mview.columns.len(),
+            actual_columns: column_count,
+        });
+    }
 
-            for column in 0..column_count {
-                match &mut mview.columns[column].data {
-                    ColumnVector::Strings(v) => {
-                        v.v.reserve_exact(output_rows_count);
-                        for row in 0..output_rows_count {
-                            v.v.push(outputs[row][column].clone());
-                        }
-                    }
-                    ColumnVector::Ints(v) => {
-                        v.v.reserve_exact(output_rows_count);
-                        for row in 0..output_rows_count {
-                            v.v.push(outputs[row][column].parse::<i64>().unwrap());
-                        }
+    let mut rows = stmt.query(rusqlite::params![]).map_err(|e| {
+        DatabaseValidationError::SqlMatViewStatementInitException {
+            table_name: mview.name.as_str().to_string(),
+            sql_expression: expr.to_string(),
+            error: e.to_string(),
+        }
+    })?;
+
+    let mut outputs: Vec<Vec<String>> = Vec::new();
+    while let Some(output) = rows.next().map_err(|e| {
+        DatabaseValidationError::SqlMatViewStatementQueryException {
+            table_name: mview.name.as_str().to_string(),
+            sql_expression: expr.to_string(),
+            error: e.to_string(),
+        }
+    })? {
+        let mut this_row = Vec::with_capacity(column_count);
+        for column in 0..column_count {
+            let ctype = mview.columns[column].data.column_type();
+            match output.get_ref_unwrap(column) {
+                rusqlite::types::ValueRef::Null => {
+                    return Err(DatabaseValidationError::SqlMatViewNullReturnsUnsupported {
+                        table_name: mview.name.as_str().to_string(),
+                        sql_expression: expr.to_string(),
+                        column_name: mview.columns[column].column_name.as_str().to_string(),
+                        return_row_index: outputs.len() + 1,
+                    })
+                }
+                rusqlite::types::ValueRef::Integer(i) => {
+                    let accepted_types =
+                        [DBType::Int, DBType::Float, DBType::Text, DBType::Bool];
+                    if !accepted_types.contains(&ctype) {
+                        return Err(
+                            DatabaseValidationError::SqlMatViewWrongColumnTypeReturned {
+                                table_name: mview.name.as_str().to_string(),
+                                sql_expression: expr.to_string(),
+                                column_name: mview.columns[column]
+                                    .column_name
+                                    .as_str()
+                                    .to_string(),
+                                return_row_index: outputs.len() + 1,
+                                actual_column_type: "INT".to_string(),
+                                expected_column_type: ctype,
+                            },
+                        );
                     }
-                    ColumnVector::Floats(v) => {
-                        v.v.reserve_exact(output_rows_count);
-                        for row in 0..output_rows_count {
-                            v.v.push(outputs[row][column].parse::<f64>().unwrap());
-                        }
+                    this_row.push(i.to_string())
+                }
+                rusqlite::types::ValueRef::Real(i) => {
+                    let accepted_types = [DBType::Float, DBType::Text];
+                    if !accepted_types.contains(&ctype) {
+                        return Err(
+                            DatabaseValidationError::SqlMatViewWrongColumnTypeReturned {
+                                table_name: mview.name.as_str().to_string(),
+                                sql_expression: expr.to_string(),
+                                column_name: mview.columns[column]
+                                    .column_name
+                                    .as_str()
+                                    .to_string(),
+                                return_row_index: outputs.len() + 1,
+                                actual_column_type: "FLOAT".to_string(),
+                                expected_column_type: ctype,
+                            },
+                        );
                     }
-                    ColumnVector::Bools(v) => {
-                        v.v.reserve_exact(output_rows_count);
-                        for row in 0..output_rows_count {
-                            // in sqlite 1 is true and 0 is false
-                            let to_insert = match outputs[row][column].as_str() {
-                                "1" => true,
-                                "0" => false,
-                                v => {
-                                    panic!(
-                                        "Unexpected sqlite value returned when wanting a bool: {}",
-                                        v
-                                    )
-                                }
-                            };
-                            v.v.push(to_insert);
-                        }
+                    this_row.push(i.to_string())
+                }
+                rusqlite::types::ValueRef::Text(i) => {
+                    let accepted_types = [DBType::Text];
+                    if !accepted_types.contains(&ctype) {
+                        return Err(
+                            DatabaseValidationError::SqlMatViewWrongColumnTypeReturned {
+                                table_name: mview.name.as_str().to_string(),
+                                sql_expression: expr.to_string(),
+                                column_name: mview.columns[column]
+                                    .column_name
+                                    .as_str()
+                                    .to_string(),
+                                return_row_index: outputs.len() + 1,
+                                actual_column_type: "TEXT".to_string(),
+                                expected_column_type: mview.columns[column]
+                                    .data
+                                    .column_type(),
+                            },
+                        );
                     }
+                    this_row.push(String::from_utf8(i.to_vec()).unwrap())
+                }
+                rusqlite::types::ValueRef::Blob(_) => {
+                    panic!("Binary blobs of sqlite are not supported");
                 }
             }
+        }
+        outputs.push(this_row);
+    }
+
+    Ok(outputs)
+}
 
-            // other mat views may depend on this mat view
-            insert_sqlite_data(mview, &mut conn_rw)?;
+/// Lua materialized view gets the same read only `db` api as lua proofs
+/// and returns array of rows keyed by column name
+fn lua_mat_view_rows(
+    lua: &mlua::Lua,
+    res: &AllData,
+    mview: &DataTable,
+    expr: &str,
+) -> Result<Vec<Vec<String>>, DatabaseValidationError> {
+    let eval_err = |e: mlua::Error| DatabaseValidationError::LuaMatViewEvaluationError {
+        table_name: mview.name.as_str().to_string(),
+        lua_expression: expr.to_string(),
+        error: e.to_string(),
+    };
+    let return_type_err = |error: String| DatabaseValidationError::LuaMatViewUnexpectedReturnType {
+        table_name: mview.name.as_str().to_string(),
+        lua_expression: expr.to_string(),
+        error,
+    };
+
+    let api = lua_proof_api(lua, res).map_err(eval_err)?;
+    // keep user code line numbers intact
+    let source = format!("local db = ...; {}", expr);
+    let output: mlua::Value = lua
+        .load(&source)
+        .set_name(mview.name.as_str())
+        .and_then(|c| c.call(api))
+        .map_err(eval_err)?;
+
+    let rows = match output {
+        mlua::Value::Table(t) => t
+            .sequence_values::<mlua::Value>()
+            .map(|v| match v.map_err(eval_err)? {
+                mlua::Value::Table(t) => Ok(t),
+                other => Err(return_type_err(format!(
+                    "Materialized view rows must be tables keyed by column name, got {}",
+                    other.type_name()
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        other => {
+            return Err(return_type_err(format!(
+                "Lua materialized view must return array of rows, got {}",
+                other.type_name()
+            )));
         }
+    };
+
+    let mut outputs: Vec<Vec<String>> = Vec::with_capacity(rows.len());
+    for row in rows {
+        let return_row_index = outputs.len() + 1;
+        for pair in row.clone().pairs::<mlua::Value, mlua::Value>() {
+            let (k, _) = pair.map_err(eval_err)?;
+            let column_name = lua_value_to_string(&k);
+            if !mview.columns.iter().any(|c| c.column_name.as_str() == column_name) {
+                return Err(DatabaseValidationError::LuaMatViewUnknownColumn {
+                    table_name: mview.name.as_str().to_string(),
+                    lua_expression: expr.to_string(),
+                    column_name,
+                    return_row_index,
+                });
+            }
+        }
+
+        let mut this_row = Vec::with_capacity(mview.columns.len());
+        for column in &mview.columns {
+            let ctype = column.data.column_type();
+            let value: mlua::Value = row.get(column.column_name.as_str()).map_err(eval_err)?;
+            let (accepted_types, actual_column_type, output): (&[DBType], &str, String) =
+                match value {
+                    mlua::Value::Nil => {
+                        return Err(DatabaseValidationError::LuaMatViewColumnNotFound {
+                            table_name: mview.name.as_str().to_string(),
+                            lua_expression: expr.to_string(),
+                            column_name: column.column_name.as_str().to_string(),
+                            return_row_index,
+                        });
+                    }
+                    mlua::Value::Integer(i) => {
+                        (&[DBType::Int, DBType::Float, DBType::Text], "INT", i.to_string())
+                    }
+                    mlua::Value::Number(n) => {
+                        (&[DBType::Float, DBType::Text], "FLOAT", n.to_string())
+                    }
+                    mlua::Value::String(s) => {
+                        (&[DBType::Text], "TEXT", s.to_str().map_err(eval_err)?.to_string())
+                    }
+                    // bools are stored as in sqlite
+                    mlua::Value::Boolean(b) => {
+                        (&[DBType::Bool], "BOOL", if b { "1" } else { "0" }.to_string())
+                    }
+                    other => {
+                        return Err(return_type_err(format!(
+                            "Column {} value must be a string, number or boolean, got {}",
+                            column.column_name.as_str(),
+                            other.type_name()
+                        )));
+                    }
+                };
+
+            if !accepted_types.contains(&ctype) {
+                return Err(DatabaseValidationError::LuaMatViewWrongColumnTypeReturned {
+                    table_name: mview.name.as_str().to_string(),
+                    lua_expression: expr.to_string(),
+                    column_name: column.column_name.as_str().to_string(),
+                    return_row_index,
+                    actual_column_type: actual_column_type.to_string(),
+                    expected_column_type: ctype,
+                });
+            }
+
+            this_row.push(output);
+        }
+        outputs.push(this_row);
     }
 
-    Ok(())
+    Ok(outputs)
 }
 
 fn create_sqlite_table(
@@ -1307,7 +2109,7 @@ fn create_sqlite_table(
     Ok(())
 }
 
-fn insert_sqlite_data(
+pub(crate) fn insert_sqlite_data(
     table: &DataTable,
     conn: &mut rusqlite::Connection,
 ) -> Result<(), DatabaseValidationError> {
@@ -1416,10 +2218,8 @@ fn create_sqlite_indexes(
 fn maybe_insert_sqlite_data(
     res: &mut AllData,
     so: &SourceOutputs,
-    sqlite_needed: bool,
 ) -> Result<(), DatabaseValidationError> {
-    let sqlite_needed = sqlite_needed
-        || !so.sql_proofs().is_empty()
+    let sqlite_needed = !so.sql_proofs().is_empty()
         || res.tables.iter().any(|i| i.mat_view_expression.is_some());
 
     if !sqlite_needed {
@@ -1442,149 +2242,653 @@ fn maybe_insert_sqlite_data(
         create_sqlite_indexes(table, &conn)?;
     }
 
-    Ok(())
+    Ok(())
+}
+
+fn run_sqlite_proofs(res: &mut AllData, so: &SourceOutputs) -> Result<(), DatabaseValidationError> {
+    if so.sql_proofs().is_empty() {
+        return Ok(());
+    }
+
+    for proof in so.sql_proofs() {
+        let dbi = DBIdentifier::new(proof.output_table_name.as_str())?;
+        let tbl = res.find_table_named_idx(&dbi);
+
+        if tbl.is_empty() {
+            return Err(DatabaseValidationError::SqlProofTableNotFound {
+                table_name: proof.output_table_name.clone(),
+                proof_expression: proof.expression.clone(),
+                comment: proof.comment.clone(),
+            });
+        }
+    }
+
+    let conn = res.sqlite_db.ro.lock().unwrap();
+    let mut warnings = Vec::new();
+
+    for proof in so.sql_proofs() {
+        let mut stmt = conn.prepare(proof.expression.as_str()).map_err(|e| {
+            DatabaseValidationError::SqlProofQueryPlanningError {
+                error: e.to_string(),
+                table_name: proof.output_table_name.clone(),
+                proof_expression: proof.expression.clone(),
+                comment: proof.comment.clone(),
+            }
+        })?;
+
+        let column_names = stmt.column_names();
+        if column_names.is_empty() {
+            return Err(
+                DatabaseValidationError::SqlProofQueryErrorSingleRowIdColumnExpected {
+                    error: "Required output column count is at least 1, got 0".to_string(),
+                    table_name: proof.output_table_name.clone(),
+                    proof_expression: proof.expression.clone(),
+                    comment: proof.comment.clone(),
+                },
+            );
+        }
+
+        if column_names[0] != "rowid" {
+            return Err(
+                DatabaseValidationError::SqlProofQueryErrorSingleRowIdColumnExpected {
+                    error: format!(
+                        "Required output column name must be rowid, got {}",
+                        column_names[0]
+                    ),
+                    table_name: proof.output_table_name.clone(),
+                    proof_expression: proof.expression.clone(),
+                    comment: proof.comment.clone(),
+                },
+            );
+        }
+
+        // columns after rowid explain why row is offending
+        let explanation_columns = column_names[1..]
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>();
+
+        // I kinda wish rusqlite exposed this :(
+        let (query_tname, query_colname) = unsafe {
+            let raw_stmt = stmt.raw_stmt();
+            let raw_ptr = raw_stmt.ptr();
+            let tname_raw = sqlite3_column_table_name(raw_ptr, 0);
+            let cname_raw = sqlite3_column_origin_name(raw_ptr, 0);
+
+            let tname = if tname_raw.is_null() {
+                "NULL".to_string()
+            } else {
+                std::ffi::CStr::from_ptr(tname_raw)
+                    .to_string_lossy()
+                    .to_string()
+            };
+            let colname = if cname_raw.is_null() {
+                "NULL".to_string()
+            } else {
+                std::ffi::CStr::from_ptr(cname_raw)
+                    .to_string_lossy()
+                    .to_string()
+            };
+
+            (tname, colname)
+        };
+
+        if query_tname != proof.output_table_name || query_colname != "rowid" {
+            return Err(
+                DatabaseValidationError::SqlProofQueryColumnOriginMismatchesExpected {
+                    error: "Actual column origin table name or origin mistmaches expectations"
+                        .to_string(),
+                    expected_column_origin_table: proof.output_table_name.to_string(),
+                    expected_column_origin_name: "rowid".to_string(),
+                    actual_column_origin_table: query_tname,
+                    actual_column_origin_name: query_colname,
+                    proof_expression: proof.expression.to_string(),
+                    comment: proof.comment.clone(),
+                },
+            );
+        }
+
+        let mut offenders_res =
+            stmt.query([])
+                .map_err(|e| DatabaseValidationError::SqlProofQueryError {
+                    error: e.to_string(),
+                    table_name: proof.output_table_name.clone(),
+                    proof_expression: proof.expression.clone(),
+                    comment: proof.comment.clone(),
+                })?;
+
+        let mut offenders: Vec<usize> = Vec::new();
+        let mut explanations: Vec<String> = Vec::new();
+        while let Some(offender) =
+            offenders_res
+                .next()
+                .map_err(|e| DatabaseValidationError::SqlProofQueryError {
+                    error: e.to_string(),
+                    table_name: proof.output_table_name.clone(),
+                    proof_expression: proof.expression.clone(),
+                    comment: proof.comment.clone(),
+                })?
+        {
+            let res = offender.get::<usize, usize>(0).unwrap();
+            // rowid values are 1 based
+            offenders.push(res - 1);
+
+            if !explanation_columns.is_empty() {
+                let mut explanation = serde_json::Map::default();
+                for (idx, cname) in explanation_columns.iter().enumerate() {
+                    let value = sqlite_value_to_json(offender.get_ref(idx + 1).unwrap());
+                    let _ = explanation.insert(cname.clone(), value);
+                }
+                explanations.push(serde_json::to_string_pretty(&explanation).unwrap());
+            }
+        }
+
+        let dbi = DBIdentifier::new(proof.output_table_name.as_str())?;
+        let tbl = &res.tables[res.find_table_named_idx(&dbi)[0]];
+        if proof.quantifier != ProofQuantifier::None {
+            if let Some(err) = quantified_proof_failure(proof, tbl, &offenders)? {
+                match proof.severity {
+                    ProofSeverity::Error => return Err(err),
+                    ProofSeverity::Warn => warnings.push(err),
+                }
+            }
+            continue;
+        }
+
+        let excused = proof_excused_offenders(proof, tbl, &offenders)?;
+        if !excused.is_empty() {
+            if !explanations.is_empty() {
+                explanations = explanations
+                    .into_iter()
+                    .zip(&offenders)
+                    .filter(|(_, o)| !excused.contains(*o))
+                    .map(|(e, _)| e)
+                    .collect();
+            }
+            offenders.retain(|o| !excused.contains(o));
+        }
+
+        if !offenders.is_empty() {
+            let offenders_mapped = offenders
+                .into_iter()
+                .map(|o| tbl.row_as_pretty_json(o).unwrap())
+                .collect::<Vec<_>>();
+
+            let err = DatabaseValidationError::SqlProofOffendersFound {
+                table_name: proof.output_table_name.clone(),
+                proof_expression: proof.expression.clone(),
+                offending_columns: offenders_mapped,
+                offender_explanations: explanations,
+                comment: proof.comment.clone(),
+            };
+            match proof.severity {
+                ProofSeverity::Error => return Err(err),
+                ProofSeverity::Warn => warnings.push(err),
+            }
+        }
+    }
+
+    drop(conn);
+    res.proof_warnings.extend(warnings);
+
+    Ok(())
+}
+
+fn run_lua_proofs(res: &mut AllData, so: &SourceOutputs) -> Result<(), DatabaseValidationError> {
+    if so.lua_proofs().is_empty() {
+        return Ok(());
+    }
+
+    let mut warnings = Vec::new();
+    let lua = res.lua_runtime.lock().unwrap();
+    // rows are read only, so all proofs share the api built for the first one
+    let mut api: Option<mlua::Table> = None;
+
+    for proof in so.lua_proofs() {
+        let dbi = DBIdentifier::new(proof.output_table_name.as_str())?;
+        let tbl = res.find_table_named_idx(&dbi);
+        if tbl.is_empty() {
+            return Err(DatabaseValidationError::LuaProofTableNotFound {
+                table_name: proof.output_table_name.clone(),
+                comment: proof.comment.clone(),
+                proof_expression: proof.expression.clone(),
+            });
+        }
+        let tbl = &res.tables[tbl[0]];
+        if tbl.primary_key_column().is_none() {
+            return Err(DatabaseValidationError::LuaProofTableHasNoPrimaryKey {
+                table_name: proof.output_table_name.clone(),
+                comment: proof.comment.clone(),
+            });
+        }
+
+        let eval_err = |e: mlua::Error| DatabaseValidationError::LuaProofEvaluationError {
+            table_name: proof.output_table_name.clone(),
+            comment: proof.comment.clone(),
+            proof_expression: proof.expression.clone(),
+            error: e.to_string(),
+        };
+        let return_type_err = |error: String| {
+            DatabaseValidationError::LuaProofUnexpectedReturnType {
+                table_name: proof.output_table_name.clone(),
+                comment: proof.comment.clone(),
+                proof_expression: proof.expression.clone(),
+                error,
+            }
+        };
+
+        let api = match &api {
+            Some(api) => api.clone(),
+            None => api.insert(lua_proof_api(&lua, res).map_err(eval_err)?).clone(),
+        };
+        // keep user code line numbers intact
+        let source = format!("local db = ...; {}", proof.expression);
+        let output: mlua::Value = lua
+            .load(&source)
+            .set_name(&proof.comment)
+            .and_then(|c| c.call(api))
+            .map_err(eval_err)?;
+
+        let keys = match output {
+            mlua::Value::Table(t) => t
+                .sequence_values::<mlua::Value>()
+                .map(|v| match v.map_err(eval_err)? {
+                    mlua::Value::String(s) => Ok(s.to_string_lossy().to_string()),
+                    other => Err(return_type_err(format!(
+                        "Offending primary keys must be strings, got {}",
+                        other.type_name()
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            other => {
+                return Err(return_type_err(format!(
+                    "Lua proof must return array of offending primary keys, got {}",
+                    other.type_name()
+                )));
+            }
+        };
+
+        let row_keys = (0..tbl.len())
+            .map(|r| (tbl.row_primary_key(r), r))
+            .collect::<HashMap<_, _>>();
+        let mut offenders = Vec::with_capacity(keys.len());
+        for k in keys {
+            match row_keys.get(&k) {
+                Some(r) => offenders.push(*r),
+                None => {
+                    return Err(DatabaseValidationError::LuaProofOffenderKeyNotFound {
+                        table_name: proof.output_table_name.clone(),
+                        comment: proof.comment.clone(),
+                        offender_key: k,
+                    });
+                }
+            }
+        }
+
+        let failure = if proof.quantifier != ProofQuantifier::None {
+            quantified_proof_failure(proof, tbl, &offenders)?
+        } else {
+            let excused = proof_excused_offenders(proof, tbl, &offenders)?;
+            offenders.retain(|o| !excused.contains(o));
+            if offenders.is_empty() {
+                None
+            } else {
+                Some(DatabaseValidationError::LuaProofOffendersFound {
+                    table_name: proof.output_table_name.clone(),
+                    comment: proof.comment.clone(),
+                    proof_expression: proof.expression.clone(),
+                    offending_columns: offenders
+                        .into_iter()
+                        .map(|o| tbl.row_as_pretty_json(o).unwrap())
+                        .collect(),
+                })
+            }
+        };
+
+        if let Some(err) = failure {
+            match proof.severity {
+                ProofSeverity::Error => return Err(err),
+                ProofSeverity::Warn => warnings.push(err),
+            }
+        }
+    }
+
+    drop(api);
+    drop(lua);
+    res.proof_warnings.extend(warnings);
+
+    Ok(())
+}
+
+/// Read only view of all tables passed to lua proofs as `db`,
+/// assigning to rows or `db` raises lua error
+fn lua_proof_api<'lua>(
+    lua: &'lua mlua::Lua,
+    res: &AllData,
+) -> Result<mlua::Table<'lua>, mlua::Error> {
+    let rows = lua.create_table()?;
+    let row_keys = lua.create_table()?;
+    let key_index = lua.create_table()?;
+    for t in &res.tables {
+        let t_rows = lua.create_table()?;
+        let t_keys = lua.create_table()?;
+        let t_key_index = lua.create_table()?;
+        let has_key = t.primary_key_column().is_some();
+        for row_idx in 0..t.len() {
+            let row = lua.create_table()?;
+            for c in &t.columns {
+                let cname = c.column_name.as_str();
+                match &c.data {
+                    ColumnVector::Strings(v) => row.set(cname, v.v[row_idx].as_str())?,
+                    ColumnVector::Ints(v) => row.set(cname, v.v[row_idx])?,
+                    ColumnVector::Floats(v) => row.set(cname, v.v[row_idx])?,
+                    ColumnVector::Bools(v) => row.set(cname, v.v[row_idx])?,
+                }
+            }
+            t_rows.raw_set(row_idx + 1, row)?;
+            if has_key {
+                let key = t.row_primary_key(row_idx);
+                t_key_index.set(key.as_str(), row_idx + 1)?;
+                t_keys.raw_set(row_idx + 1, key)?;
+            }
+        }
+        rows.set(t.name.as_str(), t_rows)?;
+        row_keys.set(t.name.as_str(), t_keys)?;
+        key_index.set(t.name.as_str(), t_key_index)?;
+    }
+
+    let relation_table = |outer: &mlua::Table<'lua>, first: &str, second: &str| {
+        let inner = match outer.get::<_, Option<mlua::Table>>(first)? {
+            Some(t) => t,
+            None => {
+                let t = lua.create_table()?;
+                outer.set(first, t.clone())?;
+                t
+            }
+        };
+        let existing = inner.get::<_, Option<mlua::Table>>(second)?;
+        match existing {
+            Some(t) => Ok(t),
+            None => {
+                let t = lua.create_table()?;
+                inner.set(second, t.clone())?;
+                Ok::<_, mlua::Error>(t)
+            }
+        }
+    };
+
+    // lua indexes are one based
+    let children = lua.create_table()?;
+    for (k, v) in &res.parent_child_keys_map {
+        let by_parent = relation_table(&children, k.parent_table.as_str(), k.child_table.as_str())?;
+        for (idx, l) in v.children_for_parents_index.iter().enumerate() {
+            by_parent.raw_set(idx + 1, lua.create_sequence_from(l.iter().map(|i| i + 1))?)?;
+        }
+    }
+
+    // same table may refer with multiple columns, merge them
+    let referrers = lua.create_table()?;
+    for (k, v) in &res.foreign_keys_map {
+        let by_referred =
+            relation_table(&referrers, k.referred_table.as_str(), k.referee_table.as_str())?;
+        for (idx, l) in v.reverse_referrees_data.iter().enumerate() {
+            let existing = match by_referred.get::<_, Option<mlua::Table>>(idx + 1)? {
+                Some(t) => t,
+                None => {
+                    let t = lua.create_table()?;
+                    by_referred.set(idx + 1, t.clone())?;
+                    t
+                }
+            };
+            for i in l {
+                existing.raw_set(existing.raw_len() + 1, i + 1)?;
+            }
+        }
+    }
+
+    lua.load(lua_proof_api_library())
+        .set_name("lua proof api")?
+        .call((rows, row_keys, key_index, children, referrers))
 }
 
-fn run_sqlite_proofs(res: &mut AllData, so: &SourceOutputs) -> Result<(), DatabaseValidationError> {
-    if so.sql_proofs().is_empty() {
-        return Ok(());
-    }
+fn lua_proof_api_library() -> &'static str {
+    r#"
+local raw_rows, row_keys, key_index, children, referrers = ...
+
+-- luajit has no __len or __pairs, so proxies only allow reading fields
+local function read_only(raw, what)
+    return setmetatable({}, {
+        __index = raw,
+        __newindex = function()
+            error(what .. " is read only", 2)
+        end,
+        __metatable = false,
+    })
+end
 
-    for proof in so.sql_proofs() {
-        let dbi = DBIdentifier::new(proof.output_table_name.as_str())?;
-        let tbl = res.find_table_named_idx(&dbi);
+local rows = {}
+local row_index = {}
+for t, t_rows in pairs(raw_rows) do
+    rows[t] = {}
+    row_index[t] = {}
+    for idx, row in ipairs(t_rows) do
+        local proxy = read_only(row, "row of table " .. t)
+        rows[t][idx] = proxy
+        row_index[t][proxy] = idx
+    end
+end
 
-        if tbl.is_empty() {
-            return Err(DatabaseValidationError::SqlProofTableNotFound {
-                table_name: proof.output_table_name.clone(),
-                proof_expression: proof.expression.clone(),
-                comment: proof.comment.clone(),
-            });
-        }
-    }
+local function table_rows(t)
+    local r = rows[t]
+    if r == nil then
+        error("table " .. tostring(t) .. " doesn't exist")
+    end
+    return r
+end
 
-    let conn = res.sqlite_db.ro.lock().unwrap();
+local function related(relation, t, key, other, what)
+    table_rows(t)
+    table_rows(other)
+    local idx = key_index[t][key]
+    if idx == nil then
+        error("key " .. tostring(key) .. " doesn't exist in table " .. t)
+    end
+    local res = {}
+    if relation[t] == nil or relation[t][other] == nil then
+        error("table " .. other .. " has no " .. what .. " relation to table " .. t)
+    end
+    for _, ri in ipairs(relation[t][other][idx] or {}) do
+        table.insert(res, rows[other][ri])
+    end
+    return res
+end
 
-    for proof in so.sql_proofs() {
-        let mut stmt = conn.prepare(proof.expression.as_str()).map_err(|e| {
-            DatabaseValidationError::SqlProofQueryPlanningError {
-                error: e.to_string(),
-                table_name: proof.output_table_name.clone(),
-                proof_expression: proof.expression.clone(),
-                comment: proof.comment.clone(),
-            }
-        })?;
+local db = {}
 
-        let column_names = stmt.column_names();
-        let column_count = column_names.len();
-        if column_names.len() != 1 {
-            return Err(
-                DatabaseValidationError::SqlProofQueryErrorSingleRowIdColumnExpected {
-                    error: format!("Required output column count is 1, got {column_count}"),
-                    table_name: proof.output_table_name.clone(),
-                    proof_expression: proof.expression.clone(),
-                    comment: proof.comment.clone(),
-                },
-            );
-        }
+-- all rows of table, array is a copy which caller may modify
+function db.rows(t)
+    local res = {}
+    for idx, row in ipairs(table_rows(t)) do
+        res[idx] = row
+    end
+    return res
+end
 
-        if column_names[0] != "rowid" {
-            return Err(
-                DatabaseValidationError::SqlProofQueryErrorSingleRowIdColumnExpected {
-                    error: format!(
-                        "Required output column name must be rowid, got {}",
-                        column_names[0]
-                    ),
-                    table_name: proof.output_table_name.clone(),
-                    proof_expression: proof.expression.clone(),
-                    comment: proof.comment.clone(),
-                },
-            );
-        }
+-- row by primary keys from the root parent joined with =>, nil if not found
+function db.find(t, key)
+    table_rows(t)
+    local idx = key_index[t][key]
+    if idx == nil then
+        return nil
+    end
+    return rows[t][idx]
+end
 
-        // I kinda wish rusqlite exposed this :(
-        let (query_tname, query_colname) = unsafe {
-            let raw_stmt = stmt.raw_stmt();
-            let raw_ptr = raw_stmt.ptr();
-            let tname_raw = sqlite3_column_table_name(raw_ptr, 0);
-            let cname_raw = sqlite3_column_origin_name(raw_ptr, 0);
+-- primary key of row returned by other functions
+function db.key(t, row)
+    table_rows(t)
+    local idx = row_index[t][row]
+    if idx == nil then
+        error("row doesn't belong to table " .. t)
+    end
+    return row_keys[t][idx]
+end
 
-            let tname = if tname_raw.is_null() {
-                "NULL".to_string()
-            } else {
-                std::ffi::CStr::from_ptr(tname_raw)
-                    .to_string_lossy()
-                    .to_string()
-            };
-            let colname = if cname_raw.is_null() {
-                "NULL".to_string()
-            } else {
-                std::ffi::CStr::from_ptr(cname_raw)
-                    .to_string_lossy()
-                    .to_string()
-            };
+-- rows of child table under row with key
+function db.children(t, key, child_table)
+    return related(children, t, key, child_table, "child")
+end
 
-            (tname, colname)
-        };
+-- rows of table referring to row with key by REF columns
+function db.referrers(t, key, referrer_table)
+    return related(referrers, t, key, referrer_table, "referrer")
+end
 
-        if query_tname != proof.output_table_name || query_colname != "rowid" {
-            return Err(
-                DatabaseValidationError::SqlProofQueryColumnOriginMismatchesExpected {
-                    error: "Actual column origin table name or origin mistmaches expectations"
-                        .to_string(),
-                    expected_column_origin_table: proof.output_table_name.to_string(),
-                    expected_column_origin_name: "rowid".to_string(),
-                    actual_column_origin_table: query_tname,
-                    actual_column_origin_name: query_colname,
-                    proof_expression: proof.expression.to_string(),
-                    comment: proof.comment.clone(),
-                },
-            );
-        }
+return read_only(db, "db")
+"#
+}
 
-        let mut offenders_res =
-            stmt.query([])
-                .map_err(|e| DatabaseValidationError::SqlProofQueryError {
-                    error: e.to_string(),
-                    table_name: proof.output_table_name.clone(),
-                    proof_expression: proof.expression.clone(),
-                    comment: proof.comment.clone(),
-                })?;
+/// Checks output rows of proofs other than NONE EXIST against table rows
+fn quantified_proof_failure(
+    proof: &ExpressionProof,
+    table: &DataTable,
+    output_rows: &[usize],
+) -> Result<Option<DatabaseValidationError>, DatabaseValidationError> {
+    let output_rows: BTreeSet<usize> = output_rows.iter().cloned().collect();
+    let rows_json = |rows: Vec<usize>| {
+        rows.into_iter()
+            .map(|r| table.row_as_pretty_json(r).unwrap())
+            .collect::<Vec<_>>()
+    };
+    let count_mismatch = |expected_rows: String| DatabaseValidationError::ProofRowCountMismatch {
+        table_name: proof.output_table_name.clone(),
+        comment: proof.comment.clone(),
+        proof_expression: proof.expression.clone(),
+        expected_rows,
+        found_rows: output_rows.len(),
+        matching_rows: rows_json(output_rows.iter().cloned().collect()),
+    };
 
-        let mut offenders: Vec<usize> = Vec::new();
-        while let Some(offender) =
-            offenders_res
-                .next()
-                .map_err(|e| DatabaseValidationError::SqlProofQueryError {
-                    error: e.to_string(),
+    if proof.exceptions.is_some() && proof.quantifier != ProofQuantifier::All {
+        return Err(DatabaseValidationError::ProofExceptionsOnlyAllowedForNoneOrAll {
+            table_name: proof.output_table_name.clone(),
+            comment: proof.comment.clone(),
+        });
+    }
+
+    match proof.quantifier {
+        ProofQuantifier::None => panic!("NONE EXIST proofs are checked for offenders"),
+        ProofQuantifier::All => {
+            let mut missing = (0..table.len())
+                .filter(|r| !output_rows.contains(r))
+                .collect::<Vec<_>>();
+            let excused = proof_excused_offenders(proof, table, &missing)?;
+            missing.retain(|r| !excused.contains(r));
+            if !missing.is_empty() {
+                return Ok(Some(DatabaseValidationError::ProofRowsMissing {
                     table_name: proof.output_table_name.clone(),
-                    proof_expression: proof.expression.clone(),
                     comment: proof.comment.clone(),
-                })?
-        {
-            let res = offender.get::<usize, usize>(0).unwrap();
-            // rowid values are 1 based
-            offenders.push(res - 1);
+                    proof_expression: proof.expression.clone(),
+                    missing_rows: rows_json(missing),
+                }));
+            }
         }
+        ProofQuantifier::AtLeast(n) => {
+            if output_rows.len() < n {
+                return Ok(Some(count_mismatch(format!("at least {n}"))));
+            }
+        }
+        ProofQuantifier::Exactly(n) => {
+            if output_rows.len() != n {
+                return Ok(Some(count_mismatch(format!("exactly {n}"))));
+            }
+        }
+    }
 
-        if !offenders.is_empty() {
-            let dbi = DBIdentifier::new(proof.output_table_name.as_str())?;
-            let tbl = &res.tables[res.find_table_named_idx(&dbi)[0]];
-            let offenders_mapped = offenders
-                .into_iter()
-                .map(|o| tbl.row_as_pretty_json(o).unwrap())
-                .collect::<Vec<_>>();
+    Ok(None)
+}
+
+/// Offending rows allowed by proof EXCEPT clause, fails if allowlist
+/// has expired or lists keys which don't offend anymore
+fn proof_excused_offenders(
+    proof: &ExpressionProof,
+    table: &DataTable,
+    offenders: &[usize],
+) -> Result<HashSet<usize>, DatabaseValidationError> {
+    let mut res = HashSet::new();
+    let exceptions = match &proof.exceptions {
+        Some(e) => e,
+        None => return Ok(res),
+    };
+
+    if table.primary_key_column().is_none() {
+        return Err(DatabaseValidationError::ProofExceptionsTableHasNoPrimaryKey {
+            table_name: proof.output_table_name.clone(),
+            comment: proof.comment.clone(),
+        });
+    }
 
-            return Err(DatabaseValidationError::SqlProofOffendersFound {
+    if let Some(until) = &exceptions.until {
+        // dates are validated by parser, YYYY-MM-DD format compares lexicographically
+        if *until < today_date() {
+            return Err(DatabaseValidationError::ProofExceptionsExpired {
                 table_name: proof.output_table_name.clone(),
-                proof_expression: proof.expression.clone(),
-                offending_columns: offenders_mapped,
                 comment: proof.comment.clone(),
+                until: until.clone(),
             });
         }
     }
 
-    Ok(())
+    let mut not_offending: Vec<&String> = exceptions.keys.iter().collect();
+    for o in offenders {
+        let key = table.row_primary_key(*o);
+        if exceptions.keys.contains(&key) {
+            let _ = res.insert(*o);
+            not_offending.retain(|k| **k != key);
+        }
+    }
+
+    if !not_offending.is_empty() {
+        return Err(DatabaseValidationError::ProofExceptionKeysNotOffending {
+            table_name: proof.output_table_name.clone(),
+            comment: proof.comment.clone(),
+            keys: not_offending.into_iter().cloned().collect(),
+        });
+    }
+
+    Ok(res)
+}
+
+/// Current UTC date in YYYY-MM-DD format
+fn today_date() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    // civil from days algorithm by Howard Hinnant
+    let z = secs / 86400 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn sqlite_value_to_json(v: rusqlite::types::ValueRef) -> Value {
+    use rusqlite::types::ValueRef;
+    match v {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::Number(i.into()),
+        ValueRef::Real(f) => serde_json::Number::from_f64(f)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).to_string()),
+        ValueRef::Blob(b) => Value::String(String::from_utf8_lossy(b).to_string()),
+    }
 }
 
 fn preprocess_lua_expression(inp_exp: &str) -> String {
@@ -2031,12 +3335,12 @@ fn ensure_no_nan_or_infinity_floats(res: &AllData) -> Result<(), DatabaseValidat
                     for (idx, f) in v.v.iter().enumerate() {
                         if f.is_infinite() || f.is_nan() {
                             return Err(
-                                DatabaseValidationError::NanOrInfiniteFloatNumbersAreNotAllowed {
+                                table.row_error(idx, DatabaseValidationError::NanOrInfiniteFloatNumbersAreNotAllowed {
                                     table_name: table.name.as_str().to_string(),
                                     column_name: column.column_name.as_str().to_string(),
                                     column_value: f.to_string(),
                                     row_index: idx + 1,
-                                },
+                                }),
                             );
                         }
                     }
@@ -2136,18 +3440,18 @@ fn ensure_row_checks(res: &AllData) -> Result<(), DatabaseValidationError> {
                 match res {
                     Ok(mlua::Value::Boolean(v)) => {
                         if !v {
-                            return Err(DatabaseValidationError::LuaCheckEvaluationFailed {
+                            return Err(table.row_error(row_no, DatabaseValidationError::LuaCheckEvaluationFailed {
                                 table_name: table.name.as_str().to_string(),
                                 expression: expression.to_string(),
                                 row_values,
                                 column_names,
                                 error: "Expression check for the row didn't pass.".to_string(),
-                            });
+                            }));
                         }
                     }
                     Ok(v) => {
                         return Err(
-                            DatabaseValidationError::LuaCheckEvaluationErrorUnexpectedReturnType {
+                            table.row_error(row_no, DatabaseValidationError::LuaCheckEvaluationErrorUnexpectedReturnType {
                                 table_name: table.name.as_str().to_string(),
                                 expression: expression.to_string(),
                                 row_values,
@@ -2156,17 +3460,17 @@ fn ensure_row_checks(res: &AllData) -> Result<(), DatabaseValidationError> {
                                     "Unexpected expression return value, expected boolean, got {}",
                                     v.type_name()
                                 ),
-                            },
+                            }),
                         )
                     }
                     Err(e) => {
-                        return Err(DatabaseValidationError::LuaCheckEvaluationError {
+                        return Err(table.row_error(row_no, DatabaseValidationError::LuaCheckEvaluationError {
                             table_name: table.name.as_str().to_string(),
                             expression: expression.to_string(),
                             row_values,
                             column_names,
                             error: e.to_string(),
-                        })
+                        }))
                     }
                 }
             }
@@ -2217,8 +3521,12 @@ fn set_column_value_in_lua_runtime(
 
 fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
     res: &mut AllData,
+    mat_views_ready: bool,
 ) -> Result<(), DatabaseValidationError> {
     for t in &res.tables {
+        if !mat_views_ready && t.mat_view_expression.is_some() {
+            continue;
+        }
         if let Some(pk) = t.primary_key_column() {
             if let KeyType::ChildPrimary { .. } = &pk.key_type {
                 for fk_table in &res.tables {
@@ -2386,7 +3694,7 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                                         .push(row);
                                                                 }
                                                                 None => {
-                                                                    return Err(DatabaseValidationError::NonExistingForeignKeyToChildTable {
+                                                                    return Err(fk_table.row_error(row, DatabaseValidationError::NonExistingForeignKeyToChildTable {
                                                                         table_parent_keys: referee_uniq_context[row].clone(),
                                                                         table_parent_tables: parent_table_names.iter().map(|i| i.as_str().to_string()).collect(),
                                                                         table_parent_columns: parent_table_colums.iter().map(|i| i.as_str().to_string()).collect(),
@@ -2395,12 +3703,12 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                                         referred_table: t.name.as_str().to_string(),
                                                                         referred_table_column: pk.column_name.as_str().to_string(),
                                                                         key_value: v.v[row].clone(),
-                                                                    });
+                                                                    }));
                                                                 }
                                                             }
                                                         }
                                                         None => {
-                                                            return Err(DatabaseValidationError::NonExistingForeignKeyToChildTable {
+                                                            return Err(fk_table.row_error(row, DatabaseValidationError::NonExistingForeignKeyToChildTable {
                                                                 table_parent_keys: referee_uniq_context[row].clone(),
                                                                 table_parent_tables: parent_table_names.iter().map(|i| i.as_str().to_string()).collect(),
                                                                 table_parent_columns: parent_table_colums.iter().map(|i| i.as_str().to_string()).collect(),
@@ -2409,7 +3717,7 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                                 referred_table: t.name.as_str().to_string(),
                                                                 referred_table_column: pk.column_name.as_str().to_string(),
                                                                 key_value: v.v[row].clone(),
-                                                            });
+                                                            }));
                                                         }
                                                     }
                                                 }
@@ -2429,7 +3737,7 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                                         .push(row);
                                                                 }
                                                                 None => {
-                                                                    return Err(DatabaseValidationError::NonExistingForeignKeyToChildTable {
+                                                                    return Err(fk_table.row_error(row, DatabaseValidationError::NonExistingForeignKeyToChildTable {
                                                                         table_parent_keys: referee_uniq_context[row].clone(),
                                                                         table_parent_tables: parent_table_names.iter().map(|i| i.as_str().to_string()).collect(),
                                                                         table_parent_columns: parent_table_colums.iter().map(|i| i.as_str().to_string()).collect(),
@@ -2438,12 +3746,12 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                                         referred_table: t.name.as_str().to_string(),
                                                                         referred_table_column: pk.column_name.as_str().to_string(),
                                                                         key_value: v.v[row].to_string(),
-                                                                    });
+                                                                    }));
                                                                 }
                                                             }
                                                         }
                                                         None => {
-                                                            return Err(DatabaseValidationError::NonExistingForeignKeyToChildTable {
+                                                            return Err(fk_table.row_error(row, DatabaseValidationError::NonExistingForeignKeyToChildTable {
                                                                 table_parent_keys: referee_uniq_context[row].clone(),
                                                                 table_parent_tables: parent_table_names.iter().map(|i| i.as_str().to_string()).collect(),
                                                                 table_parent_columns: parent_table_colums.iter().map(|i| i.as_str().to_string()).collect(),
@@ -2452,7 +3760,7 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                                 referred_table: t.name.as_str().to_string(),
                                                                 referred_table_column: pk.column_name.as_str().to_string(),
                                                                 key_value: v.v[row].to_string(),
-                                                            });
+                                                            }));
                                                         }
                                                     }
                                                 }
@@ -2651,7 +3959,7 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                             reverse_ref_vector[*idx].push(row);
                                                         }
                                                         None => {
-                                                            return Err(DatabaseValidationError::NonExistingParentToChildKey {
+                                                            return Err(fk_table.row_error(row, DatabaseValidationError::NonExistingParentToChildKey {
                                                                 table_parent_keys: referrer_parent_keys[row].clone(),
                                                                 table_parent_tables: parent_table_names.iter().map(|i| i.as_str().to_string()).collect(),
                                                                 table_parent_columns: data.common_keys.iter().map(|i| i.as_str().to_string()).collect(),
@@ -2660,12 +3968,12 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                                 referred_table: t.name.as_str().to_string(),
                                                                 referred_table_column: data.refereed_columns_by_key.iter().map(|i| i.as_str().to_string()).collect::<Vec<_>>().join("=>"),
                                                                 key_value: referrer_to_fk_keys[row].clone(),
-                                                            });
+                                                            }));
                                                         }
                                                     }
                                                 }
                                                 None => {
-                                                    return Err(DatabaseValidationError::NonExistingParentToChildKey {
+                                                    return Err(fk_table.row_error(row, DatabaseValidationError::NonExistingParentToChildKey {
                                                         table_parent_keys: referrer_parent_keys[row].clone(),
                                                         table_parent_tables: parent_table_names.iter().map(|i| i.as_str().to_string()).collect(),
                                                         table_parent_columns: data.common_keys.iter().map(|i| i.as_str().to_string()).collect(),
@@ -2674,7 +3982,7 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                         referred_table: t.name.as_str().to_string(),
                                                         referred_table_column: data.refereed_columns_by_key.iter().map(|i| i.as_str().to_string()).collect::<Vec<_>>().join("=>"),
                                                         key_value: referrer_to_fk_keys[row].clone(),
-                                                    });
+                                                    }));
                                                 }
                                             }
                                         }
@@ -2867,7 +4175,7 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                             reverse_ref_vector[*idx].push(row);
                                                         }
                                                         None => {
-                                                            return Err(DatabaseValidationError::NonExistingForeignKeyToChildTable {
+                                                            return Err(fk_table.row_error(row, DatabaseValidationError::NonExistingForeignKeyToChildTable {
                                                                 table_parent_keys: referrer_parent_keys[row].clone(),
                                                                 table_parent_tables: parent_table_names.iter().map(|i| i.as_str().to_string()).collect(),
                                                                 table_parent_columns: data.common_parent_keys.iter().map(|i| i.as_str().to_string()).collect(),
@@ -2876,12 +4184,12 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                                 referred_table: t.name.as_str().to_string(),
                                                                 referred_table_column: data.refereed_columns_by_key.iter().map(|i| i.as_str().to_string()).collect::<Vec<_>>().join("=>"),
                                                                 key_value: referrer_to_fk_keys[row].clone(),
-                                                            });
+                                                            }));
                                                         }
                                                     }
                                                 }
                                                 None => {
-                                                    return Err(DatabaseValidationError::NonExistingForeignKeyToChildTable {
+                                                    return Err(fk_table.row_error(row, DatabaseValidationError::NonExistingForeignKeyToChildTable {
                                                         table_parent_keys: referrer_parent_keys[row].clone(),
                                                         table_parent_tables: parent_table_names.iter().map(|i| i.as_str().to_string()).collect(),
                                                         table_parent_columns: data.common_parent_keys.iter().map(|i| i.as_str().to_string()).collect(),
@@ -2890,7 +4198,7 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
                                                         referred_table: t.name.as_str().to_string(),
                                                         referred_table_column: data.refereed_columns_by_key.iter().map(|i| i.as_str().to_string()).collect::<Vec<_>>().join("=>"),
                                                         key_value: referrer_to_fk_keys[row].clone(),
-                                                    });
+                                                    }));
                                                 }
                                             }
                                         }
@@ -2924,8 +4232,12 @@ fn ensure_child_primary_keys_unique_per_table_and_fkeys_exist(
 
 fn ensure_primary_keys_unique_per_table_and_fkeys_exist(
     res: &mut AllData,
+    mat_views_ready: bool,
 ) -> Result<(), DatabaseValidationError> {
     for t in res.tables.iter() {
+        if !mat_views_ready && t.mat_view_expression.is_some() {
+            continue;
+        }
         if let Some(pk) = t.primary_key_column() {
             if let KeyType::Primary = &pk.key_type {
                 match &pk.data {
@@ -2933,10 +4245,10 @@ fn ensure_primary_keys_unique_per_table_and_fkeys_exist(
                         let mut pkey_map = HashMap::new();
                         for (idx, k) in vc.v.iter().enumerate() {
                             if pkey_map.insert(k.clone(), idx).is_some() {
-                                return Err(DatabaseValidationError::DuplicatePrimaryKey {
+                                return Err(t.row_error(idx, DatabaseValidationError::DuplicatePrimaryKey {
                                     table_name: t.name.as_str().to_string(),
                                     value: k.clone(),
-                                });
+                                }));
                             }
                         }
 
@@ -2960,13 +4272,13 @@ fn ensure_primary_keys_unique_per_table_and_fkeys_exist(
                                                             reverse_fk_index[*idx].push(r_idx);
                                                         }
                                                         None => {
-                                                            return Err(DatabaseValidationError::NonExistingForeignKey {
+                                                            return Err(fk.row_error(r_idx, DatabaseValidationError::NonExistingForeignKey {
                                                                 table_with_foreign_key: fk.name.as_str().to_string(),
                                                                 foreign_key_column: fkc.column_name.as_str().to_string(),
                                                                 referred_table: t.name.as_str().to_string(),
                                                                 referred_table_column: pk.column_name.as_str().to_string(),
                                                                 key_value: fval.clone(),
-                                                            });
+                                                            }));
                                                         }
                                                     }
                                                 }
@@ -2995,10 +4307,10 @@ fn ensure_primary_keys_unique_per_table_and_fkeys_exist(
                         let mut pkey_map = HashMap::new();
                         for (idx, k) in vc.v.iter().enumerate() {
                             if pkey_map.insert(*k, idx).is_some() {
-                                return Err(DatabaseValidationError::DuplicatePrimaryKey {
+                                return Err(t.row_error(idx, DatabaseValidationError::DuplicatePrimaryKey {
                                     table_name: t.name.as_str().to_string(),
                                     value: format!("{k}"),
-                                });
+                                }));
                             }
                         }
 
@@ -3021,13 +4333,13 @@ fn ensure_primary_keys_unique_per_table_and_fkeys_exist(
                                                             reverse_fk_index[*idx].push(r_idx);
                                                         }
                                                         None => {
-                                                            return Err(DatabaseValidationError::NonExistingForeignKey {
+                                                            return Err(fk.row_error(r_idx, DatabaseValidationError::NonExistingForeignKey {
                                                                 table_with_foreign_key: fk.name.as_str().to_string(),
                                                                 foreign_key_column: fkc.column_name.as_str().to_string(),
                                                                 referred_table: t.name.as_str().to_string(),
                                                                 referred_table_column: pk.column_name.as_str().to_string(),
                                                                 key_value: format!("{fval}"),
-                                                            });
+                                                            }));
                                                         }
                                                     }
                                                 }
@@ -3068,6 +4380,7 @@ fn ensure_primary_keys_unique_per_table_and_fkeys_exist(
 
 fn ensure_parent_primary_keys_exist_for_children(
     res: &mut AllData,
+    mat_views_ready: bool,
 ) -> Result<(), DatabaseValidationError> {
     for i in &res.tables {
         let parent_columns = i
@@ -3092,6 +4405,10 @@ fn ensure_parent_primary_keys_exist_for_children(
                     *parent_table, i.name,
                     "Paranoia... We should have checked this much earlier"
                 );
+                let parent_idx = res.find_table_named_idx(parent_table)[0];
+                if !mat_views_ready && res.tables[parent_idx].mat_view_expression.is_some() {
+                    continue;
+                }
                 // get all tuples for this table, and ensure they all exist on parent table
 
                 let (uniq_parents_by_child, uniq_parents_by_child_vec_idx) = {
@@ -3154,11 +4471,11 @@ fn ensure_parent_primary_keys_exist_for_children(
 
                         if tuple_set.contains(&tuple) {
                             return Err(
-                                DatabaseValidationError::FoundDuplicateChildPrimaryKeySet {
+                                i.row_error(row, DatabaseValidationError::FoundDuplicateChildPrimaryKeySet {
                                     table_name: i.name.as_str().to_string(),
                                     columns: format!("({})", column_names_vec.join(", ")),
                                     duplicate_values: format!("({})", tuple.join(", ")),
-                                },
+                                }),
                             );
                         } else {
                             let ins = tuple_set.insert(tuple);
@@ -3223,11 +4540,11 @@ fn ensure_parent_primary_keys_exist_for_children(
                         match parent_set.get(&tuple) {
                             Some(_) => {
                                 return Err(
-                                    DatabaseValidationError::FoundDuplicateChildPrimaryKeySet {
+                                    last_parent_table.row_error(row, DatabaseValidationError::FoundDuplicateChildPrimaryKeySet {
                                         table_name: last_parent_table.name.as_str().to_string(),
                                         columns: format!("({})", column_names_vec.join(", ")),
                                         duplicate_values: format!("({})", tuple.join(", ")),
-                                    },
+                                    }),
                                 );
                             }
                             None => {
@@ -3322,7 +4639,7 @@ fn ensure_uniq_constaints_are_not_violated(res: &AllData) -> Result<(), Database
                 }
 
                 if tuple_set.contains(&key) {
-                    return Err(DatabaseValidationError::UniqConstraintViolated {
+                    return Err(t.row_error(row_idx, DatabaseValidationError::UniqConstraintViolated {
                         table_name: t.name.as_str().to_string(),
                         tuple_definition: format!(
                             "({})",
@@ -3333,7 +4650,7 @@ fn ensure_uniq_constaints_are_not_violated(res: &AllData) -> Result<(), Database
                                 .join(", ")
                         ),
                         tuple_value: format!("({})", key.join(", ")),
-                    });
+                    }));
                 } else {
                     tuple_set.insert(key);
                 }
@@ -3404,13 +4721,22 @@ fn init_all_declared_tables(
 
         let row_checks = tbl.row_checks.clone();
 
+        if tbl.row_constants && !columns.iter().any(|c| matches!(c.key_type, KeyType::Primary | KeyType::ChildPrimary { .. })) {
+            return Err(DatabaseValidationError::RowConstantsTableHasNoPrimaryKey {
+                table_name: tbl.name.clone(),
+            });
+        }
+
         res.tables.push(DataTable {
             name: DBIdentifier::new(tbl.name.as_str())?,
             columns,
             uniq_constraints,
             row_checks,
             mat_view_expression: tbl.mat_view_expression.clone(),
+            mat_view_language: tbl.mat_view_language,
             exclusive_lock: false,
+            row_constants: tbl.row_constants,
+            imported_rows: HashMap::new(),
         })
     }
 
@@ -4311,6 +5637,15 @@ fn insert_main_data(
     for ds in outputs.table_data_segments() {
         match ds {
             TableDataSegment::DataFrame(df) => {
+                let csv = outputs
+                    .csv_imports()
+                    .iter()
+                    .find(|csv| csv.source_file_id == df.source_file_id);
+                if let Some(csv) = csv {
+                    insert_csv_import(res, df, csv)?;
+                    continue;
+                }
+
                 let mut data_slices = Vec::with_capacity(df.data.len());
                 let mut replacement_maps: Vec<Vec<(i32, usize, usize)>> = Vec::with_capacity(df.data.len());
                 for i in df.data.iter() {
@@ -4338,7 +5673,207 @@ fn insert_main_data(
                 )?;
             }
             TableDataSegment::StructuredData(sd) => insert_structured_data(res, sd)?,
+            TableDataSegment::SqliteImport(si) => insert_sqlite_import(res, si, outputs)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn quote_sqlite_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn insert_sqlite_import(
+    res: &mut AllData,
+    si: &SqliteImport,
+    outputs: &SourceOutputs,
+) -> Result<(), DatabaseValidationError> {
+    let import_error = |error: String| DatabaseValidationError::SqliteImportError {
+        sqlite_file: si.path.clone(),
+        sqlite_table: si.sqlite_table.clone(),
+        error,
+    };
+
+    // relative to the directory of the source file
+    let source_file = &outputs.sources_db()[si.source_file_id as usize];
+    let source_directory = match &source_file.source_dir {
+        Some(dir) => PathBuf::from_str(dir).unwrap(),
+        None => PathBuf::from_str(&source_file.path)
+            .unwrap()
+            .parent()
+            .unwrap()
+            .to_path_buf(),
+    };
+    let conn = rusqlite::Connection::open_with_flags(
+        source_directory.join(&si.path),
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )
+    .map_err(|e| import_error(e.to_string()))?;
+
+    let mut mapping = si.column_mapping.clone();
+    if mapping.is_empty() {
+        let mut stmt = conn
+            .prepare(&format!("PRAGMA table_info({})", quote_sqlite_identifier(&si.sqlite_table)))
+            .map_err(|e| import_error(e.to_string()))?;
+        let columns = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| import_error(e.to_string()))?;
+        if columns.is_empty() {
+            return Err(import_error("table doesn't exist".to_string()));
+        }
+        mapping = columns.into_iter().map(|c| (c.clone(), c)).collect();
+    }
+
+    let query = format!(
+        "SELECT rowid, {} FROM {} ORDER BY rowid",
+        mapping
+            .iter()
+            .map(|(_, sqlite_column)| quote_sqlite_identifier(sqlite_column))
+            .collect::<Vec<_>>()
+            .join(", "),
+        quote_sqlite_identifier(&si.sqlite_table),
+    );
+    let mut stmt = conn.prepare(&query).map_err(|e| import_error(e.to_string()))?;
+    let mut rows = stmt.query([]).map_err(|e| import_error(e.to_string()))?;
+
+    let mut row_sources: Vec<ImportedRowSource> = Vec::new();
+    let mut data: Vec<Vec<String>> = Vec::new();
+    while let Some(row) = rows.next().map_err(|e| import_error(e.to_string()))? {
+        let rowid: i64 = row.get(0).map_err(|e| import_error(e.to_string()))?;
+        let mut values = Vec::with_capacity(mapping.len());
+        for (idx, (_, sqlite_column)) in mapping.iter().enumerate() {
+            let value = row.get_ref(idx + 1).map_err(|e| import_error(e.to_string()))?;
+            let value = match value {
+                rusqlite::types::ValueRef::Integer(v) => v.to_string(),
+                rusqlite::types::ValueRef::Real(v) => v.to_string(),
+                rusqlite::types::ValueRef::Text(v) => String::from_utf8_lossy(v).to_string(),
+                rusqlite::types::ValueRef::Null | rusqlite::types::ValueRef::Blob(_) => {
+                    return Err(DatabaseValidationError::SqliteImportUnsupportedValue {
+                        sqlite_file: si.path.clone(),
+                        sqlite_table: si.sqlite_table.clone(),
+                        rowid,
+                        column_name: sqlite_column.clone(),
+                        value_type: value.data_type().to_string(),
+                    });
+                }
+            };
+            values.push(value);
+        }
+        row_sources.push(ImportedRowSource::Sqlite {
+            sqlite_file: si.path.clone(),
+            sqlite_table: si.sqlite_table.clone(),
+            rowid,
+        });
+        data.push(values);
+    }
+
+    let target_fields = mapping.iter().map(|(c, _)| c.as_str()).collect::<Vec<_>>();
+    let data_slices = data
+        .iter()
+        .map(|row| row.iter().map(|v| v.as_str()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    insert_imported_rows(
+        res,
+        &si.target_table_name,
+        &target_fields,
+        &data_slices,
+        &row_sources,
+        false,
+    )
+}
+
+fn insert_csv_import(
+    res: &mut AllData,
+    df: &TableData,
+    csv: &CsvImport,
+) -> Result<(), DatabaseValidationError> {
+    let target_fields = df.target_fields.iter().map(|i| i.as_str()).collect::<Vec<_>>();
+    let data_slices = df
+        .data
+        .iter()
+        .map(|row| row.value_fields.iter().map(|f| f.value.as_str()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let row_sources = csv
+        .row_lines
+        .iter()
+        .map(|line| ImportedRowSource::Csv {
+            csv_file: csv.source.path.clone(),
+            line: *line,
+        })
+        .collect::<Vec<_>>();
+
+    insert_imported_rows(
+        res,
+        &df.target_table_name,
+        &target_fields,
+        &data_slices,
+        &row_sources,
+        df.is_exclusive,
+    )
+    .map_err(|e| match e {
+        // csv header doesn't match table columns
+        DatabaseValidationError::DataTargetColumnNotFound { .. }
+        | DatabaseValidationError::DataRequiredNonDefaultColumnValueNotProvided { .. }
+        | DatabaseValidationError::ComputedColumnCannotBeExplicitlySpecified { .. } => {
+            DatabaseValidationError::CsvDataError {
+                csv_file: csv.source.path.clone(),
+                line: 1,
+                error: Box::new(e),
+            }
+        }
+        e => e,
+    })
+}
+
+/// Inserts rows read from other files, row errors point to
+/// the row source and later validation errors can find it in the table
+fn insert_imported_rows(
+    res: &mut AllData,
+    target_table_name: &str,
+    target_fields: &[&str],
+    input_data: &[Vec<&str>],
+    row_sources: &[ImportedRowSource],
+    is_exclusive: bool,
+) -> Result<(), DatabaseValidationError> {
+    // empty sqlite table or csv file with only the header
+    if input_data.is_empty() {
+        return Ok(());
+    }
+
+    // values don't come from edl sources, they can't be replaced in source
+    let replacement_maps = input_data
+        .iter()
+        .map(|row| vec![(IMPORTED_ROW_SOURCE_FILE_ID, 0, 0); row.len()])
+        .collect::<Vec<_>>();
+
+    insert_table_data(
+        res,
+        target_table_name,
+        target_fields,
+        input_data,
+        &replacement_maps,
+        is_exclusive,
+    )
+    .map_err(|e| {
+        let row_index = match &e {
+            DatabaseValidationError::DataTooManyColumns { row_index, .. }
+            | DatabaseValidationError::DataTooFewColumns { row_index, .. }
+            | DatabaseValidationError::DataCannotParseDataColumnValue { row_index, .. } => Some(*row_index),
+            _ => None,
+        };
+        match row_index.and_then(|idx| row_sources.get(idx - 1)) {
+            Some(source) => source.wrap_error(e),
+            None => e,
         }
+    })?;
+
+    let table_idx = res.find_table_named_idx(&DBIdentifier::new(target_table_name)?)[0];
+    let table = &mut res.tables[table_idx];
+    let first_row = table.len() - input_data.len();
+    for (idx, source) in row_sources.iter().enumerate() {
+        table.imported_rows.insert(first_row + idx, source.clone());
     }
 
     Ok(())
@@ -4419,7 +5954,14 @@ fn insert_structured_data(
             }
             let composite_key = composite_key.join("=>");
             if let Some(replacement) = replacements.get(&composite_key) {
-                // can only come from lua
+                if sd.source_file_id == STRUCTURED_DOCUMENT_SOURCE_FILE_ID {
+                    return Err(DatabaseValidationError::ReplacementOverIncludedDocumentValuesIsNotSupported {
+                        table: res.tables[tbl_idx].name.as_str().to_string(),
+                        replacement_primary_key: composite_key,
+                    });
+                }
+
+                // generated by lua or ocaml data modules
                 if sd.source_file_id < 0 {
                     return Err(DatabaseValidationError::ReplacementOverLuaGeneratedValuesIsNotSupported {
                         table: res.tables[tbl_idx].name.as_str().to_string(),
@@ -4656,6 +6198,9 @@ fn check_exclusive_data_violations(
             TableDataSegment::StructuredData(sd) => {
                 recur_check_exclusive_data_violations_structured(&mut counts, sd);
             }
+            TableDataSegment::SqliteImport(si) => {
+                counts.entry(si.target_table_name.clone()).or_default().push(false);
+            }
         }
     }
 
@@ -5006,6 +6551,7 @@ fn insert_extra_data(
             TableDataSegment::StructuredData(sd) => {
                 insert_extra_data_structured(res, std::slice::from_ref(sd), &[])?
             }
+            TableDataSegment::SqliteImport(_) => {}
         }
     }
 
@@ -5678,6 +7224,12 @@ fn insert_table_data(
                     for (f_idx, field) in target_table_fields.iter().enumerate() {
                         if let Some(f_repl) = replacement.values.get(*field) {
                             let (source_file_idx, offset_start, offset_end) = source_replacement_map[row_idx][f_idx];
+                            if source_file_idx == IMPORTED_ROW_SOURCE_FILE_ID {
+                                return Err(DatabaseValidationError::ReplacementOverImportedValuesIsNotSupported {
+                                    table: target_table_name.to_string(),
+                                    replacement_primary_key: composite_key,
+                                });
+                            }
                             assert!(source_file_idx >= 0, "Unknown source id, should never be reached");
                             source_replacements.push(ScheduledValueReplacementInSource {
                                 source_file_idx, offset_start, offset_end, value_to_replace_with: f_repl.clone(),
@@ -5888,6 +7440,10 @@ fn reserved_table_column_names() -> ResColumnNames {
     ]
 }
 
+pub(crate) fn is_reserved_column_name(name: &str) -> bool {
+    reserved_table_column_names().iter().any(|(reserved, check)| check(name, reserved))
+}
+
 fn validate_table_definition(td: &TableDefinition) -> Option<DatabaseValidationError> {
     if td.name.to_lowercase() != td.name {
         return Some(DatabaseValidationError::TableNameIsNotLowercase {
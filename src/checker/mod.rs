@@ -1,4 +1,5 @@
 pub mod errors;
 pub mod logic;
+pub mod sql_functions;
 pub mod tests;
 pub mod types;
@@ -0,0 +1,248 @@
+//! Built-in deterministic functions registered on every sqlite connection
+//! used by proofs and materialized views. This list is part of the EdenDB
+//! language, functions may be added but existing ones must keep their behaviour.
+//!
+//! | function                | returns                                          |
+//! |-------------------------|--------------------------------------------------|
+//! | `regexp(pattern, text)` | 1 if text matches regex pattern, also `REGEXP`   |
+//! | `ip_in_cidr(ip, cidr)`  | 1 if IPv4/IPv6 address is inside network         |
+//! | `cidr_overlaps(a, b)`   | 1 if two networks share at least one address     |
+//! | `semver_cmp(a, b)`      | -1, 0 or 1 comparing semantic versions           |
+//! | `json_path(json, path)` | value at path like `$.a.b[0]`, NULL if missing   |
+//!
+//! All functions return NULL if any argument is NULL. `EXPORT LUA FUNCTION`
+//! can't use these names.
+
+use std::net::IpAddr;
+
+use regex::Regex;
+use rusqlite::{
+    functions::{Context, FunctionFlags},
+    types::Value as SqlValue,
+    Connection,
+};
+use serde_json::Value;
+
+pub const BUILTIN_SQL_FUNCTIONS: &[&str] =
+    &["regexp", "ip_in_cidr", "cidr_overlaps", "semver_cmp", "json_path"];
+
+pub(crate) fn register_builtin_sql_functions(conn: &Connection) -> rusqlite::Result<()> {
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+
+    conn.create_scalar_function("regexp", 2, flags, |ctx| {
+        let text = match ctx.get::<Option<String>>(1)? {
+            Some(t) => t,
+            None => return Ok(None),
+        };
+        if ctx.get_raw(0) == rusqlite::types::ValueRef::Null {
+            return Ok(None);
+        }
+        // pattern is usually constant, compile it once per statement
+        let re: std::sync::Arc<Regex> = ctx.get_or_create_aux(0, |v| {
+            let pattern = v.as_str().map_err(|e| e.to_string())?;
+            Regex::new(pattern).map_err(|e| format!("regexp: {e}"))
+        })?;
+        Ok(Some(re.is_match(&text)))
+    })?;
+
+    conn.create_scalar_function("ip_in_cidr", 2, flags, |ctx| {
+        let (ip, cidr) = match text_args(ctx)? {
+            Some(a) => a,
+            None => return Ok(None),
+        };
+        let ip = parse_ip(&ip).map_err(user_err)?;
+        let net = parse_cidr(&cidr).map_err(user_err)?;
+        Ok(Some(ip_in_network(&ip, &net)))
+    })?;
+
+    conn.create_scalar_function("cidr_overlaps", 2, flags, |ctx| {
+        let (a, b) = match text_args(ctx)? {
+            Some(a) => a,
+            None => return Ok(None),
+        };
+        let a = parse_cidr(&a).map_err(user_err)?;
+        let b = parse_cidr(&b).map_err(user_err)?;
+        let overlaps = a.bits == b.bits && {
+            let prefix = a.prefix.min(b.prefix);
+            let mask = network_mask(a.bits, prefix);
+            a.address & mask == b.address & mask
+        };
+        Ok(Some(overlaps))
+    })?;
+
+    conn.create_scalar_function("semver_cmp", 2, flags, |ctx| {
+        let (a, b) = match text_args(ctx)? {
+            Some(a) => a,
+            None => return Ok(None),
+        };
+        let a = SemVer::parse(&a).map_err(user_err)?;
+        let b = SemVer::parse(&b).map_err(user_err)?;
+        Ok(Some(a.cmp(&b) as i64))
+    })?;
+
+    conn.create_scalar_function("json_path", 2, flags, |ctx| {
+        let (json, path) = match text_args(ctx)? {
+            Some(a) => a,
+            None => return Ok(SqlValue::Null),
+        };
+        let json: Value =
+            serde_json::from_str(&json).map_err(|e| user_err(format!("json_path: {e}")))?;
+        let pointer = json_path_to_pointer(&path).map_err(user_err)?;
+        Ok(match json.pointer(&pointer) {
+            None | Some(Value::Null) => SqlValue::Null,
+            Some(Value::Bool(b)) => SqlValue::Integer(*b as i64),
+            Some(Value::Number(n)) => match n.as_i64() {
+                Some(i) => SqlValue::Integer(i),
+                None => SqlValue::Real(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            Some(Value::String(s)) => SqlValue::Text(s.clone()),
+            Some(other) => SqlValue::Text(other.to_string()),
+        })
+    })?;
+
+    Ok(())
+}
+
+fn user_err(e: String) -> rusqlite::Error {
+    rusqlite::Error::UserFunctionError(e.into())
+}
+
+fn text_args(ctx: &Context<'_>) -> rusqlite::Result<Option<(String, String)>> {
+    match (ctx.get::<Option<String>>(0)?, ctx.get::<Option<String>>(1)?) {
+        (Some(a), Some(b)) => Ok(Some((a, b))),
+        _ => Ok(None),
+    }
+}
+
+struct Network {
+    address: u128,
+    prefix: u32,
+    // 32 for IPv4, 128 for IPv6
+    bits: u32,
+}
+
+fn parse_ip(ip: &str) -> Result<Network, String> {
+    match ip.trim().parse::<IpAddr>() {
+        Ok(IpAddr::V4(v4)) => Ok(Network { address: u32::from(v4) as u128, prefix: 32, bits: 32 }),
+        Ok(IpAddr::V6(v6)) => Ok(Network { address: u128::from(v6), prefix: 128, bits: 128 }),
+        Err(_) => Err(format!("invalid ip address '{ip}'")),
+    }
+}
+
+fn parse_cidr(cidr: &str) -> Result<Network, String> {
+    let (ip, prefix) = cidr
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| format!("invalid network '{cidr}', expected format like 10.0.0.0/8"))?;
+    let mut net = parse_ip(ip)?;
+    net.prefix = match prefix.parse::<u32>() {
+        Ok(p) if p <= net.bits => p,
+        _ => return Err(format!("invalid network prefix length in '{cidr}'")),
+    };
+    Ok(net)
+}
+
+fn network_mask(bits: u32, prefix: u32) -> u128 {
+    if prefix == 0 {
+        0
+    } else {
+        (u128::MAX << (128 - prefix)) >> (128 - bits)
+    }
+}
+
+fn ip_in_network(ip: &Network, net: &Network) -> bool {
+    let mask = network_mask(net.bits, net.prefix);
+    ip.bits == net.bits && ip.address & mask == net.address & mask
+}
+
+/// Semantic version 2.0.0, build metadata is ignored in comparisons
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct SemVer {
+    core: [u64; 3],
+    // no pre release sorts after any pre release
+    is_release: bool,
+    pre_release: Vec<PreReleaseIdentifier>,
+}
+
+// numeric identifiers sort before alphanumeric ones
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PreReleaseIdentifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl SemVer {
+    fn parse(input: &str) -> Result<SemVer, String> {
+        let err = || format!("invalid semantic version '{input}'");
+        let without_build = input.split('+').next().unwrap_or_default();
+        let (core, pre_release) = match without_build.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (without_build, None),
+        };
+
+        let numeric = |s: &str| {
+            let valid = !s.is_empty()
+                && s.chars().all(|c| c.is_ascii_digit())
+                && (s == "0" || !s.starts_with('0'));
+            if valid {
+                s.parse::<u64>().ok()
+            } else {
+                None
+            }
+        };
+
+        let core_parts = core.split('.').collect::<Vec<_>>();
+        if core_parts.len() != 3 {
+            return Err(err());
+        }
+        let mut core = [0; 3];
+        for (i, p) in core_parts.iter().enumerate() {
+            core[i] = numeric(p).ok_or_else(err)?;
+        }
+
+        let mut identifiers = Vec::new();
+        if let Some(pre_release) = pre_release {
+            for i in pre_release.split('.') {
+                if i.is_empty() || !i.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                    return Err(err());
+                }
+                identifiers.push(match numeric(i) {
+                    Some(n) => PreReleaseIdentifier::Numeric(n),
+                    None if i.chars().all(|c| c.is_ascii_digit()) => return Err(err()),
+                    None => PreReleaseIdentifier::AlphaNumeric(i.to_string()),
+                });
+            }
+        }
+
+        Ok(SemVer { core, is_release: pre_release.is_none(), pre_release: identifiers })
+    }
+}
+
+/// `$.a.b[0]` is converted to json pointer `/a/b/0`
+fn json_path_to_pointer(path: &str) -> Result<String, String> {
+    let err = || format!("invalid json path '{path}', expected format like $.a.b[0]");
+    let mut rest = path.strip_prefix('$').ok_or_else(err)?;
+    let mut pointer = String::new();
+    while !rest.is_empty() {
+        let (segment, tail) = if let Some(r) = rest.strip_prefix('.') {
+            let end = r.find(['.', '[']).unwrap_or(r.len());
+            (&r[..end], &r[end..])
+        } else if let Some(r) = rest.strip_prefix('[') {
+            let end = r.find(']').ok_or_else(err)?;
+            let index = &r[..end];
+            if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+                return Err(err());
+            }
+            (index, &r[end + 1..])
+        } else {
+            return Err(err());
+        };
+        if segment.is_empty() {
+            return Err(err());
+        }
+        pointer += "/";
+        pointer += &segment.replace('~', "~0").replace('/', "~1");
+        rest = tail;
+    }
+    Ok(pointer)
+}
@@ -0,0 +1,207 @@
+#[cfg(test)]
+use super::common::assert_test_validaton_exception;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use crate::checker::logic::{AllData, ParentKeyRelationship};
+#[cfg(test)]
+use crate::checker::types::{DBIdentifier, SerializationVector};
+#[cfg(test)]
+use crate::db_parser::InputSource;
+
+#[test]
+fn test_ancestor_descendant_keys() {
+    let input = &mut [InputSource {
+        contents: Some(
+            r#"
+TABLE datacenter {
+    dc_name TEXT PRIMARY KEY,
+}
+
+TABLE rack {
+    rack_name TEXT PRIMARY KEY CHILD OF datacenter,
+}
+
+TABLE server {
+    hostname TEXT PRIMARY KEY CHILD OF rack,
+}
+
+TABLE disks {
+    disk_id TEXT PRIMARY KEY CHILD OF server,
+}
+
+DATA datacenter {
+    dc1 WITH rack {
+        r1 WITH server {
+            srv1 WITH disks {
+                d1;
+                d2;
+            };
+        };
+    };
+    dc2 WITH rack {
+        r1 WITH server {
+            srv2 WITH disks {
+                d3;
+            };
+        };
+        r2 WITH server {
+            srv3 WITH disks {
+                d4;
+            };
+        };
+    };
+}
+"#
+            .to_string(),
+        ),
+        path: "test".to_string(),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let parsed = crate::db_parser::parse_sources(input).unwrap();
+    let all_data = AllData::new(parsed).unwrap();
+    let keys = |ancestor: &str, descendant: &str| {
+        all_data
+            .ancestor_descendant_keys_map
+            .get(&ParentKeyRelationship {
+                parent_table: DBIdentifier::new(ancestor).unwrap(),
+                child_table: DBIdentifier::new(descendant).unwrap(),
+            })
+    };
+
+    // direct parents are served by parent and children columns
+    assert!(keys("server", "disks").is_none());
+    assert!(keys("datacenter", "rack").is_none());
+
+    let dc_disks = keys("datacenter", "disks").unwrap();
+    assert_eq!(dc_disks.parents_for_children_index, vec![0, 0, 1, 1]);
+    assert_eq!(dc_disks.children_for_parents_index, vec![vec![0, 1], vec![2, 3]]);
+
+    let rack_disks = keys("rack", "disks").unwrap();
+    assert_eq!(rack_disks.parents_for_children_index, vec![0, 0, 1, 2]);
+    assert_eq!(rack_disks.children_for_parents_index, vec![vec![0, 1], vec![2], vec![3]]);
+
+    let dc_servers = keys("datacenter", "server").unwrap();
+    assert_eq!(dc_servers.parents_for_children_index, vec![0, 1, 1]);
+    assert_eq!(dc_servers.children_for_parents_index, vec![vec![0], vec![1, 2]]);
+}
+
+#[test]
+fn test_ancestor_descendant_prefixed_user_columns() {
+    // only exact generated column names are taken, not whole prefixes
+    let input = &mut [InputSource {
+        contents: Some(
+            r#"
+TABLE datacenter {
+    dc_name TEXT PRIMARY KEY,
+    descendants_count INT DEFAULT 0,
+}
+
+TABLE rack {
+    rack_name TEXT PRIMARY KEY CHILD OF datacenter,
+}
+
+TABLE server {
+    hostname TEXT PRIMARY KEY CHILD OF rack,
+    ancestor_notes TEXT DEFAULT none,
+}
+
+DATA datacenter {
+    dc1 WITH rack {
+        r1 WITH server {
+            srv1;
+        };
+    };
+}
+"#
+            .to_string(),
+        ),
+        path: "test".to_string(),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let parsed = crate::db_parser::parse_sources(input).unwrap();
+    let all_data = AllData::new(parsed).unwrap();
+
+    let vector_names = all_data
+        .serialization_vectors()
+        .iter()
+        .map(|v| match v {
+            SerializationVector::Strings(sv) => sv.column_name.clone(),
+            SerializationVector::Ints(sv) => sv.column_name.clone(),
+            SerializationVector::Floats(sv) => sv.column_name.clone(),
+            SerializationVector::Bools(sv) => sv.column_name.clone(),
+            SerializationVector::Fkeys { sv, .. } => sv.column_name.clone(),
+            SerializationVector::FkeysOneToMany { sv, .. } => sv.column_name.clone(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vector_names,
+        vec![
+            "dc_name",
+            "descendants_count",
+            "children_rack",
+            "descendants_server",
+            "rack_name",
+            "parent",
+            "children_server",
+            "hostname",
+            "ancestor_notes",
+            "parent",
+            "ancestor_datacenter",
+        ]
+    );
+}
+
+#[test]
+fn test_ancestor_descendant_generated_column_conflict() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::AncestorDescendantColumnConflict {
+            table_name: "datacenter".to_string(),
+            column_name: "descendants_server".to_string(),
+            descendant_table: "server".to_string(),
+        },
+        r#"
+TABLE datacenter {
+    dc_name TEXT PRIMARY KEY,
+    descendants_server INT DEFAULT 0,
+}
+
+TABLE rack {
+    rack_name TEXT PRIMARY KEY CHILD OF datacenter,
+}
+
+TABLE server {
+    hostname TEXT PRIMARY KEY CHILD OF rack,
+}
+        "#,
+    );
+}
+
+#[test]
+fn test_ancestor_generated_column_conflict() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::AncestorDescendantColumnConflict {
+            table_name: "server".to_string(),
+            column_name: "ancestor_datacenter".to_string(),
+            descendant_table: "server".to_string(),
+        },
+        r#"
+TABLE datacenter {
+    dc_name TEXT PRIMARY KEY,
+}
+
+TABLE rack {
+    rack_name TEXT PRIMARY KEY CHILD OF datacenter,
+}
+
+TABLE server {
+    hostname TEXT PRIMARY KEY CHILD OF rack,
+    ancestor_datacenter TEXT DEFAULT dc,
+}
+        "#,
+    );
+}
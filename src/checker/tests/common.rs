@@ -21,7 +21,7 @@ pub fn random_test_dir() -> std::path::PathBuf {
 }
 
 #[cfg(test)]
-pub fn assert_compiles_data(source: &'static str, expected_json: serde_json::Value) {
+pub fn assert_compiles_data(source: &str, expected_json: serde_json::Value) {
     use assert_json_diff::assert_json_eq;
 
     let input = &mut [InputSource {
@@ -52,6 +52,36 @@ pub fn assert_compiles_data(source: &'static str, expected_json: serde_json::Val
     }
 }
 
+#[cfg(test)]
+pub fn assert_compiles_with_proof_warnings(
+    source: &str,
+    expected_warnings: Vec<DatabaseValidationError>,
+) {
+    let input = &mut [InputSource {
+        contents: Some(source.to_string()),
+        path: "test".to_string(),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let parsed = crate::db_parser::parse_sources(input);
+    match &parsed {
+        Err(e) => {
+            panic!("Error when parsing: {}", e);
+        }
+        Ok(_) => {}
+    }
+    let all_data = AllData::new(parsed.unwrap());
+    match all_data {
+        Ok(res) => {
+            assert_eq!(expected_warnings.as_slice(), res.proof_warnings())
+        }
+        Err(e) => {
+            panic!("Expected ok, got: {}", e)
+        }
+    }
+}
+
 #[cfg(test)]
 pub fn assert_compiles_data_paths(source: &[String], expected_json: serde_json::Value) {
     use assert_json_diff::assert_json_eq;
@@ -94,6 +124,45 @@ pub fn assert_compiles_data_paths(source: &[String], expected_json: serde_json::
     }
 }
 
+/// Returns either parsing or validation error, parsing errors
+/// must be `DatabaseValidationError`
+#[cfg(test)]
+pub fn assert_compiles_data_paths_error(source: &[String]) -> DatabaseValidationError {
+    let mut input = source
+        .iter()
+        .map(|i| {
+            let dir_path = i.to_string();
+            let mut p = std::fs::canonicalize(dir_path).unwrap();
+            let pres = p.pop();
+            assert!(pres);
+            let p = p.as_path().to_str().unwrap().to_string();
+            InputSource {
+                contents: None,
+                path: i.to_string(),
+                source_dir: Some(p),
+                line_comments: Vec::new(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let parsed = match crate::db_parser::parse_sources_with_external(input.as_mut_slice()) {
+        Ok(parsed) => parsed,
+        Err(e) => match e.downcast::<DatabaseValidationError>() {
+            Ok(e) => return *e,
+            Err(e) => panic!("Error when parsing: {}", e),
+        },
+    };
+    let all_data = AllData::new(parsed);
+    match all_data {
+        Ok(_) => {
+            panic!("Expected error when running this test, but passed");
+        }
+        Err(e) => {
+            e
+        }
+    }
+}
+
 #[cfg(test)]
 pub fn assert_compiles_data_paths_error_source_replacements(source: &[String], replacements: &str) -> DatabaseValidationError {
     let mut input = source
@@ -183,7 +252,7 @@ pub fn assert_compiles_data_with_source_replacements(source: &[String], replacem
 #[cfg(test)]
 pub fn assert_test_validaton_exception(
     expected_exception: DatabaseValidationError,
-    source: &'static str,
+    source: &str,
 ) {
     let input = &mut [InputSource {
         contents: Some(source.to_string()),
@@ -207,9 +276,31 @@ pub fn assert_test_validaton_exception(
     }
 }
 
+/// Validation errors which are detected while parsing sources
+#[cfg(test)]
+pub fn assert_parse_validation_exception(
+    expected_exception: DatabaseValidationError,
+    source: &str,
+) {
+    let mut input = [InputSource {
+        contents: Some(source.to_string()),
+        path: "test".to_string(),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    match crate::db_parser::parse_sources_with_external(&mut input) {
+        Ok(_) => panic!("Expected database validation error, parsing passed"),
+        Err(e) => match e.downcast::<DatabaseValidationError>() {
+            Ok(e) => assert_eq!(expected_exception, *e),
+            Err(e) => panic!("Unexpected parsing error: {}", e),
+        },
+    }
+}
+
 #[cfg(test)]
 pub fn assert_test_validaton_exception_return_error(
-    source: &'static str,
+    source: &str,
 ) -> DatabaseValidationError {
     let input = &mut [InputSource {
         path: "test".to_string(),
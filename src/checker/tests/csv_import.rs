@@ -0,0 +1,421 @@
+#[cfg(test)]
+use super::common::assert_compiles_data_paths;
+#[cfg(test)]
+use super::common::assert_compiles_data_paths_error;
+#[cfg(test)]
+use super::common::assert_compiles_data_paths_error_source_replacements;
+#[cfg(test)]
+use super::common::assert_compiles_data_with_source_replacements;
+#[cfg(test)]
+use super::common::random_test_dir;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use crate::checker::types::DBType;
+#[cfg(test)]
+use serde_json::json;
+
+#[cfg(test)]
+fn write_csv_test_files(source: &str, csv: &str) -> Vec<String> {
+    let tmp_dir = random_test_dir();
+    std::fs::write(tmp_dir.join("root.edl"), source).unwrap();
+    std::fs::write(tmp_dir.join("servers.csv"), csv).unwrap();
+
+    vec![tmp_dir.join("root.edl").to_str().unwrap().to_string()]
+}
+
+#[test]
+fn test_csv_import_defaults_and_foreign_keys() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE datacenter {
+  dc_name TEXT PRIMARY KEY,
+}
+
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  dc REF datacenter,
+  cores INT DEFAULT 4,
+  region TEXT DETACHED DEFAULT,
+  CHECK { cores > 0 }
+}
+
+DEFAULTS {
+  server.region eu,
+}
+
+DATA datacenter {
+  dc1;
+  dc2;
+}
+
+DATA EXCLUSIVE FROM CSV "servers.csv" INTO server
+"#,
+        "hostname,dc\nsrv-a,dc1\n\"srv,b\",dc2\n",
+    );
+
+    assert_compiles_data_paths(
+        paths.as_slice(),
+        json!({
+            "datacenter": [
+                {"dc_name": "dc1"},
+                {"dc_name": "dc2"},
+            ],
+            "server": [
+                {"hostname": "srv-a", "dc": "dc1", "cores": 4.0, "region": "eu"},
+                {"hostname": "srv,b", "dc": "dc2", "cores": 4.0, "region": "eu"},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_csv_import_header_mapping() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  cores INT,
+}
+
+DATA server {
+  srv-z, 1;
+}
+
+DATA FROM CSV "servers.csv" INTO server {
+  "Host Name" => hostname,
+  "CPU Cores" => cores,
+}
+"#,
+        "Rack, Host Name ,CPU Cores\nr1,srv-a,16\nr2,srv-b,32\n",
+    );
+
+    assert_compiles_data_paths(
+        paths.as_slice(),
+        json!({
+            "server": [
+                {"hostname": "srv-z", "cores": 1.0},
+                {"hostname": "srv-a", "cores": 16.0},
+                {"hostname": "srv-b", "cores": 32.0},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_csv_import_bad_value_line() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  cores INT,
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname,cores\n\nsrv-a,16\nsrv-b,lots\n",
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::CsvDataError {
+            csv_file: "servers.csv".to_string(),
+            line: 4,
+            error: Box::new(DatabaseValidationError::DataCannotParseDataColumnValue {
+                table_name: "server".to_string(),
+                row_index: 2,
+                column_index: 2,
+                column_name: "cores".to_string(),
+                column_value: "lots".to_string(),
+                expected_type: DBType::Int,
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_csv_import_missing_column() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  cores INT,
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname\nsrv-a\n",
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::CsvDataError {
+            csv_file: "servers.csv".to_string(),
+            line: 1,
+            error: Box::new(DatabaseValidationError::DataRequiredNonDefaultColumnValueNotProvided {
+                table_name: "server".to_string(),
+                column_name: "cores".to_string(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_csv_import_unequal_row_length() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  cores INT,
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname,cores\nsrv-a,16\nsrv-b\n",
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::CsvParsingError {
+            csv_file: "servers.csv".to_string(),
+            line: 3,
+            error: "expected 2 fields, found 1".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_csv_import_header_not_found() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+DATA FROM CSV "servers.csv" INTO server {
+  "Host Name" => hostname,
+}
+"#,
+        "hostname\nsrv-a\n",
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::CsvHeaderNotFound {
+            csv_file: "servers.csv".to_string(),
+            header: "Host Name".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_csv_import_non_existing_foreign_key() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE datacenter {
+  dc_name TEXT PRIMARY KEY,
+}
+
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  dc REF datacenter,
+}
+
+DATA datacenter {
+  dc1;
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname,dc\nsrv-a,dc1\nsrv-b,dc2\n",
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::CsvDataError {
+            csv_file: "servers.csv".to_string(),
+            line: 3,
+            error: Box::new(DatabaseValidationError::NonExistingForeignKey {
+                table_with_foreign_key: "server".to_string(),
+                foreign_key_column: "dc".to_string(),
+                referred_table: "datacenter".to_string(),
+                referred_table_column: "dc_name".to_string(),
+                key_value: "dc2".to_string(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_csv_import_duplicate_primary_key_line() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+DATA server {
+  srv-z;
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname\nsrv-a\n\nsrv-z\n",
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::CsvDataError {
+            csv_file: "servers.csv".to_string(),
+            line: 4,
+            error: Box::new(DatabaseValidationError::DuplicatePrimaryKey {
+                table_name: "server".to_string(),
+                value: "srv-z".to_string(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_csv_import_uniq_constraint_line() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  rack TEXT,
+  slot INT,
+  UNIQUE(rack, slot),
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname,rack,slot\nsrv-a,r1,1\nsrv-b,r1,2\nsrv-c,r1,1\n",
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::CsvDataError {
+            csv_file: "servers.csv".to_string(),
+            line: 4,
+            error: Box::new(DatabaseValidationError::UniqConstraintViolated {
+                table_name: "server".to_string(),
+                tuple_definition: "(rack, slot)".to_string(),
+                tuple_value: "(r1, 1)".to_string(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_csv_import_lua_check_line() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  cores INT,
+  CHECK { cores > 0 }
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname,cores\nsrv-a,4\nsrv-b,0\n",
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::CsvDataError {
+            csv_file: "servers.csv".to_string(),
+            line: 3,
+            error: Box::new(DatabaseValidationError::LuaCheckEvaluationFailed {
+                table_name: "server".to_string(),
+                expression: " cores > 0 ".to_string(),
+                column_names: vec!["hostname".to_string(), "cores".to_string()],
+                row_values: vec!["srv-b".to_string(), "0".to_string()],
+                error: "Expression check for the row didn't pass.".to_string(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_csv_import_replacements_not_supported() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  cores INT,
+}
+
+DATA server(hostname, cores) {
+  srv-z, 1;
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname,cores\n\"srv,a\",16\n",
+    );
+
+    let replacements = r#"
+{
+  "server": [
+    {
+      "primary_key": "srv,a",
+      "replacements": {
+        "cores": "32"
+      }
+    }
+  ]
+}
+"#;
+
+    assert_eq!(
+        assert_compiles_data_paths_error_source_replacements(paths.as_slice(), replacements),
+        DatabaseValidationError::ReplacementOverImportedValuesIsNotSupported {
+            table: "server".to_string(),
+            replacement_primary_key: "srv,a".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_csv_import_replacements_in_data_of_same_table() {
+    let paths = write_csv_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  cores INT,
+}
+
+DATA server(hostname, cores) {
+  srv-z, 1;
+}
+
+DATA FROM CSV "servers.csv" INTO server
+"#,
+        "hostname,cores\nsrv-a,16\n",
+    );
+
+    let replacements = r#"
+{
+  "server": [
+    {
+      "primary_key": "srv-z",
+      "replacements": {
+        "cores": "2"
+      }
+    }
+  ]
+}
+"#;
+
+    assert_compiles_data_with_source_replacements(
+        paths.as_slice(),
+        replacements,
+        &json!({
+            "server": [
+                {"hostname": "srv-z", "cores": 2.0},
+                {"hostname": "srv-a", "cores": 16.0},
+            ],
+        }),
+    );
+}
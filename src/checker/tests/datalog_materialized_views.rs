@@ -0,0 +1,332 @@
+#[cfg(test)]
+use super::common::assert_compiles_data;
+#[cfg(test)]
+use super::common::assert_test_validaton_exception;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use crate::checker::types::DBType;
+#[cfg(test)]
+use serde_json::json;
+
+#[cfg(test)]
+const DATALOG_MAT_VIEW_TEST_SCHEMA: &str = r#"
+TABLE link {
+    src TEXT,
+    dst TEXT,
+}
+
+DATA link {
+    a, b;
+    b, c;
+    c, d;
+}
+"#;
+
+#[cfg(test)]
+const DATALOG_MAT_VIEW_REACH_RULES: &str = r#"
+  reach(A, B) :- t_link__src(A, R), t_link__dst(B, R).
+  reach(A, C) :- reach(A, B), t_link__src(B, R), t_link__dst(C, R).
+  OUTPUT(A, B) :- reach(A, B).
+"#;
+
+#[test]
+fn test_datalog_mat_view_transitive_closure() {
+    let source = format!(
+        r#"{DATALOG_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW reach {{
+    src TEXT,
+    dst TEXT,
+}} AS DATALOG {{{DATALOG_MAT_VIEW_REACH_RULES}}}
+
+MATERIALIZED VIEW reach_from_a {{
+    dst TEXT,
+}} AS {{
+    SELECT dst FROM reach WHERE src = 'a'
+}}
+
+PROOF "no node reaches itself" NONE EXIST OF reach {{
+    SELECT rowid FROM reach WHERE src = dst
+}}
+"#
+    );
+    assert_compiles_data(
+        &source,
+        json!({
+            "link": [
+                {"src": "a", "dst": "b"},
+                {"src": "b", "dst": "c"},
+                {"src": "c", "dst": "d"},
+            ],
+            "reach": [
+                {"src": "a", "dst": "b"},
+                {"src": "a", "dst": "c"},
+                {"src": "a", "dst": "d"},
+                {"src": "b", "dst": "c"},
+                {"src": "b", "dst": "d"},
+                {"src": "c", "dst": "d"},
+            ],
+            "reach_from_a": [
+                {"dst": "b"},
+                {"dst": "c"},
+                {"dst": "d"},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_datalog_mat_view_typed_columns() {
+    let source = format!(
+        r#"{DATALOG_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW link_index {{
+    dst TEXT,
+    idx INT,
+    score FLOAT,
+}} AS DATALOG {{
+  OUTPUT(Dst, Idx, Score) :- t_link__dst(Dst, Idx), t_link__dst(Dst, Score).
+}}
+"#
+    );
+    assert_compiles_data(
+        &source,
+        json!({
+            "link": [
+                {"src": "a", "dst": "b"},
+                {"src": "b", "dst": "c"},
+                {"src": "c", "dst": "d"},
+            ],
+            "link_index": [
+                {"dst": "b", "idx": 0.0, "score": 0.0},
+                {"dst": "c", "idx": 1.0, "score": 1.0},
+                {"dst": "d", "idx": 2.0, "score": 2.0},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_datalog_mat_view_wrong_column_count() {
+    let source = format!(
+        r#"{DATALOG_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW reach {{
+    src TEXT,
+}} AS DATALOG {{{DATALOG_MAT_VIEW_REACH_RULES}}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogMatViewWrongColumnCount {
+            table_name: "reach".to_string(),
+            datalog_expression: DATALOG_MAT_VIEW_REACH_RULES.to_string(),
+            expected_columns: 1,
+            actual_columns: 2,
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_datalog_mat_view_wrong_column_type() {
+    let source = format!(
+        r#"{DATALOG_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW reach {{
+    src INT,
+    dst TEXT,
+}} AS DATALOG {{{DATALOG_MAT_VIEW_REACH_RULES}}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogMatViewWrongColumnTypeReturned {
+            table_name: "reach".to_string(),
+            datalog_expression: DATALOG_MAT_VIEW_REACH_RULES.to_string(),
+            column_name: "src".to_string(),
+            return_row_index: 1,
+            actual_column_type: "TEXT".to_string(),
+            expected_column_type: DBType::Int,
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_datalog_mat_view_duplicate_output_variables() {
+    let source = format!(
+        r#"{DATALOG_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW link_index {{
+    idx INT,
+    score FLOAT,
+}} AS DATALOG {{
+  OUTPUT(Idx, Idx) :- t_link__dst(_, Idx).
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogMatViewBadOutputRuleFormat {
+            error: "Output rule must have only distinct variables in its head.".to_string(),
+            table_name: "link_index".to_string(),
+            datalog_expression: "\n  OUTPUT(Idx, Idx) :- t_link__dst(_, Idx).\n".to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_datalog_mat_view_output_rule_not_found() {
+    let source = format!(
+        r#"{DATALOG_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW reach {{
+    src TEXT,
+}} AS DATALOG {{
+  reach(A) :- t_link__src(A, _).
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogMatViewOutputRuleNotFound {
+            error: "Datalog materialized view must contain output rule in the format like 'OUTPUT(A, B) :- t_some_table__a(A, Row), t_some_table__b(B, Row).'".to_string(),
+            table_name: "reach".to_string(),
+            datalog_expression: "\n  reach(A) :- t_link__src(A, _).\n".to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_datalog_mat_view_foreign_key_relations() {
+    assert_compiles_data(
+        r#"
+TABLE service {
+    name TEXT PRIMARY KEY,
+}
+
+TABLE dependency {
+    src REF service,
+    dst REF service,
+}
+
+DATA service {
+    web;
+    api;
+    db;
+}
+
+DATA dependency {
+    web, api;
+    api, db;
+}
+
+MATERIALIZED VIEW depends_on {
+    src TEXT,
+    dst TEXT,
+} AS DATALOG {
+  dep(S, D) :- fk_dependency__src(R, S), fk_dependency__dst(R, D).
+  dep(S, D) :- dep(S, M), dep(M, D).
+  OUTPUT(A, B) :- dep(S, D), t_service__name(A, S), t_service__name(B, D).
+}
+"#,
+        json!({
+            "service": [
+                {"name": "web"},
+                {"name": "api"},
+                {"name": "db"},
+            ],
+            "dependency": [
+                {"src": "web", "dst": "api"},
+                {"src": "api", "dst": "db"},
+            ],
+            "depends_on": [
+                {"src": "api", "dst": "db"},
+                {"src": "web", "dst": "api"},
+                {"src": "web", "dst": "db"},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_datalog_mat_view_ancestor_relations() {
+    assert_compiles_data(
+        r#"
+TABLE datacenter {
+    dc_name TEXT PRIMARY KEY,
+}
+
+TABLE rack {
+    rack_name TEXT PRIMARY KEY CHILD OF datacenter,
+}
+
+TABLE server {
+    hostname TEXT PRIMARY KEY CHILD OF rack,
+}
+
+DATA datacenter {
+    dc1 WITH rack {
+        r1 WITH server {
+            srv-a;
+        };
+    };
+}
+
+MATERIALIZED VIEW server_dc {
+    hostname TEXT,
+    dc_name TEXT,
+} AS DATALOG {
+  OUTPUT(H, D) :- ancestor_server_datacenter(S, Dc), t_server__hostname(H, S), t_datacenter__dc_name(D, Dc).
+}
+"#,
+        json!({
+            "datacenter": [
+                {"dc_name": "dc1"},
+            ],
+            "rack": [
+                {"dc_name": "dc1", "rack_name": "r1"},
+            ],
+            "server": [
+                {"dc_name": "dc1", "rack_name": "r1", "hostname": "srv-a"},
+            ],
+            "server_dc": [
+                {"hostname": "srv-a", "dc_name": "dc1"},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_datalog_mat_view_referred_by_table() {
+    assert_compiles_data(
+        r#"
+TABLE node {
+    name TEXT PRIMARY KEY,
+}
+
+DATA node {
+    a;
+}
+
+MATERIALIZED VIEW node_copy {
+    copy_name TEXT PRIMARY KEY,
+} AS DATALOG {
+  OUTPUT(N) :- t_node__name(N, _).
+}
+
+TABLE node_use {
+    node_ref REF node_copy,
+}
+
+DATA node_use {
+    a;
+}
+"#,
+        json!({
+            "node": [
+                {"name": "a"},
+            ],
+            "node_copy": [
+                {"copy_name": "a"},
+            ],
+            "node_use": [
+                {"node_ref": "a"},
+            ],
+        }),
+    );
+}
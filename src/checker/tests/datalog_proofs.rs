@@ -1,6 +1,8 @@
 #[cfg(test)]
 use super::common::assert_compiles_data;
 #[cfg(test)]
+use super::common::assert_compiles_with_proof_warnings;
+#[cfg(test)]
 use super::common::assert_test_validaton_exception;
 #[cfg(test)]
 use super::common::assert_test_validaton_exception_return_error;
@@ -214,6 +216,7 @@ fn test_datalog_proof_offenders_found() {
 }"
                 .to_string(),
             ],
+            offender_explanations: vec![],
         },
         r#"
 TABLE cholo {
@@ -244,6 +247,7 @@ fn test_datalog_proof_offenders_found_exact() {
   \"id\": 1.0
 }"
             .to_string()],
+            offender_explanations: vec![],
         },
         r#"
 TABLE cholo {
@@ -290,6 +294,264 @@ PROOF "fail 1" NONE EXIST OF cholo DATALOG {
     );
 }
 
+#[test]
+fn test_datalog_proof_offenders_found_with_explanation() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogProofOffendersFound {
+            table_name: "cholo".to_string(),
+            proof_expression: "\n    OUTPUT(Offender, Reason) :- t_cholo__id(1, Offender), t_cholo__reason(Reason, Offender).\n".to_string(),
+            comment: "fail with reason".to_string(),
+            offending_columns: vec!["{
+  \"id\": 1.0,
+  \"reason\": \"one is reserved\"
+}"
+            .to_string()],
+            offender_explanations: vec!["{
+  \"Reason\": \"one is reserved\"
+}"
+            .to_string()],
+        },
+        r#"
+TABLE cholo {
+    id INT,
+    reason TEXT,
+}
+
+DATA cholo {
+    1, one is reserved;
+    2, fine;
+}
+
+PROOF "fail with reason" NONE EXIST OF cholo DATALOG {
+    OUTPUT(Offender, Reason) :- t_cholo__id(1, Offender), t_cholo__reason(Reason, Offender).
+}
+        "#,
+    );
+}
+
+#[test]
+fn test_datalog_proof_warning_offenders_found() {
+    assert_compiles_with_proof_warnings(
+        r#"
+TABLE cholo {
+    id INT,
+}
+
+DATA cholo {
+    1;
+    2;
+}
+
+PROOF WARN "warn exact" NONE EXIST OF cholo DATALOG {
+    OUTPUT(Offender) :- t_cholo__id(2, Offender).
+}
+        "#,
+        vec![DatabaseValidationError::DatalogProofOffendersFound {
+            table_name: "cholo".to_string(),
+            proof_expression: "\n    OUTPUT(Offender) :- t_cholo__id(2, Offender).\n".to_string(),
+            comment: "warn exact".to_string(),
+            offending_columns: vec!["{
+  \"id\": 2.0
+}"
+            .to_string()],
+            offender_explanations: vec![],
+        }],
+    );
+}
+
+#[test]
+fn test_datalog_proof_exceptions() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogProofOffendersFound {
+            table_name: "cholo".to_string(),
+            proof_expression: "\n    OUTPUT(Offender) :- t_cholo__fine(false, Offender).\n".to_string(),
+            comment: "all fine".to_string(),
+            offending_columns: vec!["{
+  \"fine\": false,
+  \"id\": 3.0
+}"
+            .to_string()],
+            offender_explanations: vec![],
+        },
+        r#"
+TABLE cholo {
+    id INT PRIMARY KEY,
+    fine BOOL,
+}
+
+DATA cholo {
+    1, true;
+    2, false;
+    3, false;
+}
+
+PROOF "all fine" NONE EXIST OF cholo EXCEPT (2) UNTIL 2999-01-01 DATALOG {
+    OUTPUT(Offender) :- t_cholo__fine(false, Offender).
+}
+        "#,
+    );
+}
+
+#[test]
+fn test_datalog_proof_all_missing_rows() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::ProofRowsMissing {
+            table_name: "cholo".to_string(),
+            proof_expression: "\n    OUTPUT(Offender) :- t_cholo__fine(true, Offender).\n".to_string(),
+            comment: "all fine".to_string(),
+            missing_rows: vec!["{
+  \"fine\": false,
+  \"id\": 2.0
+}"
+            .to_string()],
+        },
+        r#"
+TABLE cholo {
+    id INT,
+    fine BOOL,
+}
+
+DATA cholo {
+    1, true;
+    2, false;
+}
+
+PROOF "exactly one fine" EXIST EXACTLY 1 OF cholo DATALOG {
+    OUTPUT(Offender) :- t_cholo__fine(true, Offender).
+}
+
+PROOF "all fine" ALL EXIST OF cholo DATALOG {
+    OUTPUT(Offender) :- t_cholo__fine(true, Offender).
+}
+        "#,
+    );
+}
+
+#[cfg(test)]
+const DATALOG_RELATIONS_TEST_SCHEMA: &str = r#"
+TABLE manufacturer {
+    name TEXT PRIMARY KEY,
+}
+
+TABLE server {
+    hostname TEXT PRIMARY KEY,
+}
+
+TABLE disk {
+    dev_slot TEXT PRIMARY KEY CHILD OF server,
+    make REF manufacturer,
+}
+
+TABLE partition {
+    part_no INT PRIMARY KEY CHILD OF disk,
+}
+
+DATA manufacturer {
+    intel;
+    crucial;
+}
+
+DATA server {
+    epyc1 WITH disk {
+        sda, intel WITH partition {
+            1;
+        };
+    };
+    epyc2 WITH disk {
+        sda, crucial WITH partition {
+            1;
+            2;
+        };
+    };
+}
+"#;
+
+#[test]
+fn test_datalog_proof_foreign_key_relation() {
+    let source = format!(
+        r#"{DATALOG_RELATIONS_TEST_SCHEMA}
+PROOF "no crucial disks" NONE EXIST OF disk DATALOG {{
+    OUTPUT(Offender) :- fk_disk__make(Offender, M), t_manufacturer__name("crucial", M).
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogProofOffendersFound {
+            table_name: "disk".to_string(),
+            proof_expression: "\n    OUTPUT(Offender) :- fk_disk__make(Offender, M), t_manufacturer__name(\"crucial\", M).\n".to_string(),
+            comment: "no crucial disks".to_string(),
+            offending_columns: vec!["{
+  \"dev_slot\": \"sda\",
+  \"hostname\": \"epyc2\",
+  \"make\": \"crucial\"
+}"
+            .to_string()],
+            offender_explanations: vec![],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_datalog_proof_parent_relation() {
+    let source = format!(
+        r#"{DATALOG_RELATIONS_TEST_SCHEMA}
+PROOF "no disks in epyc1" NONE EXIST OF disk DATALOG {{
+    OUTPUT(Offender) :- parent_disk(Offender, S), t_server__hostname("epyc1", S).
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogProofOffendersFound {
+            table_name: "disk".to_string(),
+            proof_expression: "\n    OUTPUT(Offender) :- parent_disk(Offender, S), t_server__hostname(\"epyc1\", S).\n".to_string(),
+            comment: "no disks in epyc1".to_string(),
+            offending_columns: vec!["{
+  \"dev_slot\": \"sda\",
+  \"hostname\": \"epyc1\",
+  \"make\": \"intel\"
+}"
+            .to_string()],
+            offender_explanations: vec![],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_datalog_proof_ancestor_prelude() {
+    let source = format!(
+        r#"{DATALOG_RELATIONS_TEST_SCHEMA}
+PROOF "no partitions in epyc2" NONE EXIST OF partition DATALOG {{
+    OUTPUT(Offender) :- ancestor_partition_server(Offender, S), t_server__hostname("epyc2", S).
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::DatalogProofOffendersFound {
+            table_name: "partition".to_string(),
+            proof_expression: "\n    OUTPUT(Offender) :- ancestor_partition_server(Offender, S), t_server__hostname(\"epyc2\", S).\n".to_string(),
+            comment: "no partitions in epyc2".to_string(),
+            offending_columns: vec![
+                "{
+  \"dev_slot\": \"sda\",
+  \"hostname\": \"epyc2\",
+  \"part_no\": 1.0
+}"
+                .to_string(),
+                "{
+  \"dev_slot\": \"sda\",
+  \"hostname\": \"epyc2\",
+  \"part_no\": 2.0
+}"
+                .to_string(),
+            ],
+            offender_explanations: vec![],
+        },
+        &source,
+    );
+}
+
 #[test]
 #[should_panic] // comparisons not implemented yet by datalog engine
 fn test_datalog_proof_offenders_found_comparison() {
@@ -309,6 +571,7 @@ fn test_datalog_proof_offenders_found_comparison() {
 }"
                 .to_string(),
             ],
+            offender_explanations: vec![],
         },
         r#"
 TABLE cholo {
@@ -25,6 +25,7 @@ fn test_lua_and_datalog_integration() {
   \"is_even\": true
 }"
             .to_string()],
+            offender_explanations: vec![],
         },
         r#"
 TABLE cholo {
@@ -0,0 +1,189 @@
+#[cfg(test)]
+use super::common::assert_compiles_data;
+#[cfg(test)]
+use super::common::assert_test_validaton_exception;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use crate::checker::types::DBType;
+#[cfg(test)]
+use serde_json::json;
+
+#[cfg(test)]
+const LUA_MAT_VIEW_TEST_SCHEMA: &str = r#"
+TABLE service {
+    name TEXT PRIMARY KEY,
+    replicas INT,
+}
+
+DATA service {
+    api, 3;
+    db, 1;
+}
+"#;
+
+#[test]
+fn test_lua_mat_view_port_assignment() {
+    let source = format!(
+        r#"{LUA_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW service_port {{
+    service REF service,
+    port INT,
+    weight FLOAT,
+    primary_replica BOOL,
+}} AS LUA {{
+  local res = {{}}
+  local port = 8000
+  for _, srv in ipairs(db.rows("service")) do
+    for i = 1, srv.replicas do
+      port = port + 1
+      table.insert(res, {{
+        service = srv.name,
+        port = port,
+        weight = i / 2,
+        primary_replica = i == 1,
+      }})
+    end
+  end
+  return res
+}}
+
+PROOF "no duplicate ports" NONE EXIST OF service_port {{
+  SELECT a.rowid
+  FROM service_port a
+  JOIN service_port b ON a.port = b.port AND a.rowid != b.rowid
+}}
+"#
+    );
+    assert_compiles_data(
+        &source,
+        json!({
+            "service": [
+                {"name": "api", "replicas": 3.0},
+                {"name": "db", "replicas": 1.0},
+            ],
+            "service_port": [
+                {"service": "api", "port": 8001.0, "weight": 0.5, "primary_replica": true},
+                {"service": "api", "port": 8002.0, "weight": 1.0, "primary_replica": false},
+                {"service": "api", "port": 8003.0, "weight": 1.5, "primary_replica": false},
+                {"service": "db", "port": 8004.0, "weight": 0.5, "primary_replica": true},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_lua_mat_view_rows_are_sorted() {
+    let source = format!(
+        r#"{LUA_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW reversed {{
+    name TEXT,
+}} AS LUA {{
+  return {{ {{ name = "db" }}, {{ name = "api" }} }}
+}}
+"#
+    );
+    assert_compiles_data(
+        &source,
+        json!({
+            "service": [
+                {"name": "api", "replicas": 3.0},
+                {"name": "db", "replicas": 1.0},
+            ],
+            "reversed": [
+                {"name": "api"},
+                {"name": "db"},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_lua_mat_view_wrong_column_type() {
+    let source = format!(
+        r#"{LUA_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW service_port {{
+    port INT,
+}} AS LUA {{
+  return {{ {{ port = 8000 }}, {{ port = "8001" }} }}
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaMatViewWrongColumnTypeReturned {
+            table_name: "service_port".to_string(),
+            lua_expression: "\n  return { { port = 8000 }, { port = \"8001\" } }\n".to_string(),
+            column_name: "port".to_string(),
+            return_row_index: 2,
+            actual_column_type: "TEXT".to_string(),
+            expected_column_type: DBType::Int,
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_mat_view_column_not_found() {
+    let source = format!(
+        r#"{LUA_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW service_port {{
+    service TEXT,
+    port INT,
+}} AS LUA {{
+  return {{ {{ service = "api" }} }}
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaMatViewColumnNotFound {
+            table_name: "service_port".to_string(),
+            lua_expression: "\n  return { { service = \"api\" } }\n".to_string(),
+            column_name: "port".to_string(),
+            return_row_index: 1,
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_mat_view_unknown_column() {
+    let source = format!(
+        r#"{LUA_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW service_port {{
+    port INT,
+}} AS LUA {{
+  return {{ {{ port = 8000, prot = 8000 }} }}
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaMatViewUnknownColumn {
+            table_name: "service_port".to_string(),
+            lua_expression: "\n  return { { port = 8000, prot = 8000 } }\n".to_string(),
+            column_name: "prot".to_string(),
+            return_row_index: 1,
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_mat_view_unexpected_return_type() {
+    let source = format!(
+        r#"{LUA_MAT_VIEW_TEST_SCHEMA}
+MATERIALIZED VIEW service_port {{
+    port INT,
+}} AS LUA {{
+  return 8000
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaMatViewUnexpectedReturnType {
+            table_name: "service_port".to_string(),
+            lua_expression: "\n  return 8000\n".to_string(),
+            error: "Lua materialized view must return array of rows, got integer".to_string(),
+        },
+        &source,
+    );
+}
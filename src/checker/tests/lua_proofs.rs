@@ -0,0 +1,257 @@
+#[cfg(test)]
+use super::common::assert_compiles_with_proof_warnings;
+#[cfg(test)]
+use super::common::assert_test_validaton_exception;
+#[cfg(test)]
+use super::common::assert_test_validaton_exception_return_error;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+
+#[cfg(test)]
+const LUA_PROOFS_TEST_SCHEMA: &str = r#"
+TABLE server_profile {
+    name TEXT PRIMARY KEY,
+    disk_count INT,
+}
+
+TABLE server {
+    hostname TEXT PRIMARY KEY,
+    profile REF server_profile,
+}
+
+TABLE disk {
+    dev_slot TEXT PRIMARY KEY CHILD OF server,
+}
+
+DATA server_profile {
+    storage, 2;
+    compute, 1;
+}
+
+DATA server {
+    epyc1, storage WITH disk {
+        sda;
+        sdb;
+    };
+    epyc2, storage WITH disk {
+        sda;
+    };
+    epyc3, compute WITH disk {
+        sda;
+    };
+}
+"#;
+
+#[test]
+fn test_lua_proof_offenders_found() {
+    let source = format!(
+        r#"{LUA_PROOFS_TEST_SCHEMA}
+PROOF "servers have disks required by profile" NONE EXIST OF server LUA {{
+    local res = {{}}
+    for _, srv in ipairs(db.rows("server")) do
+        local profile = db.find("server_profile", srv.profile)
+        local disks = db.children("server", srv.hostname, "disk")
+        if #disks ~= profile.disk_count then
+            table.insert(res, db.key("server", srv))
+        end
+    end
+    return res
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaProofOffendersFound {
+            table_name: "server".to_string(),
+            comment: "servers have disks required by profile".to_string(),
+            proof_expression: r#"
+    local res = {}
+    for _, srv in ipairs(db.rows("server")) do
+        local profile = db.find("server_profile", srv.profile)
+        local disks = db.children("server", srv.hostname, "disk")
+        if #disks ~= profile.disk_count then
+            table.insert(res, db.key("server", srv))
+        end
+    end
+    return res
+"#
+            .to_string(),
+            offending_columns: vec!["{
+  \"hostname\": \"epyc2\",
+  \"profile\": \"storage\"
+}"
+            .to_string()],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_proof_referrers_and_child_keys() {
+    let source = format!(
+        r#"{LUA_PROOFS_TEST_SCHEMA}
+PROOF "every profile is used by two servers" NONE EXIST OF server_profile LUA {{
+    local res = {{}}
+    for _, p in ipairs(db.rows("server_profile")) do
+        if #db.referrers("server_profile", p.name, "server") ~= 2 then
+            table.insert(res, p.name)
+        end
+    end
+    return res
+}}
+
+PROOF "only first disk of epyc2 offends" NONE EXIST OF disk EXCEPT (epyc2=>sda) LUA {{
+    return {{ "epyc2=>sda" }}
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaProofOffendersFound {
+            table_name: "server_profile".to_string(),
+            comment: "every profile is used by two servers".to_string(),
+            proof_expression: r#"
+    local res = {}
+    for _, p in ipairs(db.rows("server_profile")) do
+        if #db.referrers("server_profile", p.name, "server") ~= 2 then
+            table.insert(res, p.name)
+        end
+    end
+    return res
+"#
+            .to_string(),
+            offending_columns: vec!["{
+  \"disk_count\": 1.0,
+  \"name\": \"compute\"
+}"
+            .to_string()],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_proof_warning_and_quantifier() {
+    let source = format!(
+        r#"{LUA_PROOFS_TEST_SCHEMA}
+PROOF "exactly one compute server" EXIST EXACTLY 1 OF server LUA {{
+    local res = {{}}
+    for _, srv in ipairs(db.rows("server")) do
+        if srv.profile == "compute" then
+            table.insert(res, srv.hostname)
+        end
+    end
+    return res
+}}
+
+PROOF WARN "no epyc1" NONE EXIST OF server LUA {{ return {{ "epyc1" }} }}
+"#
+    );
+    assert_compiles_with_proof_warnings(
+        &source,
+        vec![DatabaseValidationError::LuaProofOffendersFound {
+            table_name: "server".to_string(),
+            comment: "no epyc1".to_string(),
+            proof_expression: r#" return { "epyc1" } "#.to_string(),
+            offending_columns: vec!["{
+  \"hostname\": \"epyc1\",
+  \"profile\": \"storage\"
+}"
+            .to_string()],
+        }],
+    );
+}
+
+#[test]
+fn test_lua_proof_offender_key_not_found() {
+    let source = format!(
+        r#"{LUA_PROOFS_TEST_SCHEMA}
+PROOF "bad key" NONE EXIST OF server LUA {{ return {{ "epyc7" }} }}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaProofOffenderKeyNotFound {
+            table_name: "server".to_string(),
+            comment: "bad key".to_string(),
+            offender_key: "epyc7".to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_proof_unexpected_return_type() {
+    let source = format!(
+        r#"{LUA_PROOFS_TEST_SCHEMA}
+PROOF "bad return" NONE EXIST OF server LUA {{ return 7 }}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaProofUnexpectedReturnType {
+            table_name: "server".to_string(),
+            comment: "bad return".to_string(),
+            proof_expression: " return 7 ".to_string(),
+            error: "Lua proof must return array of offending primary keys, got integer"
+                .to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_proof_rows_are_read_only() {
+    let source = format!(
+        r#"{LUA_PROOFS_TEST_SCHEMA}
+PROOF "mutate" NONE EXIST OF server LUA {{
+    db.find("server", "epyc1").profile = "compute"
+    return {{}}
+}}
+"#
+    );
+    match assert_test_validaton_exception_return_error(&source) {
+        DatabaseValidationError::LuaProofEvaluationError { comment, error, .. } => {
+            assert_eq!(comment, "mutate");
+            assert!(error.contains("row of table server is read only"), "{error}");
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn test_lua_proof_db_is_read_only() {
+    let source = format!(
+        r#"{LUA_PROOFS_TEST_SCHEMA}
+PROOF "replace find" NONE EXIST OF server LUA {{
+    db.find = function() return nil end
+    return {{}}
+}}
+"#
+    );
+    match assert_test_validaton_exception_return_error(&source) {
+        DatabaseValidationError::LuaProofEvaluationError { error, .. } => {
+            assert!(error.contains("db is read only"), "{error}");
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn test_lua_proof_rows_array_is_not_shared_between_proofs() {
+    let source = format!(
+        r#"{LUA_PROOFS_TEST_SCHEMA}
+PROOF "mutate" NONE EXIST OF server LUA {{
+    local servers = db.rows("server")
+    servers[1] = nil
+    table.insert(servers, {{ hostname = "fake" }})
+    return {{}}
+}}
+
+PROOF "still all servers" NONE EXIST OF server LUA {{
+    local servers = db.rows("server")
+    if #servers ~= 3 or servers[1].hostname ~= "epyc1" then
+        return {{ "epyc1" }}
+    end
+    return {{}}
+}}
+"#
+    );
+    assert_compiles_with_proof_warnings(&source, vec![]);
+}
@@ -0,0 +1,238 @@
+#[cfg(test)]
+use super::common::assert_compiles_data;
+#[cfg(test)]
+use super::common::assert_test_validaton_exception;
+#[cfg(test)]
+use super::common::assert_test_validaton_exception_return_error;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use serde_json::json;
+
+#[cfg(test)]
+const LUA_SQLITE_FUNCTIONS_TEST_SCHEMA: &str = r#"
+INCLUDE LUA {
+    function parse_version(v)
+        local major, minor, patch = v:match("^(%d+)%.(%d+)%.(%d+)$")
+        if major == nil then
+            return nil
+        end
+        return tonumber(major) * 10000 + tonumber(minor) * 100 + tonumber(patch)
+    end
+
+    function is_major(v)
+        return v % 10000 == 0
+    end
+
+    function half(v)
+        return v / 2
+    end
+
+    function broken(v)
+        return { v }
+    end
+}
+
+TABLE package {
+    name TEXT PRIMARY KEY,
+    version TEXT,
+}
+
+DATA package {
+    curl, 8.0.0;
+    openssl, 3.1.4;
+}
+"#;
+
+#[test]
+fn test_lua_sqlite_function_in_mat_view() {
+    let source = format!(
+        r#"{LUA_SQLITE_FUNCTIONS_TEST_SCHEMA}
+EXPORT LUA FUNCTION parse_version(TEXT) -> INT
+EXPORT LUA FUNCTION is_major(INT) -> BOOL
+EXPORT LUA FUNCTION half(INT) -> FLOAT
+
+MATERIALIZED VIEW package_version {{
+    name TEXT,
+    version INT,
+    is_major BOOL,
+    half_version FLOAT,
+}} AS {{
+    SELECT
+      name,
+      parse_version(version),
+      is_major(parse_version(version)),
+      half(parse_version(version))
+    FROM package
+}}
+"#
+    );
+    assert_compiles_data(
+        &source,
+        json!({
+            "package": [
+                {"name": "curl", "version": "8.0.0"},
+                {"name": "openssl", "version": "3.1.4"},
+            ],
+            "package_version": [
+                {"name": "curl", "version": 80000.0, "is_major": true, "half_version": 40000.0},
+                {"name": "openssl", "version": 30104.0, "is_major": false, "half_version": 15052.0},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_lua_sqlite_function_in_proof() {
+    let source = format!(
+        r#"{LUA_SQLITE_FUNCTIONS_TEST_SCHEMA}
+EXPORT LUA FUNCTION parse_version(TEXT) -> INT
+
+PROOF "openssl is at least 3.2" NONE EXIST OF package {{
+    SELECT rowid FROM package
+    WHERE name = 'openssl' AND parse_version(version) < parse_version('3.2.0')
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::SqlProofOffendersFound {
+            table_name: "package".to_string(),
+            comment: "openssl is at least 3.2".to_string(),
+            proof_expression: r#"
+    SELECT rowid FROM package
+    WHERE name = 'openssl' AND parse_version(version) < parse_version('3.2.0')
+"#
+            .to_string(),
+            offending_columns: vec![r#"{
+  "name": "openssl",
+  "version": "3.1.4"
+}"#
+            .to_string()],
+            offender_explanations: vec![],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_sqlite_function_nil_is_null() {
+    let source = format!(
+        r#"{LUA_SQLITE_FUNCTIONS_TEST_SCHEMA}
+EXPORT LUA FUNCTION parse_version(TEXT) -> INT
+
+PROOF "all versions are valid" NONE EXIST OF package {{
+    SELECT rowid FROM package WHERE parse_version(version || '-rc1') IS NOT NULL
+}}
+"#
+    );
+    assert_compiles_data(
+        &source,
+        json!({
+            "package": [
+                {"name": "curl", "version": "8.0.0"},
+                {"name": "openssl", "version": "3.1.4"},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_lua_sqlite_function_wrong_return_type() {
+    let source = format!(
+        r#"{LUA_SQLITE_FUNCTIONS_TEST_SCHEMA}
+EXPORT LUA FUNCTION broken(TEXT) -> TEXT
+
+MATERIALIZED VIEW broken_view {{
+    value TEXT,
+}} AS {{
+    SELECT broken(name) FROM package
+}}
+"#
+    );
+    let err = assert_test_validaton_exception_return_error(&source);
+    if let DatabaseValidationError::SqlMatViewStatementQueryException { error, .. } = err {
+        assert!(error.contains("Lua function broken must return TEXT, got *lua table*"));
+    } else {
+        panic!("unexpected error {:?}", err)
+    }
+}
+
+#[test]
+fn test_lua_sqlite_function_not_found() {
+    let source = format!(
+        r#"{LUA_SQLITE_FUNCTIONS_TEST_SCHEMA}
+EXPORT LUA FUNCTION parse_semver(TEXT) -> INT
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaFunctionExportNotFound {
+            function_name: "parse_semver".to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_sqlite_function_duplicate() {
+    let source = format!(
+        r#"{LUA_SQLITE_FUNCTIONS_TEST_SCHEMA}
+EXPORT LUA FUNCTION parse_version(TEXT) -> INT
+EXPORT LUA FUNCTION parse_version(TEXT) -> TEXT
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaFunctionExportDuplicate {
+            function_name: "parse_version".to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_sqlite_function_duplicate_different_case() {
+    let source = format!(
+        r#"{LUA_SQLITE_FUNCTIONS_TEST_SCHEMA}
+INCLUDE LUA {{
+    function PARSE_VERSION(v)
+        return 1
+    end
+}}
+EXPORT LUA FUNCTION parse_version(TEXT) -> INT
+EXPORT LUA FUNCTION PARSE_VERSION(TEXT) -> INT
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaFunctionExportDuplicate {
+            function_name: "PARSE_VERSION".to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_lua_sqlite_function_shadows_builtin() {
+    let source = format!(
+        r#"{LUA_SQLITE_FUNCTIONS_TEST_SCHEMA}
+INCLUDE LUA {{
+    function REGEXP(pattern, text)
+        return 1
+    end
+}}
+
+EXPORT LUA FUNCTION REGEXP(TEXT, TEXT) -> INT
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::LuaFunctionExportShadowsBuiltinFunction {
+            function_name: "REGEXP".to_string(),
+            builtin_functions: vec![
+                "regexp".to_string(),
+                "ip_in_cidr".to_string(),
+                "cidr_overlaps".to_string(),
+                "semver_cmp".to_string(),
+                "json_path".to_string(),
+            ],
+        },
+        &source,
+    );
+}
@@ -1320,6 +1320,29 @@ DATA cholo {
     );
 }
 
+#[test]
+fn test_prohibit_generated_inf_floats() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::NanOrInfiniteFloatNumbersAreNotAllowed {
+            table_name: "cholo".to_string(),
+            column_name: "ratio".to_string(),
+            column_value: "inf".to_string(),
+            row_index: 2,
+        },
+        r#"
+TABLE cholo {
+    col FLOAT,
+    ratio FLOAT GENERATED AS { 1.0 / col },
+}
+
+DATA cholo {
+    2.5;
+    0.0;
+}
+        "#,
+    );
+}
+
 #[test]
 fn test_prohibit_neg_inf_floats() {
     assert_test_validaton_exception(
@@ -1,19 +1,30 @@
+mod ancestor_descendant_keys;
 mod child_foreign_keys;
 mod child_keys_and_foreign_keys;
+mod csv_import;
 pub mod common;
 mod common_parent_fkeys;
 #[cfg(feature = "datalog")]
+mod datalog_materialized_views;
+#[cfg(feature = "datalog")]
 mod datalog_proofs;
 mod detached_defaults;
 mod integration;
 mod lua_column_checks;
 mod lua_data_insertion;
 mod lua_generated_columns;
+mod lua_materialized_views;
 mod lua_multifile;
+mod lua_proofs;
+mod lua_sqlite_functions;
 mod main;
 mod regression;
+mod row_constants;
+mod sqlite_import;
+mod sql_functions;
 mod sql_materialized_views;
 mod sql_proofs;
 mod struct_statement;
+mod structured_data_import;
 mod with_statement;
 mod replacements;
@@ -556,6 +556,60 @@ INCLUDE LUA {
     )
 }
 
+#[test]
+fn test_source_file_replacements_included_document_not_supported() {
+    let tmp_dir = random_test_dir();
+
+    std::fs::write(
+        tmp_dir.join("root.edl"),
+        r#"
+      TABLE test_table {
+        id INT PRIMARY KEY,
+        v1 TEXT,
+      }
+
+      INCLUDE JSON "rows.json"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        tmp_dir.join("rows.json"),
+        r#"{ "test_table": [{ "id": 1, "v1": "henlo" }] }"#,
+    )
+    .unwrap();
+
+    let replacements = r#"
+{
+  "test_table": [
+    {
+      "primary_key": "1",
+      "replacements": {
+        "v1": "holo"
+      }
+    }
+  ]
+}
+"#;
+
+    let paths = [
+      "root.edl",
+    ]
+        .iter()
+        .map(|i| tmp_dir.join(i).to_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        assert_compiles_data_paths_error_source_replacements(
+            paths.as_slice(),
+            replacements,
+        ),
+        DatabaseValidationError::ReplacementOverIncludedDocumentValuesIsNotSupported {
+            table: "test_table".to_string(),
+            replacement_primary_key: "1".to_string(),
+        },
+    )
+}
+
 #[test]
 fn test_source_file_replacement_for_parent_keys_not_allowed() {
     let tmp_dir = random_test_dir();
@@ -0,0 +1,118 @@
+#[cfg(test)]
+use super::common::assert_test_validaton_exception;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use crate::checker::logic::AllData;
+#[cfg(test)]
+use crate::checker::types::DBIdentifier;
+#[cfg(test)]
+use crate::db_parser::InputSource;
+
+#[test]
+fn test_row_constants_table_without_primary_key() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::RowConstantsTableHasNoPrimaryKey {
+            table_name: "disk_manufacturer".to_string(),
+        },
+        r#"
+TABLE disk_manufacturer CONSTANTS {
+    model TEXT,
+}
+        "#,
+    );
+}
+
+#[test]
+fn test_row_constants_duplicate_name() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::RowConstantsDuplicateName {
+            table_name: "disk_manufacturer".to_string(),
+            constant_name: "disk_manufacturer_western_digital".to_string(),
+        },
+        r#"
+TABLE disk_manufacturer CONSTANTS {
+    model TEXT PRIMARY KEY,
+}
+
+DATA disk_manufacturer {
+    western-digital;
+    western_digital;
+}
+        "#,
+    );
+}
+
+#[test]
+fn test_row_constants_name_collision_between_tables() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::RowConstantsNameCollisionBetweenTables {
+            constant_name: "disk_manufacturer_intel".to_string(),
+            first_table_name: "disk".to_string(),
+            second_table_name: "disk_manufacturer".to_string(),
+        },
+        r#"
+TABLE disk CONSTANTS {
+    model TEXT PRIMARY KEY,
+}
+
+TABLE disk_manufacturer CONSTANTS {
+    name TEXT PRIMARY KEY,
+}
+
+DATA disk {
+    manufacturer_intel;
+}
+
+DATA disk_manufacturer {
+    intel;
+}
+        "#,
+    );
+}
+
+#[test]
+fn test_row_constants_names() {
+    let input = &mut [InputSource {
+        contents: Some(
+            r#"
+TABLE server CONSTANTS {
+    hostname TEXT PRIMARY KEY,
+}
+
+TABLE disks CONSTANTS {
+    disk_id TEXT PRIMARY KEY CHILD OF server,
+}
+
+TABLE no_constants {
+    id INT PRIMARY KEY,
+}
+
+DATA server {
+    Epyc-1 WITH disks {
+        root.disk;
+    };
+}
+
+DATA no_constants {
+    1;
+}
+"#
+            .to_string(),
+        ),
+        path: "test".to_string(),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let parsed = crate::db_parser::parse_sources(input).unwrap();
+    let all_data = AllData::new(parsed).unwrap();
+    let names = |t: &str| {
+        let idx = all_data.find_table_named_idx(&DBIdentifier::new(t).unwrap());
+        all_data.tables[idx[0]].row_constant_names()
+    };
+
+    assert_eq!(names("server"), vec!["server_epyc_1".to_string()]);
+    assert_eq!(names("disks"), vec!["disks_epyc_1_root_disk".to_string()]);
+    assert!(names("no_constants").is_empty());
+}
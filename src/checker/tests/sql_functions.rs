@@ -0,0 +1,247 @@
+#[cfg(test)]
+use super::common::assert_compiles_data;
+#[cfg(test)]
+use super::common::assert_test_validaton_exception;
+#[cfg(test)]
+use super::common::assert_test_validaton_exception_return_error;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use serde_json::json;
+
+#[test]
+fn test_sql_function_regexp() {
+    assert_compiles_data(
+        r#"
+MATERIALIZED VIEW cholo {
+    matches BOOL,
+    operator_matches BOOL,
+    no_match BOOL,
+} AS {
+    SELECT
+      regexp('^srv-[0-9]+$', 'srv-123'),
+      'srv-1' REGEXP '^srv-\d$',
+      regexp('^srv-[0-9]+$', 'srv-abc')
+}
+        "#,
+        json!({
+            "cholo": [
+                {"matches": true, "operator_matches": true, "no_match": false},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_sql_function_regexp_in_proof() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::SqlProofOffendersFound {
+            table_name: "server".to_string(),
+            comment: "hostnames are valid".to_string(),
+            proof_expression: "\n    SELECT rowid FROM server WHERE NOT hostname REGEXP '^[a-z0-9-]+$'\n"
+                .to_string(),
+            offending_columns: vec![r#"{
+  "hostname": "Bad_Host"
+}"#
+            .to_string()],
+            offender_explanations: vec![],
+        },
+        r#"
+TABLE server {
+    hostname TEXT PRIMARY KEY,
+}
+
+DATA server {
+    good-host;
+    Bad_Host;
+}
+
+PROOF "hostnames are valid" NONE EXIST OF server {
+    SELECT rowid FROM server WHERE NOT hostname REGEXP '^[a-z0-9-]+$'
+}
+        "#,
+    );
+}
+
+#[test]
+fn test_sql_function_ip_in_cidr() {
+    assert_compiles_data(
+        r#"
+MATERIALIZED VIEW cholo {
+    v4_inside BOOL,
+    v4_outside BOOL,
+    v4_host_route BOOL,
+    v6_inside BOOL,
+    mixed_families BOOL,
+} AS {
+    SELECT
+      ip_in_cidr('10.17.3.4', '10.16.0.0/12'),
+      ip_in_cidr('10.32.0.1', '10.16.0.0/12'),
+      ip_in_cidr('192.168.1.1', '192.168.1.1/32'),
+      ip_in_cidr('2001:db8::1', '2001:db8::/32'),
+      ip_in_cidr('10.0.0.1', '::/0')
+}
+        "#,
+        json!({
+            "cholo": [
+                {
+                    "v4_inside": true,
+                    "v4_outside": false,
+                    "v4_host_route": true,
+                    "v6_inside": true,
+                    "mixed_families": false,
+                },
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_sql_function_cidr_overlaps() {
+    assert_compiles_data(
+        r#"
+MATERIALIZED VIEW cholo {
+    nested BOOL,
+    disjoint BOOL,
+    everything BOOL,
+    v6_disjoint BOOL,
+} AS {
+    SELECT
+      cidr_overlaps('10.0.0.0/8', '10.1.2.0/24'),
+      cidr_overlaps('10.0.0.0/24', '10.0.1.0/24'),
+      cidr_overlaps('0.0.0.0/0', '172.16.0.0/12'),
+      cidr_overlaps('2001:db8::/48', '2001:db8:1::/48')
+}
+        "#,
+        json!({
+            "cholo": [
+                {"nested": true, "disjoint": false, "everything": true, "v6_disjoint": false},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_sql_function_invalid_cidr() {
+    let err = assert_test_validaton_exception_return_error(
+        r#"
+MATERIALIZED VIEW cholo {
+    inside BOOL,
+} AS {
+    SELECT ip_in_cidr('10.0.0.1', '10.0.0.0/33')
+}
+        "#,
+    );
+    if let DatabaseValidationError::SqlMatViewStatementQueryException { error, .. } = err {
+        assert!(error.contains("invalid network prefix length in '10.0.0.0/33'"));
+    } else {
+        panic!("unexpected error {:?}", err)
+    }
+}
+
+#[test]
+fn test_sql_function_semver_cmp() {
+    assert_compiles_data(
+        r#"
+MATERIALIZED VIEW cholo {
+    less INT,
+    equal_ignoring_build INT,
+    greater_numeric INT,
+    release_after_pre INT,
+    pre_release_order INT,
+} AS {
+    SELECT
+      semver_cmp('1.2.3', '1.10.0'),
+      semver_cmp('1.2.3+build.5', '1.2.3'),
+      semver_cmp('2.0.0', '1.99.99'),
+      semver_cmp('1.0.0', '1.0.0-rc.1'),
+      semver_cmp('1.0.0-alpha.1', '1.0.0-alpha.beta')
+}
+        "#,
+        json!({
+            "cholo": [
+                {
+                    "less": -1.0,
+                    "equal_ignoring_build": 0.0,
+                    "greater_numeric": 1.0,
+                    "release_after_pre": 1.0,
+                    "pre_release_order": -1.0,
+                },
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_sql_function_semver_cmp_invalid() {
+    let err = assert_test_validaton_exception_return_error(
+        r#"
+MATERIALIZED VIEW cholo {
+    cmp INT,
+} AS {
+    SELECT semver_cmp('1.2', '1.2.0')
+}
+        "#,
+    );
+    if let DatabaseValidationError::SqlMatViewStatementQueryException { error, .. } = err {
+        assert!(error.contains("invalid semantic version '1.2'"));
+    } else {
+        panic!("unexpected error {:?}", err)
+    }
+}
+
+#[test]
+fn test_sql_function_json_path() {
+    assert_compiles_data(
+        r#"
+TABLE server {
+    hostname TEXT PRIMARY KEY,
+    labels TEXT,
+}
+
+DATA server {
+    epyc-1, '{"rack": "r1", "slots": [4, 8], "spare": true, "nested": {"a": 1.5}}';
+}
+
+MATERIALIZED VIEW server_labels {
+    hostname TEXT,
+    rack TEXT,
+    second_slot INT,
+    spare BOOL,
+    nested_a FLOAT,
+    slots TEXT,
+} AS {
+    SELECT
+      hostname,
+      json_path(labels, '$.rack'),
+      json_path(labels, '$.slots[1]'),
+      json_path(labels, '$.spare'),
+      json_path(labels, '$.nested.a'),
+      json_path(labels, '$.slots')
+    FROM server
+}
+
+PROOF "missing paths are null" ALL EXIST OF server {
+    SELECT rowid FROM server WHERE json_path(labels, '$.missing[3]') IS NULL
+}
+        "#,
+        json!({
+            "server": [
+                {
+                    "hostname": "epyc-1",
+                    "labels": r#"{"rack": "r1", "slots": [4, 8], "spare": true, "nested": {"a": 1.5}}"#,
+                },
+            ],
+            "server_labels": [
+                {
+                    "hostname": "epyc-1",
+                    "rack": "r1",
+                    "second_slot": 8.0,
+                    "spare": true,
+                    "nested_a": 1.5,
+                    "slots": "[4,8]",
+                },
+            ],
+        }),
+    );
+}
@@ -1,4 +1,8 @@
 #[cfg(test)]
+use super::common::assert_compiles_with_proof_warnings;
+#[cfg(test)]
+use super::common::assert_parse_validation_exception;
+#[cfg(test)]
 use super::common::assert_test_validaton_exception;
 #[cfg(test)]
 use crate::checker::errors::DatabaseValidationError;
@@ -49,7 +53,7 @@ fn test_sql_proof_invalid_column_count() {
         DatabaseValidationError::SqlProofQueryErrorSingleRowIdColumnExpected {
             table_name: "cholo".to_string(),
             proof_expression: " SELECT 1, 2 ".to_string(),
-            error: "Required output column count is 1, got 2".to_string(),
+            error: "Required output column name must be rowid, got 1".to_string(),
             comment: "too many columns".to_string(),
         },
         r#"
@@ -187,6 +191,7 @@ fn test_sql_proof_offenders_found() {
 }"
                 .to_string(),
             ],
+            offender_explanations: vec![],
         },
         r#"
 TABLE cholo {
@@ -217,6 +222,7 @@ fn test_sql_proof_boolean_offenders_found() {
   \"offends\": true
 }"
             .to_string()],
+            offender_explanations: vec![],
         },
         r#"
 TABLE cholo {
@@ -234,3 +240,409 @@ PROOF "offends column" NONE EXIST OF cholo { SELECT rowid FROM cholo WHERE offen
         "#,
     );
 }
+
+#[test]
+fn test_sql_proof_offenders_found_with_explanation() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::SqlProofOffendersFound {
+            table_name: "server".to_string(),
+            proof_expression: "
+    SELECT server.rowid, count(disk.rowid) AS disk_count, 'expected 4' AS reason
+    FROM server
+    LEFT JOIN disk ON disk.hostname = server.hostname
+    GROUP BY server.rowid
+    HAVING disk_count != 4
+".to_string(),
+            comment: "every server has four disks".to_string(),
+            offending_columns: vec!["{
+  \"hostname\": \"epyc2\"
+}"
+            .to_string()],
+            offender_explanations: vec!["{
+  \"disk_count\": 3,
+  \"reason\": \"expected 4\"
+}"
+            .to_string()],
+        },
+        r#"
+TABLE server {
+    hostname TEXT PRIMARY KEY,
+}
+
+TABLE disk {
+    dev_slot TEXT PRIMARY KEY CHILD OF server,
+}
+
+DATA server {
+    epyc1 WITH disk {
+        sda; sdb; sdc; sdd;
+    };
+    epyc2 WITH disk {
+        sda; sdb; sdc;
+    };
+}
+
+PROOF "every server has four disks" NONE EXIST OF server {
+    SELECT server.rowid, count(disk.rowid) AS disk_count, 'expected 4' AS reason
+    FROM server
+    LEFT JOIN disk ON disk.hostname = server.hostname
+    GROUP BY server.rowid
+    HAVING disk_count != 4
+}
+        "#,
+    );
+}
+
+#[test]
+fn test_sql_proof_warning_offenders_found() {
+    assert_compiles_with_proof_warnings(
+        r#"
+TABLE cholo {
+    id INT,
+}
+
+DATA cholo {
+    1;
+    2;
+}
+
+PROOF WARN "ids should stay below 2" NONE EXIST OF cholo { SELECT rowid FROM cholo WHERE id >= 2 }
+
+PROOF ERROR "ids are positive" NONE EXIST OF cholo { SELECT rowid FROM cholo WHERE id < 1 }
+        "#,
+        vec![DatabaseValidationError::SqlProofOffendersFound {
+            table_name: "cholo".to_string(),
+            proof_expression: " SELECT rowid FROM cholo WHERE id >= 2 ".to_string(),
+            comment: "ids should stay below 2".to_string(),
+            offending_columns: vec!["{
+  \"id\": 2.0
+}"
+            .to_string()],
+            offender_explanations: vec![],
+        }],
+    );
+}
+
+#[test]
+fn test_sql_proof_explicit_error_severity() {
+    assert_test_validaton_exception(
+        DatabaseValidationError::SqlProofOffendersFound {
+            table_name: "cholo".to_string(),
+            proof_expression: " SELECT rowid FROM cholo WHERE id >= 2 ".to_string(),
+            comment: "ids must stay below 2".to_string(),
+            offending_columns: vec!["{
+  \"id\": 2.0
+}"
+            .to_string()],
+            offender_explanations: vec![],
+        },
+        r#"
+TABLE cholo {
+    id INT,
+}
+
+DATA cholo {
+    1;
+    2;
+}
+
+PROOF WARN "no ids are negative" NONE EXIST OF cholo { SELECT rowid FROM cholo WHERE id < 0 }
+
+PROOF ERROR "ids must stay below 2" NONE EXIST OF cholo { SELECT rowid FROM cholo WHERE id >= 2 }
+        "#,
+    );
+}
+
+#[cfg(test)]
+const PROOF_EXCEPTIONS_TEST_SCHEMA: &str = r#"
+TABLE server {
+    hostname TEXT PRIMARY KEY,
+}
+
+TABLE disk {
+    dev_slot TEXT PRIMARY KEY CHILD OF server,
+    size_gb INT,
+}
+
+DATA server {
+    legacy-1 WITH disk {
+        sda, 10;
+    };
+    legacy-2 WITH disk {
+        sda, 20;
+        sdb, 100;
+    };
+    epyc1 WITH disk {
+        sda, 100;
+    };
+}
+"#;
+
+#[test]
+fn test_sql_proof_exceptions_allowed() {
+    let source = format!(
+        r#"{PROOF_EXCEPTIONS_TEST_SCHEMA}
+PROOF "disks are at least 50GB" NONE EXIST OF disk EXCEPT (legacy-1=>sda, "legacy-2=>sda") UNTIL 2999-12-31 {{
+    SELECT rowid FROM disk WHERE size_gb < 50
+}}
+"#
+    );
+    assert_compiles_with_proof_warnings(&source, vec![]);
+}
+
+#[test]
+fn test_sql_proof_exceptions_other_offender() {
+    let source = format!(
+        r#"{PROOF_EXCEPTIONS_TEST_SCHEMA}
+PROOF "disks are at least 50GB" NONE EXIST OF disk EXCEPT (legacy-1=>sda) {{ SELECT rowid FROM disk WHERE size_gb < 50 }}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::SqlProofOffendersFound {
+            table_name: "disk".to_string(),
+            proof_expression: " SELECT rowid FROM disk WHERE size_gb < 50 ".to_string(),
+            comment: "disks are at least 50GB".to_string(),
+            offending_columns: vec!["{
+  \"dev_slot\": \"sda\",
+  \"hostname\": \"legacy-2\",
+  \"size_gb\": 20.0
+}"
+            .to_string()],
+            offender_explanations: vec![],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_sql_proof_exceptions_key_not_offending() {
+    let source = format!(
+        r#"{PROOF_EXCEPTIONS_TEST_SCHEMA}
+PROOF "disks are at least 50GB" NONE EXIST OF disk EXCEPT (legacy-1=>sda, legacy-2=>sda, epyc1=>sda) {{
+    SELECT rowid FROM disk WHERE size_gb < 50
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::ProofExceptionKeysNotOffending {
+            table_name: "disk".to_string(),
+            comment: "disks are at least 50GB".to_string(),
+            keys: vec!["epyc1=>sda".to_string()],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_sql_proof_exceptions_expired() {
+    let source = format!(
+        r#"{PROOF_EXCEPTIONS_TEST_SCHEMA}
+PROOF "disks are at least 50GB" NONE EXIST OF disk EXCEPT (legacy-1=>sda, legacy-2=>sda) UNTIL 2020-01-31 {{
+    SELECT rowid FROM disk WHERE size_gb < 50
+}}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::ProofExceptionsExpired {
+            table_name: "disk".to_string(),
+            comment: "disks are at least 50GB".to_string(),
+            until: "2020-01-31".to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_sql_proof_exceptions_invalid_date() {
+    let source = format!(
+        r#"{PROOF_EXCEPTIONS_TEST_SCHEMA}
+PROOF "disks are at least 50GB" NONE EXIST OF disk EXCEPT (legacy-1=>sda) UNTIL 2999-13-01 {{
+    SELECT rowid FROM disk WHERE size_gb < 50
+}}
+"#
+    );
+    assert_parse_validation_exception(
+        DatabaseValidationError::ProofExceptionsInvalidDate {
+            table_name: "disk".to_string(),
+            comment: "disks are at least 50GB".to_string(),
+            date: "2999-13-01".to_string(),
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_sql_proof_exceptions_nonexistent_dates() {
+    for date in ["2999-02-29", "2100-02-29", "2026-02-31", "2026-04-31", "2026-01-00"] {
+        // no offenders, date is still checked
+        let source = format!(
+            r#"{PROOF_EXCEPTIONS_TEST_SCHEMA}
+PROOF "disks are at least 1GB" NONE EXIST OF disk EXCEPT (legacy-1=>sda) UNTIL {date} {{
+    SELECT rowid FROM disk WHERE size_gb < 1
+}}
+"#
+        );
+        assert_parse_validation_exception(
+            DatabaseValidationError::ProofExceptionsInvalidDate {
+                table_name: "disk".to_string(),
+                comment: "disks are at least 1GB".to_string(),
+                date: date.to_string(),
+            },
+            &source,
+        );
+    }
+}
+
+#[test]
+fn test_sql_proof_exceptions_leap_day() {
+    let source = format!(
+        r#"{PROOF_EXCEPTIONS_TEST_SCHEMA}
+PROOF "disks are at least 50GB" NONE EXIST OF disk EXCEPT (legacy-1=>sda, legacy-2=>sda) UNTIL 2996-02-29 {{
+    SELECT rowid FROM disk WHERE size_gb < 50
+}}
+"#
+    );
+    assert_compiles_with_proof_warnings(&source, vec![]);
+}
+
+#[cfg(test)]
+const PROOF_QUANTIFIERS_TEST_SCHEMA: &str = r#"
+TABLE dns_server {
+    hostname TEXT PRIMARY KEY,
+    is_primary BOOL,
+    port INT,
+}
+
+DATA dns_server {
+    ns1, true, 53;
+    ns2, false, 53;
+    ns3, false, 5353;
+}
+"#;
+
+#[test]
+fn test_sql_proof_quantifiers_hold() {
+    let source = format!(
+        r#"{PROOF_QUANTIFIERS_TEST_SCHEMA}
+PROOF "exactly one primary" EXIST EXACTLY 1 OF dns_server {{
+    SELECT rowid FROM dns_server WHERE is_primary
+}}
+
+PROOF "at least two on standard port" EXIST AT LEAST 2 OF dns_server {{
+    SELECT rowid FROM dns_server WHERE port = 53
+}}
+
+PROOF "all have ports" ALL EXIST OF dns_server {{
+    SELECT rowid FROM dns_server WHERE port > 0
+}}
+"#
+    );
+    assert_compiles_with_proof_warnings(&source, vec![]);
+}
+
+#[test]
+fn test_sql_proof_exactly_surplus_rows() {
+    let source = format!(
+        r#"{PROOF_QUANTIFIERS_TEST_SCHEMA}
+PROOF "exactly one secondary" EXIST EXACTLY 1 OF dns_server {{ SELECT rowid FROM dns_server WHERE NOT is_primary }}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::ProofRowCountMismatch {
+            table_name: "dns_server".to_string(),
+            comment: "exactly one secondary".to_string(),
+            proof_expression: " SELECT rowid FROM dns_server WHERE NOT is_primary ".to_string(),
+            expected_rows: "exactly 1".to_string(),
+            found_rows: 2,
+            matching_rows: vec![
+                "{
+  \"hostname\": \"ns2\",
+  \"is_primary\": false,
+  \"port\": 53.0
+}"
+                .to_string(),
+                "{
+  \"hostname\": \"ns3\",
+  \"is_primary\": false,
+  \"port\": 5353.0
+}"
+                .to_string(),
+            ],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_sql_proof_at_least_too_few_rows() {
+    let source = format!(
+        r#"{PROOF_QUANTIFIERS_TEST_SCHEMA}
+PROOF "two primaries" EXIST AT LEAST 2 OF dns_server {{ SELECT rowid FROM dns_server WHERE is_primary }}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::ProofRowCountMismatch {
+            table_name: "dns_server".to_string(),
+            comment: "two primaries".to_string(),
+            proof_expression: " SELECT rowid FROM dns_server WHERE is_primary ".to_string(),
+            expected_rows: "at least 2".to_string(),
+            found_rows: 1,
+            matching_rows: vec!["{
+  \"hostname\": \"ns1\",
+  \"is_primary\": true,
+  \"port\": 53.0
+}"
+            .to_string()],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_sql_proof_all_missing_rows() {
+    let source = format!(
+        r#"{PROOF_QUANTIFIERS_TEST_SCHEMA}
+PROOF "all on standard port" ALL EXIST OF dns_server {{ SELECT rowid FROM dns_server WHERE port = 53 }}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::ProofRowsMissing {
+            table_name: "dns_server".to_string(),
+            comment: "all on standard port".to_string(),
+            proof_expression: " SELECT rowid FROM dns_server WHERE port = 53 ".to_string(),
+            missing_rows: vec!["{
+  \"hostname\": \"ns3\",
+  \"is_primary\": false,
+  \"port\": 5353.0
+}"
+            .to_string()],
+        },
+        &source,
+    );
+}
+
+#[test]
+fn test_sql_proof_all_missing_rows_excepted() {
+    let source = format!(
+        r#"{PROOF_QUANTIFIERS_TEST_SCHEMA}
+PROOF "all on standard port" ALL EXIST OF dns_server EXCEPT (ns3) {{ SELECT rowid FROM dns_server WHERE port = 53 }}
+"#
+    );
+    assert_compiles_with_proof_warnings(&source, vec![]);
+}
+
+#[test]
+fn test_sql_proof_count_exceptions_not_allowed() {
+    let source = format!(
+        r#"{PROOF_QUANTIFIERS_TEST_SCHEMA}
+PROOF "one primary" EXIST EXACTLY 1 OF dns_server EXCEPT (ns3) {{ SELECT rowid FROM dns_server WHERE is_primary }}
+"#
+    );
+    assert_test_validaton_exception(
+        DatabaseValidationError::ProofExceptionsOnlyAllowedForNoneOrAll {
+            table_name: "dns_server".to_string(),
+            comment: "one primary".to_string(),
+        },
+        &source,
+    );
+}
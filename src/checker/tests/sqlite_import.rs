@@ -0,0 +1,419 @@
+#[cfg(test)]
+use super::common::assert_compiles_data_paths;
+#[cfg(test)]
+use super::common::assert_compiles_data_paths_error;
+#[cfg(test)]
+use super::common::assert_compiles_data_paths_error_source_replacements;
+#[cfg(test)]
+use super::common::assert_compiles_data_with_source_replacements;
+#[cfg(test)]
+use super::common::random_test_dir;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use crate::checker::types::DBType;
+#[cfg(test)]
+use serde_json::json;
+
+#[cfg(test)]
+fn write_sqlite_test_files(source: &str, sqlite_init: &str) -> Vec<String> {
+    let tmp_dir = random_test_dir();
+    std::fs::write(tmp_dir.join("root.edl"), source).unwrap();
+    let conn = rusqlite::Connection::open(tmp_dir.join("legacy.db")).unwrap();
+    conn.execute_batch(sqlite_init).unwrap();
+
+    vec![tmp_dir.join("root.edl").to_str().unwrap().to_string()]
+}
+
+#[test]
+fn test_sqlite_import_column_mapping() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE datacenter {
+  dc_name TEXT PRIMARY KEY,
+}
+
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  ram_mb INT,
+  dc REF datacenter,
+  is_vm BOOL DEFAULT false,
+  CHECK { ram_mb >= 1024 }
+}
+
+DATA datacenter {
+  dc1;
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name, ram_mb = memory, dc = "data center")
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT, memory INTEGER, "data center" TEXT, ignored REAL);
+INSERT INTO hosts VALUES ('srv-b', 4096, 'dc1', NULL);
+INSERT INTO hosts VALUES ('srv-a', 2048, 'dc1', 1.5);
+"#,
+    );
+
+    assert_compiles_data_paths(
+        paths.as_slice(),
+        json!({
+            "datacenter": [
+                {"dc_name": "dc1"},
+            ],
+            "server": [
+                {"hostname": "srv-b", "ram_mb": 4096.0, "dc": "dc1", "is_vm": false},
+                {"hostname": "srv-a", "ram_mb": 2048.0, "dc": "dc1", "is_vm": false},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_sqlite_import_same_column_names() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE metric {
+  name TEXT PRIMARY KEY,
+  weight FLOAT,
+}
+
+DATA metric {
+  cpu, 0.5;
+}
+
+IMPORT SQLITE "legacy.db" TABLE metric INTO metric
+"#,
+        r#"
+CREATE TABLE metric(name TEXT, weight REAL);
+INSERT INTO metric VALUES ('mem', 1.25);
+INSERT INTO metric VALUES ('disk', 3);
+"#,
+    );
+
+    assert_compiles_data_paths(
+        paths.as_slice(),
+        json!({
+            "metric": [
+                {"name": "cpu", "weight": 0.5},
+                {"name": "mem", "weight": 1.25},
+                {"name": "disk", "weight": 3.0},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_sqlite_import_bad_value_rowid() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  ram_mb INT,
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name, ram_mb = memory)
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT, memory);
+INSERT INTO hosts(rowid, name, memory) VALUES (7, 'srv-a', 4096);
+INSERT INTO hosts(rowid, name, memory) VALUES (42, 'srv-b', 'lots');
+"#,
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::SqliteImportDataError {
+            sqlite_file: "legacy.db".to_string(),
+            sqlite_table: "hosts".to_string(),
+            rowid: 42,
+            error: Box::new(DatabaseValidationError::DataCannotParseDataColumnValue {
+                table_name: "server".to_string(),
+                row_index: 2,
+                column_index: 2,
+                column_name: "ram_mb".to_string(),
+                column_value: "lots".to_string(),
+                expected_type: DBType::Int,
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_sqlite_import_null_value() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name)
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT);
+INSERT INTO hosts(rowid, name) VALUES (3, NULL);
+"#,
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::SqliteImportUnsupportedValue {
+            sqlite_file: "legacy.db".to_string(),
+            sqlite_table: "hosts".to_string(),
+            rowid: 3,
+            column_name: "name".to_string(),
+            value_type: "Null".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_sqlite_import_non_existing_table() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server
+"#,
+        r#"
+CREATE TABLE other(name TEXT);
+"#,
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::SqliteImportError {
+            sqlite_file: "legacy.db".to_string(),
+            sqlite_table: "hosts".to_string(),
+            error: "table doesn't exist".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_sqlite_import_duplicate_primary_key() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+DATA server {
+  srv-a;
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name)
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT);
+INSERT INTO hosts(rowid, name) VALUES (5, 'srv-a');
+"#,
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::SqliteImportDataError {
+            sqlite_file: "legacy.db".to_string(),
+            sqlite_table: "hosts".to_string(),
+            rowid: 5,
+            error: Box::new(DatabaseValidationError::DuplicatePrimaryKey {
+                table_name: "server".to_string(),
+                value: "srv-a".to_string(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_sqlite_import_non_existing_foreign_key() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE datacenter {
+  dc_name TEXT PRIMARY KEY,
+}
+
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  dc REF datacenter,
+}
+
+DATA datacenter {
+  dc1;
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name, dc = dc)
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT, dc TEXT);
+INSERT INTO hosts(rowid, name, dc) VALUES (3, 'srv-a', 'dc1');
+INSERT INTO hosts(rowid, name, dc) VALUES (8, 'srv-b', 'dc2');
+"#,
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::SqliteImportDataError {
+            sqlite_file: "legacy.db".to_string(),
+            sqlite_table: "hosts".to_string(),
+            rowid: 8,
+            error: Box::new(DatabaseValidationError::NonExistingForeignKey {
+                table_with_foreign_key: "server".to_string(),
+                foreign_key_column: "dc".to_string(),
+                referred_table: "datacenter".to_string(),
+                referred_table_column: "dc_name".to_string(),
+                key_value: "dc2".to_string(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_sqlite_import_lua_check_rowid() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  ram_mb INT,
+  CHECK { ram_mb >= 1024 }
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name, ram_mb = memory)
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT, memory INTEGER);
+INSERT INTO hosts(rowid, name, memory) VALUES (1, 'srv-a', 4096);
+INSERT INTO hosts(rowid, name, memory) VALUES (9, 'srv-b', 512);
+"#,
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::SqliteImportDataError {
+            sqlite_file: "legacy.db".to_string(),
+            sqlite_table: "hosts".to_string(),
+            rowid: 9,
+            error: Box::new(DatabaseValidationError::LuaCheckEvaluationFailed {
+                table_name: "server".to_string(),
+                expression: " ram_mb >= 1024 ".to_string(),
+                column_names: vec!["hostname".to_string(), "ram_mb".to_string()],
+                row_values: vec!["srv-b".to_string(), "512".to_string()],
+                error: "Expression check for the row didn't pass.".to_string(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_sqlite_import_replacements_not_supported() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  ram_mb INT,
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name, ram_mb = memory)
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT, memory INTEGER);
+INSERT INTO hosts VALUES ('srv-a', 2048);
+"#,
+    );
+
+    let replacements = r#"
+{
+  "server": [
+    {
+      "primary_key": "srv-a",
+      "replacements": {
+        "ram_mb": "4096"
+      }
+    }
+  ]
+}
+"#;
+
+    assert_eq!(
+        assert_compiles_data_paths_error_source_replacements(paths.as_slice(), replacements),
+        DatabaseValidationError::ReplacementOverImportedValuesIsNotSupported {
+            table: "server".to_string(),
+            replacement_primary_key: "srv-a".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_sqlite_import_empty_table() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+DATA server {
+  srv-a;
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name)
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT);
+"#,
+    );
+
+    assert_compiles_data_paths(
+        paths.as_slice(),
+        json!({
+            "server": [
+                {"hostname": "srv-a"},
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_sqlite_import_replacements_in_data_of_same_table() {
+    let paths = write_sqlite_test_files(
+        r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  ram_mb INT,
+}
+
+DATA server(hostname, ram_mb) {
+  srv-z, 1024;
+}
+
+IMPORT SQLITE "legacy.db" TABLE hosts INTO server (hostname = name, ram_mb = memory)
+"#,
+        r#"
+CREATE TABLE hosts(name TEXT, memory INTEGER);
+INSERT INTO hosts VALUES ('srv-a', 2048);
+"#,
+    );
+
+    let replacements = r#"
+{
+  "server": [
+    {
+      "primary_key": "srv-z",
+      "replacements": {
+        "ram_mb": "4096"
+      }
+    }
+  ]
+}
+"#;
+
+    assert_compiles_data_with_source_replacements(
+        paths.as_slice(),
+        replacements,
+        &json!({
+            "server": [
+                {"hostname": "srv-z", "ram_mb": 4096.0},
+                {"hostname": "srv-a", "ram_mb": 2048.0},
+            ],
+        }),
+    );
+}
@@ -0,0 +1,214 @@
+#[cfg(test)]
+use super::common::assert_compiles_data_paths;
+#[cfg(test)]
+use super::common::assert_compiles_data_paths_error;
+#[cfg(test)]
+use super::common::random_test_dir;
+#[cfg(test)]
+use crate::checker::errors::DatabaseValidationError;
+#[cfg(test)]
+use serde_json::json;
+
+#[cfg(test)]
+const STRUCTURED_IMPORT_TEST_SCHEMA: &str = r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  ram_mb INT DEFAULT 1024,
+}
+
+TABLE disk {
+  dev_slot TEXT PRIMARY KEY CHILD OF server,
+  size_gb FLOAT,
+}
+
+TABLE partition {
+  part_no INT PRIMARY KEY CHILD OF disk,
+}
+"#;
+
+#[cfg(test)]
+fn write_structured_test_files(file_name: &str, statement: &str, contents: &str) -> Vec<String> {
+    let tmp_dir = random_test_dir();
+    std::fs::write(
+        tmp_dir.join("root.edl"),
+        format!("{STRUCTURED_IMPORT_TEST_SCHEMA}\n{statement}\n"),
+    )
+    .unwrap();
+    std::fs::write(tmp_dir.join(file_name), contents).unwrap();
+
+    vec![tmp_dir.join("root.edl").to_str().unwrap().to_string()]
+}
+
+#[cfg(test)]
+fn expected_structured_import_output() -> serde_json::Value {
+    json!({
+        "server": [
+            {"hostname": "epyc1", "ram_mb": 4096.0},
+            {"hostname": "epyc2", "ram_mb": 1024.0},
+        ],
+        "disk": [
+            {"hostname": "epyc1", "dev_slot": "/dev/sda", "size_gb": 512.0},
+            {"hostname": "epyc1", "dev_slot": "/dev/sdb", "size_gb": 1.5},
+            {"hostname": "epyc2", "dev_slot": "/dev/sda", "size_gb": 256.0},
+        ],
+        "partition": [
+            {"hostname": "epyc1", "dev_slot": "/dev/sda", "part_no": 1.0},
+            {"hostname": "epyc1", "dev_slot": "/dev/sda", "part_no": 2.0},
+        ],
+    })
+}
+
+#[test]
+fn test_include_json_nested_children() {
+    let paths = write_structured_test_files(
+        "servers.json",
+        r#"INCLUDE JSON "servers.json""#,
+        r#"
+{
+  "server": [
+    {
+      "hostname": "epyc1",
+      "ram_mb": 4096,
+      "disk": [
+        { "dev_slot": "/dev/sda", "size_gb": 512, "partition": [{ "part_no": 1 }, { "part_no": 2 }] },
+        { "dev_slot": "/dev/sdb", "size_gb": 1.5 }
+      ]
+    },
+    {
+      "hostname": "epyc2",
+      "disk": { "dev_slot": "/dev/sda", "size_gb": 256 }
+    }
+  ]
+}
+"#,
+    );
+
+    assert_compiles_data_paths(paths.as_slice(), expected_structured_import_output());
+}
+
+#[test]
+fn test_include_yaml_nested_children() {
+    let paths = write_structured_test_files(
+        "servers.yaml",
+        r#"INCLUDE YAML "servers.yaml""#,
+        r#"
+server:
+  - hostname: epyc1
+    ram_mb: 4096
+    disk:
+      - dev_slot: /dev/sda
+        size_gb: 512
+        partition:
+          - part_no: 1
+          - part_no: 2
+      - dev_slot: /dev/sdb
+        size_gb: 1.5
+  - hostname: epyc2
+    disk:
+      dev_slot: /dev/sda
+      size_gb: 256
+"#,
+    );
+
+    assert_compiles_data_paths(paths.as_slice(), expected_structured_import_output());
+}
+
+#[test]
+fn test_include_toml_nested_children() {
+    let paths = write_structured_test_files(
+        "servers.toml",
+        r#"INCLUDE TOML "servers.toml""#,
+        r#"
+[[server]]
+hostname = "epyc1"
+ram_mb = 4096
+
+[[server.disk]]
+dev_slot = "/dev/sda"
+size_gb = 512
+partition = [{ part_no = 1 }, { part_no = 2 }]
+
+[[server.disk]]
+dev_slot = "/dev/sdb"
+size_gb = 1.5
+
+[[server]]
+hostname = "epyc2"
+disk = { dev_slot = "/dev/sda", size_gb = 256 }
+"#,
+    );
+
+    assert_compiles_data_paths(paths.as_slice(), expected_structured_import_output());
+}
+
+#[test]
+fn test_include_json_explicit_parent_key() {
+    let paths = write_structured_test_files(
+        "disks.json",
+        r#"
+DATA server(hostname, ram_mb) {
+  epyc1, 1024;
+  epyc2, 2048;
+}
+
+INCLUDE JSON "disks.json"
+"#,
+        r#"
+{
+  "disk": [
+    { "hostname": "epyc1", "dev_slot": "/dev/sda", "size_gb": 512 },
+    { "hostname": "epyc2", "dev_slot": "/dev/sda", "size_gb": 256 }
+  ]
+}
+"#,
+    );
+
+    assert_compiles_data_paths(
+        paths.as_slice(),
+        json!({
+            "server": [
+                {"hostname": "epyc1", "ram_mb": 1024.0},
+                {"hostname": "epyc2", "ram_mb": 2048.0},
+            ],
+            "disk": [
+                {"hostname": "epyc1", "dev_slot": "/dev/sda", "size_gb": 512.0},
+                {"hostname": "epyc2", "dev_slot": "/dev/sda", "size_gb": 256.0},
+            ],
+            "partition": [],
+        }),
+    );
+}
+
+#[test]
+fn test_include_json_null_value() {
+    let paths = write_structured_test_files(
+        "servers.json",
+        r#"INCLUDE JSON "servers.json""#,
+        r#"{ "server": [{ "hostname": "epyc1", "disk": [{ "dev_slot": null }] }] }"#,
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::StructuredDataFileError {
+            path: "servers.json".to_string(),
+            error: "server[0].disk[0].dev_slot: null values are not supported".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_include_json_root_not_object() {
+    let paths = write_structured_test_files(
+        "servers.json",
+        r#"INCLUDE JSON "servers.json""#,
+        r#"[{ "hostname": "epyc1" }]"#,
+    );
+
+    assert_eq!(
+        assert_compiles_data_paths_error(paths.as_slice()),
+        DatabaseValidationError::StructuredDataFileError {
+            path: "servers.json".to_string(),
+            error: "document root must be an object of tables".to_string(),
+        }
+    );
+}
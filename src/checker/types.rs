@@ -4,7 +4,7 @@ use std::{collections::HashMap, str::FromStr};
 
 use regex::Regex;
 
-use crate::db_parser::TableRowCheck;
+use crate::db_parser::{TableRowCheck, ValidExpressions};
 
 use super::errors::DatabaseValidationError;
 
@@ -64,7 +64,39 @@ pub struct DataTable {
     pub uniq_constraints: Vec<UniqConstraint>,
     pub row_checks: Vec<TableRowCheck>,
     pub mat_view_expression: Option<String>,
+    pub mat_view_language: ValidExpressions,
     pub exclusive_lock: bool,
+    pub row_constants: bool,
+    /// row index to the file row was imported from
+    pub imported_rows: HashMap<usize, ImportedRowSource>,
+}
+
+/// Rows that don't come from edl sources, their
+/// validation errors point to the imported file
+#[derive(Clone)]
+pub enum ImportedRowSource {
+    Csv { csv_file: String, line: usize },
+    Sqlite { sqlite_file: String, sqlite_table: String, rowid: i64 },
+}
+
+impl ImportedRowSource {
+    pub fn wrap_error(&self, e: DatabaseValidationError) -> DatabaseValidationError {
+        match self {
+            ImportedRowSource::Csv { csv_file, line } => DatabaseValidationError::CsvDataError {
+                csv_file: csv_file.clone(),
+                line: *line,
+                error: Box::new(e),
+            },
+            ImportedRowSource::Sqlite { sqlite_file, sqlite_table, rowid } => {
+                DatabaseValidationError::SqliteImportDataError {
+                    sqlite_file: sqlite_file.clone(),
+                    sqlite_table: sqlite_table.clone(),
+                    rowid: *rowid,
+                    error: Box::new(e),
+                }
+            }
+        }
+    }
 }
 
 pub enum ConsistentStringDataframeValidationError {
@@ -177,6 +209,14 @@ impl DataTable {
         self.columns[0].data.len()
     }
 
+    /// Wraps error of the row with its import source if row was imported
+    pub fn row_error(&self, row_idx: usize, e: DatabaseValidationError) -> DatabaseValidationError {
+        match self.imported_rows.get(&row_idx) {
+            Some(source) => source.wrap_error(e),
+            None => e,
+        }
+    }
+
     pub fn find_column_named_idx(&self, dbi: &DBIdentifier) -> Vec<usize> {
         let mut res = Vec::with_capacity(1);
         for (idx, i) in self.columns.iter().enumerate() {
@@ -453,6 +493,52 @@ impl DataTable {
         Some(serde_json::to_string_pretty(&row_value).unwrap())
     }
 
+    /// Primary keys from the root parent joined with `=>`
+    pub fn row_primary_key(&self, row_idx: usize) -> String {
+        self.primary_keys_with_parents()
+            .iter()
+            .map(|k| match &self.columns[*k].data {
+                ColumnVector::Strings(v) => v.v[row_idx].clone(),
+                ColumnVector::Ints(v) => v.v[row_idx].to_string(),
+                ColumnVector::Floats(v) => v.v[row_idx].to_string(),
+                ColumnVector::Bools(v) => v.v[row_idx].to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("=>")
+    }
+
+    /// Snake case row constant names for tables marked with CONSTANTS,
+    /// one per row, formed from table name and all primary keys from the root parent
+    pub fn row_constant_names(&self) -> Vec<String> {
+        if !self.row_constants {
+            return Vec::new();
+        }
+
+        let keys = self.primary_keys_with_parents();
+        (0..self.len())
+            .map(|row_idx| {
+                let mut res = self.name.as_str().to_string();
+                for k in &keys {
+                    let value = match &self.columns[*k].data {
+                        ColumnVector::Strings(v) => v.v[row_idx].clone(),
+                        ColumnVector::Ints(v) => v.v[row_idx].to_string(),
+                        ColumnVector::Floats(v) => v.v[row_idx].to_string(),
+                        ColumnVector::Bools(v) => v.v[row_idx].to_string(),
+                    };
+                    res.push('_');
+                    res.extend(value.chars().map(|c| {
+                        if c.is_ascii_alphanumeric() {
+                            c.to_ascii_lowercase()
+                        } else {
+                            '_'
+                        }
+                    }));
+                }
+                res
+            })
+            .collect()
+    }
+
     pub fn parent_table(&self) -> Option<DBIdentifier> {
         self.columns
             .iter()
@@ -754,4 +840,26 @@ impl<'a> SerializationVector<'a> {
             SerializationVector::FkeysOneToMany { sv, .. } => sv.table_name,
         }
     }
+
+    pub fn column_name(&self) -> &str {
+        match self {
+            SerializationVector::Strings(v) => &v.column_name,
+            SerializationVector::Ints(v) => &v.column_name,
+            SerializationVector::Floats(v) => &v.column_name,
+            SerializationVector::Bools(v) => &v.column_name,
+            SerializationVector::Fkeys { sv, .. } => &sv.column_name,
+            SerializationVector::FkeysOneToMany { sv, .. } => &sv.column_name,
+        }
+    }
+
+    pub fn last_for_table(&self) -> bool {
+        match self {
+            SerializationVector::Strings(v) => v.last_for_table,
+            SerializationVector::Ints(v) => v.last_for_table,
+            SerializationVector::Floats(v) => v.last_for_table,
+            SerializationVector::Bools(v) => v.last_for_table,
+            SerializationVector::Fkeys { sv, .. } => sv.last_for_table,
+            SerializationVector::FkeysOneToMany { sv, .. } => sv.last_for_table,
+        }
+    }
 }
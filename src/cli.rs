@@ -1,24 +1,71 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+
+use crate::codegen::json::JsonRelations;
 
 #[derive(Parser)] // requires `derive` feature
 #[clap(author, version, about, long_about = None)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
     /// Rust output directory
     #[clap(long)]
     pub rust_output_directory: Option<String>,
 
+    /// Emit aligned uncompressed Rust data which is read without copying
+    #[clap(long)]
+    pub rust_zero_copy_data: bool,
+
+    /// Emit Rust module tree with mod.rs and a file per table,
+    /// other .rs files in Rust output directory are removed
+    #[clap(long)]
+    pub rust_split_table_modules: bool,
+
     /// OCaml output directory
     #[clap(long)]
     pub ocaml_output_directory: Option<String>,
 
+    /// C++17 header output directory
+    #[clap(long)]
+    pub cpp_output_directory: Option<String>,
+
+    /// Go package output directory
+    #[clap(long)]
+    pub go_output_directory: Option<String>,
+
+    /// Haskell output directory
+    #[clap(long)]
+    pub haskell_output_directory: Option<String>,
+
+    /// Protobuf schema and binary data output directory
+    #[clap(long)]
+    pub protobuf_output_directory: Option<String>,
+
     /// Sqlite dump output file
     #[clap(long)]
     pub sqlite_output_file: Option<String>,
 
+    /// Json dump of all tables output file
+    #[clap(long)]
+    pub json_output_file: Option<String>,
+
+    /// Json schema of json dump output file
+    #[clap(long)]
+    pub json_schema_output_file: Option<String>,
+
+    /// Include parent, children and referrers relations in json dump
+    #[clap(long, value_enum, default_value_t = JsonRelations::None)]
+    pub json_relations: JsonRelations,
+
     /// Replacements json file to replace data in sources
     #[clap(long)]
     pub replacements_file: Option<String>,
 
+    /// Fail compilation if any proof marked with WARN finds offenders
+    #[clap(long)]
+    pub deny_warnings: bool,
+
     /// Input sources to compile and check
     #[clap(required = true, min_values(1))]
     pub inputs: Vec<String>,
@@ -29,6 +76,19 @@ pub struct Cli {
     pub dump_source_file: Option<String>,
 }
 
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generate edl table definitions and data from sqlite database
+    ImportSqlite {
+        /// Sqlite database file
+        database: String,
+
+        /// Output edl file
+        #[clap(long)]
+        out: String,
+    },
+}
+
 pub fn get_args() -> Cli {
     Cli::parse()
 }
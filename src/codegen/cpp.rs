@@ -0,0 +1,1228 @@
+use convert_case::{Case, Casing};
+
+use crate::checker::{logic::AllData, types::SerializationVector};
+
+use super::{CodeGenerator, CodegenOutputFile};
+
+/// C++17 header only target, data is loaded from
+/// zero copy format file, see `codegen::dump_as_bytes_zero_copy`
+pub struct CppCodegen {
+    pub debug_dump_function: bool,
+    pub edb_data_file_name: String,
+    pub header_file_name: String,
+}
+
+impl Default for CppCodegen {
+    fn default() -> Self {
+        Self {
+            debug_dump_function: false,
+            edb_data_file_name: "edb_data.bin".to_string(),
+            header_file_name: "database.hpp".to_string(),
+        }
+    }
+}
+
+impl CodeGenerator for CppCodegen {
+    fn generate(&self, data: &crate::checker::logic::AllData) -> super::CodegenOutputs {
+        let mut content = String::with_capacity(1024);
+        let comp = CppCodegenCompute::new(data, self);
+
+        content += "// EdenDB generated database\n";
+        content += "#pragma once\n";
+        content += "\n";
+        content += "#include <cstdint>\n";
+        content += "#include <cstring>\n";
+        content += "#include <fstream>\n";
+        content += "#include <functional>\n";
+        if self.debug_dump_function {
+            content += "#include <iostream>\n";
+        }
+        content += "#include <iterator>\n";
+        content += "#include <stdexcept>\n";
+        content += "#include <string>\n";
+        content += "#include <string_view>\n";
+        content += "#include <vector>\n";
+        content += "\n";
+        content += "namespace edendb {\n";
+        content += "\n";
+
+        content += "// Data reading library\n";
+        content += cpp_mini_deserialization_library();
+        content += "\n";
+
+        if self.debug_dump_function {
+            content += "// Value dumping library\n";
+            content += cpp_value_dump_library();
+            content += "\n";
+        }
+
+        content += "// Table row pointer types\n";
+        for trow_pointer in &comp.table_pointer_types {
+            content += trow_pointer;
+            content += "\n";
+        }
+
+        if !comp.table_row_constants.is_empty() {
+            content += "// Table row constants\n";
+            for trow_constant in &comp.table_row_constants {
+                content += trow_constant;
+                content += "\n";
+            }
+            content += "\n";
+        }
+
+        content += "// Table struct types\n";
+        for tstruct in &comp.table_structs {
+            content += tstruct;
+            content += "\n";
+        }
+
+        content += "// Table definitions\n";
+        for tdef in &comp.table_definitions {
+            content += tdef;
+            content += "\n";
+        }
+
+        content += "// Database definition\n";
+        content += &comp.database_definition;
+        content += "\n";
+
+        content += "} // namespace edendb\n";
+        content += "\n";
+
+        content += "// Table row pointer hashes\n";
+        content += "namespace std {\n";
+        for thash in &comp.table_pointer_hashes {
+            content += thash;
+        }
+        content += "} // namespace std\n";
+
+        let header_src = CodegenOutputFile {
+            filename: self.header_file_name.clone(),
+            content: content.into_bytes(),
+        };
+
+        let data_src = CodegenOutputFile {
+            filename: self.edb_data_file_name.clone(),
+            content: comp.data_bytes.clone(),
+        };
+
+        super::CodegenOutputs {
+            uncompressed_edb_data: comp.data_bytes,
+            files: vec![header_src, data_src],
+        }
+    }
+}
+
+struct CppCodegenCompute {
+    table_pointer_types: Vec<String>,
+    table_pointer_hashes: Vec<String>,
+    table_row_constants: Vec<String>,
+    table_structs: Vec<String>,
+    table_definitions: Vec<String>,
+    database_definition: String,
+    data_bytes: Vec<u8>,
+}
+
+impl CppCodegenCompute {
+    fn new(data: &AllData, opt: &CppCodegen) -> CppCodegenCompute {
+        let vecs = data.serialization_vectors();
+        let table_pointer_types = table_pointer_types(data);
+        let table_pointer_hashes = table_pointer_hashes(data);
+        let table_row_constants = table_row_constants(data);
+        let table_structs = table_structs(data, &vecs);
+        let table_definitions = table_definitions(data, &vecs);
+        let database_definition = database_definition(data, &vecs, opt.debug_dump_function);
+        let data_bytes = super::dump_as_bytes_zero_copy(&vecs);
+        CppCodegenCompute {
+            table_pointer_types,
+            table_pointer_hashes,
+            table_row_constants,
+            table_structs,
+            table_definitions,
+            database_definition,
+            data_bytes,
+        }
+    }
+}
+
+fn cpp_mini_deserialization_library() -> &'static str {
+    r#"template <typename T>
+class Span {
+public:
+    Span() : data_(nullptr), size_(0) {}
+    Span(const T* data, size_t size) : data_(data), size_(size) {}
+
+    const T* begin() const { return data_; }
+    const T* end() const { return data_ + size_; }
+    size_t size() const { return size_; }
+    bool empty() const { return size_ == 0; }
+    const T& operator[](size_t idx) const { return data_[idx]; }
+
+private:
+    const T* data_;
+    size_t size_;
+};
+
+template <typename Ptr>
+class PointerRange {
+public:
+    class iterator {
+    public:
+        using iterator_category = std::forward_iterator_tag;
+        using value_type = Ptr;
+        using difference_type = std::ptrdiff_t;
+        using pointer = const Ptr*;
+        using reference = Ptr;
+
+        explicit iterator(size_t idx) : idx_(idx) {}
+        Ptr operator*() const { return Ptr(idx_); }
+        iterator& operator++() { ++idx_; return *this; }
+        bool operator==(const iterator& other) const { return idx_ == other.idx_; }
+        bool operator!=(const iterator& other) const { return idx_ != other.idx_; }
+
+    private:
+        size_t idx_;
+    };
+
+    explicit PointerRange(size_t len) : len_(len) {}
+    iterator begin() const { return iterator(0); }
+    iterator end() const { return iterator(len_); }
+    size_t size() const { return len_; }
+
+private:
+    size_t len_;
+};
+
+class StrColumn {
+public:
+    size_t size() const { return offsets_.empty() ? 0 : offsets_.size() - 1; }
+    std::string_view get(size_t idx) const {
+        return std::string_view(bytes_).substr(offsets_[idx], offsets_[idx + 1] - offsets_[idx]);
+    }
+
+private:
+    friend class Reader;
+    std::vector<uint64_t> offsets_;
+    std::string bytes_;
+};
+
+template <typename T>
+class NestedColumn {
+public:
+    size_t size() const { return offsets_.empty() ? 0 : offsets_.size() - 1; }
+    Span<T> get(size_t idx) const {
+        return Span<T>(values_.data() + offsets_[idx], offsets_[idx + 1] - offsets_[idx]);
+    }
+
+private:
+    friend class Reader;
+    std::vector<uint64_t> offsets_;
+    std::vector<T> values_;
+};
+
+// XXH3 64 bit hash with default secret and zero seed, used to verify the data checksum
+inline constexpr uint8_t XXH3_SECRET[192] = {
+    0xb8, 0xfe, 0x6c, 0x39, 0x23, 0xa4, 0x4b, 0xbe, 0x7c, 0x01, 0x81, 0x2c, 0xf7, 0x21, 0xad, 0x1c,
+    0xde, 0xd4, 0x6d, 0xe9, 0x83, 0x90, 0x97, 0xdb, 0x72, 0x40, 0xa4, 0xa4, 0xb7, 0xb3, 0x67, 0x1f,
+    0xcb, 0x79, 0xe6, 0x4e, 0xcc, 0xc0, 0xe5, 0x78, 0x82, 0x5a, 0xd0, 0x7d, 0xcc, 0xff, 0x72, 0x21,
+    0xb8, 0x08, 0x46, 0x74, 0xf7, 0x43, 0x24, 0x8e, 0xe0, 0x35, 0x90, 0xe6, 0x81, 0x3a, 0x26, 0x4c,
+    0x3c, 0x28, 0x52, 0xbb, 0x91, 0xc3, 0x00, 0xcb, 0x88, 0xd0, 0x65, 0x8b, 0x1b, 0x53, 0x2e, 0xa3,
+    0x71, 0x64, 0x48, 0x97, 0xa2, 0x0d, 0xf9, 0x4e, 0x38, 0x19, 0xef, 0x46, 0xa9, 0xde, 0xac, 0xd8,
+    0xa8, 0xfa, 0x76, 0x3f, 0xe3, 0x9c, 0x34, 0x3f, 0xf9, 0xdc, 0xbb, 0xc7, 0xc7, 0x0b, 0x4f, 0x1d,
+    0x8a, 0x51, 0xe0, 0x4b, 0xcd, 0xb4, 0x59, 0x31, 0xc8, 0x9f, 0x7e, 0xc9, 0xd9, 0x78, 0x73, 0x64,
+    0xea, 0xc5, 0xac, 0x83, 0x34, 0xd3, 0xeb, 0xc3, 0xc5, 0x81, 0xa0, 0xff, 0xfa, 0x13, 0x63, 0xeb,
+    0x17, 0x0d, 0xdd, 0x51, 0xb7, 0xf0, 0xda, 0x49, 0xd3, 0x16, 0x55, 0x26, 0x29, 0xd4, 0x68, 0x9e,
+    0x2b, 0x16, 0xbe, 0x58, 0x7d, 0x47, 0xa1, 0xfc, 0x8f, 0xf8, 0xb8, 0xd1, 0x7a, 0xd0, 0x31, 0xce,
+    0x45, 0xcb, 0x3a, 0x8f, 0x95, 0x16, 0x04, 0x28, 0xaf, 0xd7, 0xfb, 0xca, 0xbb, 0x4b, 0x40, 0x7e,
+};
+
+inline constexpr uint64_t XXH_PRIME32_1 = 0x9E3779B1ULL;
+inline constexpr uint64_t XXH_PRIME32_2 = 0x85EBCA77ULL;
+inline constexpr uint64_t XXH_PRIME32_3 = 0xC2B2AE3DULL;
+inline constexpr uint64_t XXH_PRIME64_1 = 0x9E3779B185EBCA87ULL;
+inline constexpr uint64_t XXH_PRIME64_2 = 0xC2B2AE3D27D4EB4FULL;
+inline constexpr uint64_t XXH_PRIME64_3 = 0x165667B19E3779F9ULL;
+inline constexpr uint64_t XXH_PRIME64_4 = 0x85EBCA77C2B2AE63ULL;
+inline constexpr uint64_t XXH_PRIME64_5 = 0x27D4EB2F165667C5ULL;
+
+inline uint64_t xxh_read64(const uint8_t* p) {
+    uint64_t res = 0;
+    for (size_t i = 0; i < 8; ++i) {
+        res |= static_cast<uint64_t>(p[i]) << (8 * i);
+    }
+    return res;
+}
+
+inline uint64_t xxh_read32(const uint8_t* p) {
+    uint64_t res = 0;
+    for (size_t i = 0; i < 4; ++i) {
+        res |= static_cast<uint64_t>(p[i]) << (8 * i);
+    }
+    return res;
+}
+
+inline uint64_t xxh_rotl64(uint64_t v, int r) { return (v << r) | (v >> (64 - r)); }
+
+inline uint64_t xxh_mul128_fold64(uint64_t lhs, uint64_t rhs) {
+    uint64_t lo_lo = (lhs & 0xFFFFFFFFULL) * (rhs & 0xFFFFFFFFULL);
+    uint64_t hi_lo = (lhs >> 32) * (rhs & 0xFFFFFFFFULL);
+    uint64_t lo_hi = (lhs & 0xFFFFFFFFULL) * (rhs >> 32);
+    uint64_t hi_hi = (lhs >> 32) * (rhs >> 32);
+    uint64_t cross = (lo_lo >> 32) + (hi_lo & 0xFFFFFFFFULL) + lo_hi;
+    uint64_t upper = (hi_lo >> 32) + (cross >> 32) + hi_hi;
+    uint64_t lower = (cross << 32) | (lo_lo & 0xFFFFFFFFULL);
+    return lower ^ upper;
+}
+
+inline uint64_t xxh64_avalanche(uint64_t h) {
+    h ^= h >> 33;
+    h *= XXH_PRIME64_2;
+    h ^= h >> 29;
+    h *= XXH_PRIME64_3;
+    h ^= h >> 32;
+    return h;
+}
+
+inline uint64_t xxh3_avalanche(uint64_t h) {
+    h ^= h >> 37;
+    h *= 0x165667919E3779F9ULL;
+    h ^= h >> 32;
+    return h;
+}
+
+inline uint64_t xxh3_rrmxmx(uint64_t h, uint64_t len) {
+    h ^= xxh_rotl64(h, 49) ^ xxh_rotl64(h, 24);
+    h *= 0x9FB21C651E98DF25ULL;
+    h ^= (h >> 35) + len;
+    h *= 0x9FB21C651E98DF25ULL;
+    h ^= h >> 28;
+    return h;
+}
+
+inline uint64_t xxh3_mix16(const uint8_t* input, const uint8_t* secret) {
+    return xxh_mul128_fold64(
+        xxh_read64(input) ^ xxh_read64(secret),
+        xxh_read64(input + 8) ^ xxh_read64(secret + 8));
+}
+
+inline void xxh3_accumulate_512(uint64_t* acc, const uint8_t* input, const uint8_t* secret) {
+    for (size_t i = 0; i < 8; ++i) {
+        uint64_t data_val = xxh_read64(input + 8 * i);
+        uint64_t data_key = data_val ^ xxh_read64(secret + 8 * i);
+        acc[i ^ 1] += data_val;
+        acc[i] += (data_key & 0xFFFFFFFFULL) * (data_key >> 32);
+    }
+}
+
+inline void xxh3_scramble(uint64_t* acc, const uint8_t* secret) {
+    for (size_t i = 0; i < 8; ++i) {
+        uint64_t v = acc[i];
+        v ^= v >> 47;
+        v ^= xxh_read64(secret + 8 * i);
+        acc[i] = v * XXH_PRIME32_1;
+    }
+}
+
+inline uint64_t xxh3_64(const uint8_t* input, size_t len) {
+    const uint8_t* secret = XXH3_SECRET;
+    const uint64_t len64 = static_cast<uint64_t>(len);
+    if (len == 0) {
+        return xxh64_avalanche(xxh_read64(secret + 56) ^ xxh_read64(secret + 64));
+    }
+    if (len <= 3) {
+        uint64_t combined = (static_cast<uint64_t>(input[0]) << 16)
+            | (static_cast<uint64_t>(input[len >> 1]) << 24)
+            | static_cast<uint64_t>(input[len - 1])
+            | (len64 << 8);
+        return xxh64_avalanche(combined ^ (xxh_read32(secret) ^ xxh_read32(secret + 4)));
+    }
+    if (len <= 8) {
+        uint64_t input64 = xxh_read32(input + len - 4) + (xxh_read32(input) << 32);
+        uint64_t flip = xxh_read64(secret + 8) ^ xxh_read64(secret + 16);
+        return xxh3_rrmxmx(input64 ^ flip, len64);
+    }
+    if (len <= 16) {
+        uint64_t lo = xxh_read64(input) ^ (xxh_read64(secret + 24) ^ xxh_read64(secret + 32));
+        uint64_t hi = xxh_read64(input + len - 8)
+            ^ (xxh_read64(secret + 40) ^ xxh_read64(secret + 48));
+        uint64_t swapped = 0;
+        for (size_t i = 0; i < 8; ++i) {
+            swapped |= ((lo >> (8 * i)) & 0xFF) << (8 * (7 - i));
+        }
+        return xxh3_avalanche(len64 + swapped + hi + xxh_mul128_fold64(lo, hi));
+    }
+    uint64_t acc = len64 * XXH_PRIME64_1;
+    if (len <= 128) {
+        if (len > 32) {
+            if (len > 64) {
+                if (len > 96) {
+                    acc += xxh3_mix16(input + 48, secret + 96);
+                    acc += xxh3_mix16(input + len - 64, secret + 112);
+                }
+                acc += xxh3_mix16(input + 32, secret + 64);
+                acc += xxh3_mix16(input + len - 48, secret + 80);
+            }
+            acc += xxh3_mix16(input + 16, secret + 32);
+            acc += xxh3_mix16(input + len - 32, secret + 48);
+        }
+        acc += xxh3_mix16(input, secret);
+        acc += xxh3_mix16(input + len - 16, secret + 16);
+        return xxh3_avalanche(acc);
+    }
+    if (len <= 240) {
+        for (size_t i = 0; i < 8; ++i) {
+            acc += xxh3_mix16(input + 16 * i, secret + 16 * i);
+        }
+        acc = xxh3_avalanche(acc);
+        for (size_t i = 8; i < len / 16; ++i) {
+            acc += xxh3_mix16(input + 16 * i, secret + 16 * (i - 8) + 3);
+        }
+        acc += xxh3_mix16(input + len - 16, secret + 136 - 17);
+        return xxh3_avalanche(acc);
+    }
+
+    uint64_t accs[8] = {
+        XXH_PRIME32_3, XXH_PRIME64_1, XXH_PRIME64_2, XXH_PRIME64_3,
+        XXH_PRIME64_4, XXH_PRIME32_2, XXH_PRIME64_5, XXH_PRIME32_1,
+    };
+    const size_t stripes_per_block = (sizeof(XXH3_SECRET) - 64) / 8;
+    const size_t block_len = 64 * stripes_per_block;
+    const size_t blocks = (len - 1) / block_len;
+    for (size_t b = 0; b < blocks; ++b) {
+        for (size_t s = 0; s < stripes_per_block; ++s) {
+            xxh3_accumulate_512(accs, input + b * block_len + s * 64, secret + s * 8);
+        }
+        xxh3_scramble(accs, secret + sizeof(XXH3_SECRET) - 64);
+    }
+    const size_t last_stripes = ((len - 1) - block_len * blocks) / 64;
+    for (size_t s = 0; s < last_stripes; ++s) {
+        xxh3_accumulate_512(accs, input + blocks * block_len + s * 64, secret + s * 8);
+    }
+    xxh3_accumulate_512(accs, input + len - 64, secret + sizeof(XXH3_SECRET) - 64 - 7);
+
+    uint64_t result = len64 * XXH_PRIME64_1;
+    for (size_t i = 0; i < 4; ++i) {
+        result += xxh_mul128_fold64(
+            accs[2 * i] ^ xxh_read64(secret + 11 + 16 * i),
+            accs[2 * i + 1] ^ xxh_read64(secret + 11 + 16 * i + 8));
+    }
+    return xxh3_avalanche(result);
+}
+
+template <typename Ptrs>
+void check_pointer_range(const Ptrs& ptrs, size_t len) {
+    for (const auto& ptr : ptrs) {
+        if (ptr.idx() >= len) {
+            throw std::runtime_error("EdenDB data is corrupted, pointer out of range");
+        }
+    }
+}
+
+template <typename Ptr>
+void check_pointer_range(const NestedColumn<Ptr>& column, size_t len) {
+    for (size_t i = 0; i < column.size(); ++i) {
+        check_pointer_range(column.get(i), len);
+    }
+}
+
+// Reads little endian zero copy format, every section is padded to 8 bytes
+class Reader {
+public:
+    Reader(const uint8_t* data, size_t size) : data_(data), size_(size), pos_(0) {}
+
+    size_t position() const { return pos_; }
+
+    uint64_t u64() {
+        need(8);
+        uint64_t res = 0;
+        for (size_t i = 0; i < 8; ++i) {
+            res |= static_cast<uint64_t>(data_[pos_ + i]) << (8 * i);
+        }
+        pos_ += 8;
+        return res;
+    }
+
+    size_t count() {
+        uint64_t res = u64();
+        if (res > size_) {
+            throw std::runtime_error("EdenDB data is corrupted, invalid count");
+        }
+        return static_cast<size_t>(res);
+    }
+
+    std::vector<int64_t> ints() {
+        size_t n = count();
+        std::vector<int64_t> res;
+        res.reserve(n);
+        for (size_t i = 0; i < n; ++i) {
+            res.push_back(static_cast<int64_t>(u64()));
+        }
+        return res;
+    }
+
+    std::vector<double> floats() {
+        size_t n = count();
+        std::vector<double> res;
+        res.reserve(n);
+        for (size_t i = 0; i < n; ++i) {
+            uint64_t bits = u64();
+            double value;
+            std::memcpy(&value, &bits, sizeof(value));
+            res.push_back(value);
+        }
+        return res;
+    }
+
+    std::vector<uint8_t> bools() {
+        size_t n = count();
+        need(n);
+        std::vector<uint8_t> res(data_ + pos_, data_ + pos_ + n);
+        for (uint8_t b : res) {
+            if (b > 1) {
+                throw std::runtime_error("EdenDB data is corrupted, invalid bool value");
+            }
+        }
+        pos_ += n;
+        pad();
+        return res;
+    }
+
+    StrColumn strings() {
+        size_t n = count();
+        StrColumn res;
+        res.offsets_ = offsets(n);
+        size_t total = static_cast<size_t>(res.offsets_[n]);
+        need(total);
+        res.bytes_.assign(reinterpret_cast<const char*>(data_ + pos_), total);
+        pos_ += total;
+        pad();
+        return res;
+    }
+
+    template <typename Ptr>
+    std::vector<Ptr> pointers() {
+        size_t n = count();
+        std::vector<Ptr> res;
+        res.reserve(n);
+        for (size_t i = 0; i < n; ++i) {
+            res.push_back(Ptr(static_cast<size_t>(u64())));
+        }
+        return res;
+    }
+
+    template <typename Ptr>
+    NestedColumn<Ptr> nested() {
+        size_t n = count();
+        NestedColumn<Ptr> res;
+        res.offsets_ = offsets(n);
+        size_t total = static_cast<size_t>(res.offsets_[n]);
+        if (total > size_) {
+            throw std::runtime_error("EdenDB data is corrupted, invalid count");
+        }
+        res.values_.reserve(total);
+        for (size_t i = 0; i < total; ++i) {
+            res.values_.push_back(Ptr(static_cast<size_t>(u64())));
+        }
+        return res;
+    }
+
+private:
+    void need(size_t n) {
+        if (pos_ > size_ || n > size_ - pos_) {
+            throw std::runtime_error("EdenDB data is truncated");
+        }
+    }
+
+    void pad() {
+        pos_ = (pos_ + 7) & ~static_cast<size_t>(7);
+    }
+
+    std::vector<uint64_t> offsets(size_t n) {
+        std::vector<uint64_t> res;
+        res.reserve(n + 1);
+        for (size_t i = 0; i < n + 1; ++i) {
+            res.push_back(u64());
+        }
+        if (res[0] != 0) {
+            throw std::runtime_error("EdenDB data is corrupted, invalid offsets");
+        }
+        for (size_t i = 1; i < res.size(); ++i) {
+            if (res[i - 1] > res[i]) {
+                throw std::runtime_error("EdenDB data is corrupted, invalid offsets");
+            }
+        }
+        return res;
+    }
+
+    const uint8_t* data_;
+    size_t size_;
+    size_t pos_;
+};
+"#
+}
+
+fn cpp_value_dump_library() -> &'static str {
+    r#"inline void dump_value(std::ostream& out, std::string_view v) { out << '"' << v << '"'; }
+inline void dump_value(std::ostream& out, int64_t v) { out << v; }
+inline void dump_value(std::ostream& out, double v) { out << v; }
+inline void dump_value(std::ostream& out, bool v) { out << (v ? "true" : "false"); }
+
+template <typename Ptr>
+void dump_value(std::ostream& out, const Ptr& v) { out << v.idx(); }
+
+template <typename Ptr>
+void dump_value(std::ostream& out, const Span<Ptr>& v) {
+    out << '[';
+    for (size_t i = 0; i < v.size(); ++i) {
+        if (i > 0) {
+            out << ", ";
+        }
+        out << v[i].idx();
+    }
+    out << ']';
+}
+"#
+}
+
+struct CppColumn {
+    name: String,
+    value_type: String,
+    storage_type: String,
+    read_expr: String,
+    get_expr: String,
+}
+
+fn cpp_column(sv: &SerializationVector) -> CppColumn {
+    let name = sv.column_name().to_string();
+    let (value_type, storage_type, read_expr, get_expr) = match sv {
+        SerializationVector::Strings(_) => (
+            "std::string_view".to_string(),
+            "StrColumn".to_string(),
+            "reader.strings()".to_string(),
+            format!("c_{}_.get(ptr.idx())", name),
+        ),
+        SerializationVector::Ints(_) => (
+            "int64_t".to_string(),
+            "std::vector<int64_t>".to_string(),
+            "reader.ints()".to_string(),
+            format!("c_{}_[ptr.idx()]", name),
+        ),
+        SerializationVector::Floats(_) => (
+            "double".to_string(),
+            "std::vector<double>".to_string(),
+            "reader.floats()".to_string(),
+            format!("c_{}_[ptr.idx()]", name),
+        ),
+        SerializationVector::Bools(_) => (
+            "bool".to_string(),
+            "std::vector<uint8_t>".to_string(),
+            "reader.bools()".to_string(),
+            format!("c_{}_[ptr.idx()] != 0", name),
+        ),
+        SerializationVector::Fkeys { foreign_table, .. } => {
+            let ptr = format!("TableRowPointer{}", foreign_table.to_case(Case::Pascal));
+            (
+                ptr.clone(),
+                format!("std::vector<{}>", ptr),
+                format!("reader.pointers<{}>()", ptr),
+                format!("c_{}_[ptr.idx()]", name),
+            )
+        }
+        SerializationVector::FkeysOneToMany { foreign_table, .. } => {
+            let ptr = format!("TableRowPointer{}", foreign_table.to_case(Case::Pascal));
+            (
+                format!("Span<{}>", ptr),
+                format!("NestedColumn<{}>", ptr),
+                format!("reader.nested<{}>()", ptr),
+                format!("c_{}_.get(ptr.idx())", name),
+            )
+        }
+    };
+
+    CppColumn {
+        name,
+        value_type,
+        storage_type,
+        read_expr,
+        get_expr,
+    }
+}
+
+fn table_pointer_types(data: &AllData) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let ptr = format!("TableRowPointer{}", t.name.as_str().to_case(Case::Pascal));
+        let mut output = String::new();
+        output += &format!("class {} {{\n", ptr);
+        output += "public:\n";
+        output += &format!("    explicit {}(size_t idx) : idx_(idx) {{}}\n", ptr);
+        output += "    size_t idx() const { return idx_; }\n";
+        output += &format!("    bool operator==(const {}& other) const {{ return idx_ == other.idx_; }}\n", ptr);
+        output += &format!("    bool operator!=(const {}& other) const {{ return idx_ != other.idx_; }}\n", ptr);
+        output += &format!("    bool operator<(const {}& other) const {{ return idx_ < other.idx_; }}\n", ptr);
+        output += "\n";
+        output += "private:\n";
+        output += "    size_t idx_;\n";
+        output += "};\n";
+        res.push(output);
+    }
+    res
+}
+
+fn table_pointer_hashes(data: &AllData) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let ptr = format!("TableRowPointer{}", t.name.as_str().to_case(Case::Pascal));
+        let mut output = String::new();
+        output += "template <>\n";
+        output += &format!("struct hash<edendb::{}> {{\n", ptr);
+        output += &format!(
+            "    size_t operator()(const edendb::{}& ptr) const {{ return std::hash<size_t>()(ptr.idx()); }}\n",
+            ptr
+        );
+        output += "};\n";
+        res.push(output);
+    }
+    res
+}
+
+fn table_row_constants(data: &AllData) -> Vec<String> {
+    let mut res = Vec::new();
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        for (idx, constant) in t.row_constant_names().iter().enumerate() {
+            res.push(format!(
+                "inline const TableRowPointer{} {}({});",
+                tname_pasc_case,
+                constant.to_uppercase(),
+                idx
+            ));
+        }
+    }
+    res
+}
+
+fn table_structs(data: &AllData, vecs: &[SerializationVector]) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        let mut output = String::new();
+        output += &format!("struct TableRow{} {{\n", tname_pasc_case);
+        for sv in vecs {
+            if sv.table_name() == t.name.as_str() {
+                let col = cpp_column(sv);
+                output += &format!("    {} {};\n", col.value_type, col.name);
+            }
+        }
+        output += "};\n";
+        res.push(output);
+    }
+    res
+}
+
+fn table_definitions(data: &AllData, vecs: &[SerializationVector]) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        let trow_ptr = format!("TableRowPointer{}", tname_pasc_case);
+        let trow_def = format!("TableRow{}", tname_pasc_case);
+        let columns: Vec<_> = vecs
+            .iter()
+            .filter(|sv| sv.table_name() == t.name.as_str())
+            .map(cpp_column)
+            .collect();
+
+        let mut output = String::new();
+        output += &format!("class TableDefinition{} {{\n", tname_pasc_case);
+        output += "public:\n";
+        output += "    size_t len() const { return len_; }\n";
+        output += "\n";
+        output += &format!(
+            "    PointerRange<{}> rows_iter() const {{ return PointerRange<{}>(len_); }}\n",
+            trow_ptr, trow_ptr
+        );
+        output += "\n";
+
+        output += &format!("    {} row({} ptr) const {{\n", trow_def, trow_ptr);
+        output += &format!("        return {} {{\n", trow_def);
+        for col in &columns {
+            output += &format!("            c_{}(ptr),\n", col.name);
+        }
+        output += "        };\n";
+        output += "    }\n";
+        output += "\n";
+
+        for col in &columns {
+            output += &format!(
+                "    {} c_{}({} ptr) const {{ return {}; }}\n",
+                col.value_type, col.name, trow_ptr, col.get_expr
+            );
+        }
+        output += "\n";
+
+        output += "private:\n";
+        output += "    friend class Database;\n";
+        output += "    size_t len_ = 0;\n";
+        for col in &columns {
+            output += &format!("    {} c_{}_;\n", col.storage_type, col.name);
+        }
+        output += "};\n";
+        res.push(output);
+    }
+    res
+}
+
+fn database_definition(data: &AllData, vecs: &[SerializationVector], debug_dump: bool) -> String {
+    let mut output = String::new();
+
+    output += "class Database {\n";
+    output += "public:\n";
+    for t in data.tables_sorted() {
+        output += &format!(
+            "    const TableDefinition{}& {}() const {{ return {}_; }}\n",
+            t.name.as_str().to_case(Case::Pascal),
+            t.name.as_str(),
+            t.name.as_str()
+        );
+    }
+    output += "\n";
+
+    output += "    static Database from_file(const std::string& path) {\n";
+    output += "        std::ifstream file(path, std::ios::binary);\n";
+    output += "        if (!file) {\n";
+    output += "            throw std::runtime_error(\"Cannot open EdenDB data file \" + path);\n";
+    output += "        }\n";
+    output += "        std::vector<uint8_t> bytes((std::istreambuf_iterator<char>(file)), std::istreambuf_iterator<char>());\n";
+    output += "        return from_bytes(bytes.data(), bytes.size());\n";
+    output += "    }\n";
+    output += "\n";
+
+    output += "    static Database from_bytes(const uint8_t* data, size_t size) {\n";
+    output += "        const size_t hash_size = 8;\n";
+    output += "        if (size < 16 + hash_size) {\n";
+    output += "            throw std::runtime_error(\"EdenDB data is truncated\");\n";
+    output += "        }\n";
+    output += "        if (xxh_read64(data + size - hash_size) != xxh3_64(data, size - hash_size)) {\n";
+    output += "            throw std::runtime_error(\"EdenDB data is corrupted, checksum mismatch\");\n";
+    output += "        }\n";
+    output += "        if (std::memcmp(data, \"EDBZCOPY\", 8) != 0) {\n";
+    output += "            throw std::runtime_error(\"EdenDB data is not in zero copy format\");\n";
+    output += "        }\n";
+    output += "        Reader reader(data, size - hash_size);\n";
+    output += "        // magic\n";
+    output += "        reader.u64();\n";
+    output += &format!("        if (reader.u64() != {}) {{\n", vecs.len());
+    output += "            throw std::runtime_error(\"EdenDB data has unexpected column count\");\n";
+    output += "        }\n";
+    output += "\n";
+    output += "        Database db;\n";
+    for sv in vecs {
+        let col = cpp_column(sv);
+        output += &format!("        db.{}_.c_{}_ = {};\n", sv.table_name(), col.name, col.read_expr);
+    }
+    output += "\n";
+
+    for t in data.tables_sorted() {
+        let columns: Vec<_> = vecs
+            .iter()
+            .filter(|sv| sv.table_name() == t.name.as_str())
+            .collect();
+        let tname = t.name.as_str();
+        output += &format!(
+            "        db.{}_.len_ = db.{}_.c_{}_.size();\n",
+            tname,
+            tname,
+            columns[0].column_name()
+        );
+        for sv in &columns[1..] {
+            output += &format!(
+                "        if (db.{}_.c_{}_.size() != db.{}_.len_) {{\n",
+                tname,
+                sv.column_name(),
+                tname
+            );
+            output += "            throw std::runtime_error(\"EdenDB data is corrupted, column length mismatch\");\n";
+            output += "        }\n";
+        }
+    }
+    output += "\n";
+    for sv in vecs {
+        match sv {
+            SerializationVector::Fkeys { foreign_table, .. }
+            | SerializationVector::FkeysOneToMany { foreign_table, .. } => {
+                output += &format!(
+                    "        check_pointer_range(db.{}_.c_{}_, db.{}_.len_);\n",
+                    sv.table_name(),
+                    sv.column_name(),
+                    foreign_table
+                );
+            }
+            _ => {}
+        }
+    }
+    output += "\n";
+    output += "        if (reader.position() != size - hash_size) {\n";
+    output += "            throw std::runtime_error(\"EdenDB data has unexpected trailing bytes\");\n";
+    output += "        }\n";
+    output += "\n";
+    output += "        return db;\n";
+    output += "    }\n";
+
+    if debug_dump {
+        output += "\n";
+        output += "    void debug_dump_stdout() const {\n";
+        for t in data.tables_sorted() {
+            let tname = t.name.as_str();
+            output += &format!(
+                "        std::cout << \"TABLE: {} <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<\" << std::endl;\n",
+                tname
+            );
+            output += &format!("        for (auto ptr : {}_.rows_iter()) {{\n", tname);
+            output += &format!("            auto row = {}_.row(ptr);\n", tname);
+            output += &format!(
+                "            std::cout << \"TableRow{} {{ \";\n",
+                tname.to_case(Case::Pascal)
+            );
+            let mut first = true;
+            for sv in vecs {
+                if sv.table_name() == tname {
+                    let prefix = if first { "" } else { ", " };
+                    first = false;
+                    output += &format!("            std::cout << \"{}{}: \";\n", prefix, sv.column_name());
+                    output += &format!("            dump_value(std::cout, row.{});\n", sv.column_name());
+                }
+            }
+            output += "            std::cout << \" }\" << std::endl;\n";
+            output += "        }\n";
+        }
+        output += "    }\n";
+    }
+
+    output += "\n";
+    output += "private:\n";
+    for t in data.tables_sorted() {
+        output += &format!(
+            "    TableDefinition{} {}_;\n",
+            t.name.as_str().to_case(Case::Pascal),
+            t.name.as_str()
+        );
+    }
+    output += "};\n";
+
+    output
+}
+
+#[cfg(test)]
+fn compile_and_run_cpp(
+    tmp_dir: &std::path::Path,
+    main_cpp_contents: &str,
+    args: &[&str],
+) -> String {
+    use std::process::{Command, Stdio};
+
+    std::fs::write(tmp_dir.join("main.cpp"), main_cpp_contents).unwrap();
+
+    let output = Command::new("g++")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(tmp_dir)
+        .args(["-std=c++17", "-Wall", "-Wextra", "-Werror", "-o", "main", "main.cpp"])
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new("./main")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(tmp_dir)
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[cfg(test)]
+fn assert_cpp_db_compiled_dump_equals(source: &str, output_dump: &str) {
+    use crate::db_parser::{self, InputSource};
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(source.to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+
+    let gen = CppCodegen {
+        debug_dump_function: true,
+        ..Default::default()
+    };
+    let codegen_outputs = gen.generate(&data);
+    codegen_outputs.dump_to_dir(tmp_dir.to_str().unwrap());
+
+    let main_cpp_contents = r#"
+#include "database.hpp"
+
+int main() {
+    edendb::Database db = edendb::Database::from_file("edb_data.bin");
+    db.debug_dump_stdout();
+    return 0;
+}
+"#;
+
+    let out_res = compile_and_run_cpp(&tmp_dir, main_cpp_contents, &[]);
+    pretty_assertions::assert_eq!(out_res, output_dump);
+}
+
+#[test]
+#[ignore]
+fn test_cpp_codegen_integration() {
+    let source = r#"
+TABLE thic_boi {
+  id INT,
+  name TEXT,
+  b BOOL,
+  f FLOAT,
+  fk REF some_enum,
+}
+
+TABLE some_enum CONSTANTS {
+  name TEXT PRIMARY KEY,
+}
+
+TABLE enum_child_a {
+  inner_name_a TEXT PRIMARY KEY CHILD OF some_enum,
+}
+
+TABLE enum_child_b {
+  inner_name_b TEXT PRIMARY KEY CHILD OF some_enum,
+}
+
+DATA thic_boi {
+  1, hey ho, true, 1.23, warm;
+  2, here she goes, false, 3.21, hot;
+  3, either blah, true, 5.43, hot;
+}
+
+DATA enum_child_a(name, inner_name_a) {
+  warm, barely warm;
+  warm, medium warm;
+}
+
+DATA enum_child_b(name, inner_name_b) {
+  warm, barely degrees;
+  warm, medium degrees;
+}
+
+DATA EXCLUSIVE some_enum {
+  warm;
+  hot;
+}
+"#;
+    let output_dump = r#"TABLE: enum_child_a <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowEnumChildA { inner_name_a: "barely warm", parent: 0 }
+TableRowEnumChildA { inner_name_a: "medium warm", parent: 0 }
+TABLE: enum_child_b <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowEnumChildB { inner_name_b: "barely degrees", parent: 0 }
+TableRowEnumChildB { inner_name_b: "medium degrees", parent: 0 }
+TABLE: some_enum <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowSomeEnum { name: "warm", children_enum_child_a: [0, 1], children_enum_child_b: [0, 1], referrers_thic_boi__fk: [0] }
+TableRowSomeEnum { name: "hot", children_enum_child_a: [], children_enum_child_b: [], referrers_thic_boi__fk: [1, 2] }
+TABLE: thic_boi <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowThicBoi { id: 1, name: "hey ho", b: true, f: 1.23, fk: 0 }
+TableRowThicBoi { id: 2, name: "here she goes", b: false, f: 3.21, fk: 1 }
+TableRowThicBoi { id: 3, name: "either blah", b: true, f: 5.43, fk: 1 }
+"#;
+
+    assert_cpp_db_compiled_dump_equals(source, output_dump);
+}
+
+#[test]
+fn test_cpp_codegen_files_and_constants() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE some_enum CONSTANTS {
+  name TEXT PRIMARY KEY,
+}
+
+DATA some_enum {
+  warm;
+  hot;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let outputs = CppCodegen::default().generate(&data);
+
+    let filenames: Vec<_> = outputs.files.iter().map(|f| f.filename.as_str()).collect();
+    assert_eq!(filenames, vec!["database.hpp", "edb_data.bin"]);
+
+    let header = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+    assert!(header.contains("inline const TableRowPointerSomeEnum SOME_ENUM_WARM(0);"));
+    assert!(header.contains("inline const TableRowPointerSomeEnum SOME_ENUM_HOT(1);"));
+    assert!(!header.contains("debug_dump_stdout"));
+    assert_eq!(outputs.files[1].content, outputs.uncompressed_edb_data);
+}
+
+#[test]
+#[ignore]
+fn test_cpp_xxh3_matches_rust() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some("TABLE t { id INT }".to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    CppCodegen::default().generate(&data).dump_to_dir(tmp_dir.to_str().unwrap());
+
+    let main_cpp_contents = r#"
+#include <iostream>
+#include "database.hpp"
+
+int main(int argc, char** argv) {
+    for (int i = 1; i < argc; ++i) {
+        size_t len = std::stoul(argv[i]);
+        std::vector<uint8_t> input(len);
+        for (size_t j = 0; j < len; ++j) {
+            input[j] = static_cast<uint8_t>(j * 31 + 7);
+        }
+        std::cout << edendb::xxh3_64(input.data(), input.size()) << std::endl;
+    }
+    return 0;
+}
+"#;
+
+    let lengths = [
+        0, 1, 2, 3, 4, 7, 8, 9, 15, 16, 17, 31, 32, 33, 64, 65, 96, 97, 128, 129, 200, 239,
+        240, 241, 255, 256, 1023, 1024, 1025, 2048, 5000,
+    ];
+    let args: Vec<String> = lengths.iter().map(|l| l.to_string()).collect();
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+
+    let mut expected = String::new();
+    for len in lengths {
+        let input: Vec<u8> = (0..len).map(|j: usize| (j * 31 + 7) as u8).collect();
+        expected += &format!("{}\n", xxhash_rust::xxh3::xxh3_64(&input));
+    }
+
+    let out_res = compile_and_run_cpp(&tmp_dir, main_cpp_contents, &args);
+    pretty_assertions::assert_eq!(out_res, expected);
+}
+
+#[test]
+#[ignore]
+fn test_cpp_codegen_rejects_corrupted_data() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+TABLE disk {
+  id INT PRIMARY KEY,
+  server REF server,
+}
+
+DATA server {
+  alpha;
+}
+
+DATA disk {
+  1, alpha;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    let outputs = CppCodegen::default().generate(&data);
+    outputs.dump_to_dir(tmp_dir.to_str().unwrap());
+
+    let valid = outputs.uncompressed_edb_data.clone();
+    let data_end = valid.len() - 8;
+
+    let mut bad_checksum = valid.clone();
+    bad_checksum[16] ^= 1;
+    std::fs::write(tmp_dir.join("bad_checksum.bin"), &bad_checksum).unwrap();
+
+    let mut truncated = valid[0..data_end].to_vec();
+    let hash = xxhash_rust::xxh3::xxh3_64(&truncated[0..data_end - 8]);
+    truncated[data_end - 8..].copy_from_slice(&hash.to_le_bytes());
+    std::fs::write(tmp_dir.join("truncated.bin"), &truncated).unwrap();
+
+    // last value is the only disk pointer of server.referrers_disk__server
+    let mut bad_pointer = valid[0..data_end].to_vec();
+    assert_eq!(bad_pointer[data_end - 8..], 0u64.to_le_bytes());
+    bad_pointer[data_end - 8..].copy_from_slice(&7u64.to_le_bytes());
+    let hash = xxhash_rust::xxh3::xxh3_64(&bad_pointer);
+    bad_pointer.extend_from_slice(&hash.to_le_bytes());
+    std::fs::write(tmp_dir.join("bad_pointer.bin"), &bad_pointer).unwrap();
+
+    let main_cpp_contents = r#"
+#include <iostream>
+#include "database.hpp"
+
+int main(int argc, char** argv) {
+    for (int i = 1; i < argc; ++i) {
+        try {
+            edendb::Database::from_file(argv[i]);
+            std::cout << "ok" << std::endl;
+        } catch (const std::runtime_error& e) {
+            std::cout << e.what() << std::endl;
+        }
+    }
+    return 0;
+}
+"#;
+
+    let out_res = compile_and_run_cpp(
+        &tmp_dir,
+        main_cpp_contents,
+        &["edb_data.bin", "bad_checksum.bin", "truncated.bin", "bad_pointer.bin"],
+    );
+    pretty_assertions::assert_eq!(
+        out_res,
+        r#"ok
+EdenDB data is corrupted, checksum mismatch
+EdenDB data is truncated
+EdenDB data is corrupted, pointer out of range
+"#
+    );
+}
@@ -0,0 +1,1285 @@
+use std::collections::HashMap;
+
+use convert_case::{Case, Casing};
+
+use crate::checker::{logic::AllData, types::SerializationVector};
+
+use super::{CodeGenerator, CodegenOutputFile};
+
+/// Go package target, data file is embedded with `go:embed`
+/// and is in zero copy format, see `codegen::dump_as_bytes_zero_copy`
+pub struct GoCodegen {
+    pub debug_dump_function: bool,
+    pub package_name: String,
+    pub edb_data_file_name: String,
+    pub source_file_name: String,
+}
+
+impl Default for GoCodegen {
+    fn default() -> Self {
+        Self {
+            debug_dump_function: false,
+            package_name: "database".to_string(),
+            edb_data_file_name: "edb_data.bin".to_string(),
+            source_file_name: "database.go".to_string(),
+        }
+    }
+}
+
+impl CodeGenerator for GoCodegen {
+    fn generate(&self, data: &crate::checker::logic::AllData) -> super::CodegenOutputs {
+        let mut content = String::with_capacity(1024);
+        let comp = GoCodegenCompute::new(data, self);
+
+        content += "// EdenDB generated database\n";
+        content += &format!("package {}\n", self.package_name);
+        content += "\n";
+        content += "import (\n";
+        content += "\t_ \"embed\"\n";
+        content += "\t\"encoding/binary\"\n";
+        content += "\t\"errors\"\n";
+        if self.debug_dump_function {
+            content += "\t\"fmt\"\n";
+        }
+        content += "\t\"math\"\n";
+        content += "\t\"math/bits\"\n";
+        content += ")\n";
+        content += "\n";
+        content += &format!("//go:embed {}\n", self.edb_data_file_name);
+        content += "var edbData []byte\n";
+        content += "\n";
+
+        content += "// Data reading library\n";
+        content += go_mini_deserialization_library();
+        content += "\n";
+
+        if self.debug_dump_function {
+            content += "// Value dumping library\n";
+            content += go_value_dump_library();
+            content += "\n";
+        }
+
+        content += "// Table row pointer types\n";
+        for trow_pointer in &comp.table_pointer_types {
+            content += trow_pointer;
+        }
+        content += "\n";
+
+        if !comp.table_row_constants.is_empty() {
+            content += "// Table row constants\n";
+            content += "const (\n";
+            for trow_constant in &comp.table_row_constants {
+                content += trow_constant;
+            }
+            content += ")\n";
+            content += "\n";
+        }
+
+        content += "// Table struct types\n";
+        for tstruct in &comp.table_structs {
+            content += tstruct;
+            content += "\n";
+        }
+
+        content += "// Table definitions\n";
+        for tdef in &comp.table_definitions {
+            content += tdef;
+            content += "\n";
+        }
+
+        content += "// Database definition\n";
+        content += &comp.database_definition;
+
+        let go_src = CodegenOutputFile {
+            filename: self.source_file_name.clone(),
+            content: content.into_bytes(),
+        };
+
+        let data_src = CodegenOutputFile {
+            filename: self.edb_data_file_name.clone(),
+            content: comp.data_bytes.clone(),
+        };
+
+        super::CodegenOutputs {
+            uncompressed_edb_data: comp.data_bytes,
+            files: vec![go_src, data_src],
+        }
+    }
+}
+
+impl GoCodegen {
+    /// Pascal casing loses underscores so different names can become the same
+    /// Go identifier, like row constants `server_epyc_1` and `server_epyc1`
+    pub fn check_identifiers(&self, data: &AllData) -> Result<(), String> {
+        let vecs = data.serialization_vectors();
+        let mut tables = HashMap::new();
+        let mut constants = HashMap::new();
+        for t in data.tables_sorted() {
+            ensure_unique_go_identifier(&mut tables, t.name.as_str(), "tables")?;
+
+            let mut columns = HashMap::new();
+            for sv in table_columns(t.name.as_str(), &vecs) {
+                ensure_unique_go_identifier(
+                    &mut columns,
+                    sv.column_name(),
+                    &format!("columns of table {}", t.name.as_str()),
+                )?;
+            }
+
+            for constant in t.row_constant_names() {
+                ensure_unique_go_identifier(&mut constants, &constant, "row constants")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn ensure_unique_go_identifier(
+    seen: &mut HashMap<String, String>,
+    name: &str,
+    kind: &str,
+) -> Result<(), String> {
+    let identifier = name.to_case(Case::Pascal);
+    match seen.get(&identifier) {
+        Some(other) if other != name => Err(format!(
+            "{} {} and {} both become Go identifier {}",
+            kind, other, name, identifier
+        )),
+        _ => {
+            seen.insert(identifier, name.to_string());
+            Ok(())
+        }
+    }
+}
+
+struct GoCodegenCompute {
+    table_pointer_types: Vec<String>,
+    table_row_constants: Vec<String>,
+    table_structs: Vec<String>,
+    table_definitions: Vec<String>,
+    database_definition: String,
+    data_bytes: Vec<u8>,
+}
+
+impl GoCodegenCompute {
+    fn new(data: &AllData, opt: &GoCodegen) -> GoCodegenCompute {
+        let vecs = data.serialization_vectors();
+        let table_pointer_types = table_pointer_types(data);
+        let table_row_constants = table_row_constants(data);
+        let table_structs = table_structs(data, &vecs);
+        let table_definitions = table_definitions(data, &vecs);
+        let database_definition = database_definition(data, &vecs, opt.debug_dump_function);
+        let data_bytes = super::dump_as_bytes_zero_copy(&vecs);
+        GoCodegenCompute {
+            table_pointer_types,
+            table_row_constants,
+            table_structs,
+            table_definitions,
+            database_definition,
+            data_bytes,
+        }
+    }
+}
+
+fn go_mini_deserialization_library() -> &'static str {
+    r#"// Reads little endian zero copy format, every section is padded to 8 bytes.
+// First error is remembered and every later read returns zero values.
+type edbReader struct {
+	data []byte
+	pos  uint64
+	err  error
+}
+
+func (r *edbReader) fail(msg string) {
+	if r.err == nil {
+		r.err = errors.New("EdenDB data is corrupted, " + msg)
+	}
+}
+
+func (r *edbReader) need(n uint64) bool {
+	if r.err != nil {
+		return false
+	}
+	size := uint64(len(r.data))
+	if r.pos > size || n > size-r.pos {
+		r.fail("data is truncated")
+		return false
+	}
+	return true
+}
+
+func (r *edbReader) pad() {
+	r.pos = (r.pos + 7) &^ 7
+}
+
+func (r *edbReader) u64() uint64 {
+	if !r.need(8) {
+		return 0
+	}
+	res := binary.LittleEndian.Uint64(r.data[r.pos:])
+	r.pos += 8
+	return res
+}
+
+func (r *edbReader) count() uint64 {
+	res := r.u64()
+	if res > uint64(len(r.data)) {
+		r.fail("invalid count")
+		return 0
+	}
+	return res
+}
+
+func (r *edbReader) offsets(n uint64) []uint64 {
+	res := make([]uint64, n+1)
+	for i := range res {
+		res[i] = r.u64()
+	}
+	if r.err != nil {
+		return nil
+	}
+	if res[0] != 0 {
+		r.fail("invalid offsets")
+		return nil
+	}
+	for i := 1; i < len(res); i++ {
+		if res[i-1] > res[i] {
+			r.fail("invalid offsets")
+			return nil
+		}
+	}
+	return res
+}
+
+func (r *edbReader) ints() []int64 {
+	res := make([]int64, r.count())
+	for i := range res {
+		res[i] = int64(r.u64())
+	}
+	return res
+}
+
+func (r *edbReader) floats() []float64 {
+	res := make([]float64, r.count())
+	for i := range res {
+		res[i] = math.Float64frombits(r.u64())
+	}
+	return res
+}
+
+func (r *edbReader) bools() []bool {
+	n := r.count()
+	if !r.need(n) {
+		return nil
+	}
+	res := make([]bool, n)
+	for i := range res {
+		b := r.data[r.pos+uint64(i)]
+		if b > 1 {
+			r.fail("invalid bool value")
+			return nil
+		}
+		res[i] = b == 1
+	}
+	r.pos += n
+	r.pad()
+	return res
+}
+
+func (r *edbReader) strings() []string {
+	n := r.count()
+	offsets := r.offsets(n)
+	if r.err != nil {
+		return nil
+	}
+	total := offsets[n]
+	if !r.need(total) {
+		return nil
+	}
+	bytes := r.data[r.pos : r.pos+total]
+	res := make([]string, n)
+	for i := range res {
+		res[i] = string(bytes[offsets[i]:offsets[i+1]])
+	}
+	r.pos += total
+	r.pad()
+	return res
+}
+
+func edbPointers[T ~int](r *edbReader) []T {
+	res := make([]T, r.count())
+	for i := range res {
+		res[i] = T(r.u64())
+	}
+	return res
+}
+
+func edbNested[T ~int](r *edbReader) [][]T {
+	n := r.count()
+	offsets := r.offsets(n)
+	if r.err != nil {
+		return nil
+	}
+	total := offsets[n]
+	if total > uint64(len(r.data)) {
+		r.fail("invalid count")
+		return nil
+	}
+	values := make([]T, total)
+	for i := range values {
+		values[i] = T(r.u64())
+	}
+	res := make([][]T, n)
+	for i := range res {
+		res[i] = values[offsets[i]:offsets[i+1]:offsets[i+1]]
+	}
+	return res
+}
+
+// XXH3 64 bit hash with default secret and zero seed, used to verify the data checksum
+var edbXxh3Secret = [192]byte{
+	0xb8, 0xfe, 0x6c, 0x39, 0x23, 0xa4, 0x4b, 0xbe, 0x7c, 0x01, 0x81, 0x2c, 0xf7, 0x21, 0xad, 0x1c,
+	0xde, 0xd4, 0x6d, 0xe9, 0x83, 0x90, 0x97, 0xdb, 0x72, 0x40, 0xa4, 0xa4, 0xb7, 0xb3, 0x67, 0x1f,
+	0xcb, 0x79, 0xe6, 0x4e, 0xcc, 0xc0, 0xe5, 0x78, 0x82, 0x5a, 0xd0, 0x7d, 0xcc, 0xff, 0x72, 0x21,
+	0xb8, 0x08, 0x46, 0x74, 0xf7, 0x43, 0x24, 0x8e, 0xe0, 0x35, 0x90, 0xe6, 0x81, 0x3a, 0x26, 0x4c,
+	0x3c, 0x28, 0x52, 0xbb, 0x91, 0xc3, 0x00, 0xcb, 0x88, 0xd0, 0x65, 0x8b, 0x1b, 0x53, 0x2e, 0xa3,
+	0x71, 0x64, 0x48, 0x97, 0xa2, 0x0d, 0xf9, 0x4e, 0x38, 0x19, 0xef, 0x46, 0xa9, 0xde, 0xac, 0xd8,
+	0xa8, 0xfa, 0x76, 0x3f, 0xe3, 0x9c, 0x34, 0x3f, 0xf9, 0xdc, 0xbb, 0xc7, 0xc7, 0x0b, 0x4f, 0x1d,
+	0x8a, 0x51, 0xe0, 0x4b, 0xcd, 0xb4, 0x59, 0x31, 0xc8, 0x9f, 0x7e, 0xc9, 0xd9, 0x78, 0x73, 0x64,
+	0xea, 0xc5, 0xac, 0x83, 0x34, 0xd3, 0xeb, 0xc3, 0xc5, 0x81, 0xa0, 0xff, 0xfa, 0x13, 0x63, 0xeb,
+	0x17, 0x0d, 0xdd, 0x51, 0xb7, 0xf0, 0xda, 0x49, 0xd3, 0x16, 0x55, 0x26, 0x29, 0xd4, 0x68, 0x9e,
+	0x2b, 0x16, 0xbe, 0x58, 0x7d, 0x47, 0xa1, 0xfc, 0x8f, 0xf8, 0xb8, 0xd1, 0x7a, 0xd0, 0x31, 0xce,
+	0x45, 0xcb, 0x3a, 0x8f, 0x95, 0x16, 0x04, 0x28, 0xaf, 0xd7, 0xfb, 0xca, 0xbb, 0x4b, 0x40, 0x7e,
+}
+
+const (
+	edbPrime32_1 uint64 = 0x9E3779B1
+	edbPrime32_2 uint64 = 0x85EBCA77
+	edbPrime32_3 uint64 = 0xC2B2AE3D
+	edbPrime64_1 uint64 = 0x9E3779B185EBCA87
+	edbPrime64_2 uint64 = 0xC2B2AE3D27D4EB4F
+	edbPrime64_3 uint64 = 0x165667B19E3779F9
+	edbPrime64_4 uint64 = 0x85EBCA77C2B2AE63
+	edbPrime64_5 uint64 = 0x27D4EB2F165667C5
+)
+
+func edbRead64(b []byte) uint64 {
+	return binary.LittleEndian.Uint64(b)
+}
+
+func edbRead32(b []byte) uint64 {
+	return uint64(binary.LittleEndian.Uint32(b))
+}
+
+func edbMul128Fold64(lhs, rhs uint64) uint64 {
+	hi, lo := bits.Mul64(lhs, rhs)
+	return lo ^ hi
+}
+
+func edbXxh64Avalanche(h uint64) uint64 {
+	h ^= h >> 33
+	h *= edbPrime64_2
+	h ^= h >> 29
+	h *= edbPrime64_3
+	h ^= h >> 32
+	return h
+}
+
+func edbXxh3Avalanche(h uint64) uint64 {
+	h ^= h >> 37
+	h *= 0x165667919E3779F9
+	h ^= h >> 32
+	return h
+}
+
+func edbXxh3Rrmxmx(h uint64, length uint64) uint64 {
+	h ^= bits.RotateLeft64(h, 49) ^ bits.RotateLeft64(h, 24)
+	h *= 0x9FB21C651E98DF25
+	h ^= (h >> 35) + length
+	h *= 0x9FB21C651E98DF25
+	h ^= h >> 28
+	return h
+}
+
+func edbXxh3Mix16(input []byte, secret []byte) uint64 {
+	return edbMul128Fold64(
+		edbRead64(input)^edbRead64(secret),
+		edbRead64(input[8:])^edbRead64(secret[8:]))
+}
+
+func edbXxh3Accumulate512(acc *[8]uint64, input []byte, secret []byte) {
+	for i := 0; i < 8; i++ {
+		dataVal := edbRead64(input[8*i:])
+		dataKey := dataVal ^ edbRead64(secret[8*i:])
+		acc[i^1] += dataVal
+		acc[i] += (dataKey & 0xFFFFFFFF) * (dataKey >> 32)
+	}
+}
+
+func edbXxh3Scramble(acc *[8]uint64, secret []byte) {
+	for i := 0; i < 8; i++ {
+		v := acc[i]
+		v ^= v >> 47
+		v ^= edbRead64(secret[8*i:])
+		acc[i] = v * edbPrime32_1
+	}
+}
+
+func edbXxh3(input []byte) uint64 {
+	secret := edbXxh3Secret[:]
+	n := len(input)
+	len64 := uint64(n)
+	if n == 0 {
+		return edbXxh64Avalanche(edbRead64(secret[56:]) ^ edbRead64(secret[64:]))
+	}
+	if n <= 3 {
+		combined := uint64(input[0])<<16 | uint64(input[n>>1])<<24 | uint64(input[n-1]) | len64<<8
+		return edbXxh64Avalanche(combined ^ (edbRead32(secret) ^ edbRead32(secret[4:])))
+	}
+	if n <= 8 {
+		input64 := edbRead32(input[n-4:]) + edbRead32(input)<<32
+		flip := edbRead64(secret[8:]) ^ edbRead64(secret[16:])
+		return edbXxh3Rrmxmx(input64^flip, len64)
+	}
+	if n <= 16 {
+		lo := edbRead64(input) ^ (edbRead64(secret[24:]) ^ edbRead64(secret[32:]))
+		hi := edbRead64(input[n-8:]) ^ (edbRead64(secret[40:]) ^ edbRead64(secret[48:]))
+		return edbXxh3Avalanche(len64 + bits.ReverseBytes64(lo) + hi + edbMul128Fold64(lo, hi))
+	}
+	acc := len64 * edbPrime64_1
+	if n <= 128 {
+		if n > 32 {
+			if n > 64 {
+				if n > 96 {
+					acc += edbXxh3Mix16(input[48:], secret[96:])
+					acc += edbXxh3Mix16(input[n-64:], secret[112:])
+				}
+				acc += edbXxh3Mix16(input[32:], secret[64:])
+				acc += edbXxh3Mix16(input[n-48:], secret[80:])
+			}
+			acc += edbXxh3Mix16(input[16:], secret[32:])
+			acc += edbXxh3Mix16(input[n-32:], secret[48:])
+		}
+		acc += edbXxh3Mix16(input, secret)
+		acc += edbXxh3Mix16(input[n-16:], secret[16:])
+		return edbXxh3Avalanche(acc)
+	}
+	if n <= 240 {
+		for i := 0; i < 8; i++ {
+			acc += edbXxh3Mix16(input[16*i:], secret[16*i:])
+		}
+		acc = edbXxh3Avalanche(acc)
+		for i := 8; i < n/16; i++ {
+			acc += edbXxh3Mix16(input[16*i:], secret[16*(i-8)+3:])
+		}
+		acc += edbXxh3Mix16(input[n-16:], secret[136-17:])
+		return edbXxh3Avalanche(acc)
+	}
+
+	accs := [8]uint64{
+		edbPrime32_3, edbPrime64_1, edbPrime64_2, edbPrime64_3,
+		edbPrime64_4, edbPrime32_2, edbPrime64_5, edbPrime32_1,
+	}
+	stripesPerBlock := (len(secret) - 64) / 8
+	blockLen := 64 * stripesPerBlock
+	blocks := (n - 1) / blockLen
+	for b := 0; b < blocks; b++ {
+		for s := 0; s < stripesPerBlock; s++ {
+			edbXxh3Accumulate512(&accs, input[b*blockLen+s*64:], secret[s*8:])
+		}
+		edbXxh3Scramble(&accs, secret[len(secret)-64:])
+	}
+	lastStripes := ((n - 1) - blockLen*blocks) / 64
+	for s := 0; s < lastStripes; s++ {
+		edbXxh3Accumulate512(&accs, input[blocks*blockLen+s*64:], secret[s*8:])
+	}
+	edbXxh3Accumulate512(&accs, input[n-64:], secret[len(secret)-64-7:])
+
+	result := len64 * edbPrime64_1
+	for i := 0; i < 4; i++ {
+		result += edbMul128Fold64(
+			accs[2*i]^edbRead64(secret[11+16*i:]),
+			accs[2*i+1]^edbRead64(secret[11+16*i+8:]))
+	}
+	return edbXxh3Avalanche(result)
+}
+
+func edbCheckPointers[T ~int](values []T, length int) error {
+	for _, v := range values {
+		if v < 0 || int(v) >= length {
+			return errors.New("EdenDB data is corrupted, pointer out of range")
+		}
+	}
+	return nil
+}
+
+func edbCheckNestedPointers[T ~int](values [][]T, length int) error {
+	for _, v := range values {
+		if err := edbCheckPointers(v, length); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+"#
+}
+
+fn go_value_dump_library() -> &'static str {
+    r#"func edbDumpPointers[T ~int](values []T) string {
+	res := "["
+	for i, v := range values {
+		if i > 0 {
+			res += ", "
+		}
+		res += fmt.Sprint(int(v))
+	}
+	return res + "]"
+}
+"#
+}
+
+struct GoColumn {
+    field_name: String,
+    storage_name: String,
+    value_type: String,
+    read_expr: String,
+    dump_expr: String,
+}
+
+fn go_column(sv: &SerializationVector) -> GoColumn {
+    let field_name = sv.column_name().to_case(Case::Pascal);
+    let storage_name = format!("c{}", field_name);
+    let value = format!("row.{}", field_name);
+    let (value_type, read_expr, dump_expr) = match sv {
+        SerializationVector::Strings(_) => (
+            "string".to_string(),
+            "r.strings()".to_string(),
+            format!("\"\\\"\" + {} + \"\\\"\"", value),
+        ),
+        SerializationVector::Ints(_) => (
+            "int64".to_string(),
+            "r.ints()".to_string(),
+            format!("fmt.Sprint({})", value),
+        ),
+        SerializationVector::Floats(_) => (
+            "float64".to_string(),
+            "r.floats()".to_string(),
+            format!("fmt.Sprint({})", value),
+        ),
+        SerializationVector::Bools(_) => (
+            "bool".to_string(),
+            "r.bools()".to_string(),
+            format!("fmt.Sprint({})", value),
+        ),
+        SerializationVector::Fkeys { foreign_table, .. } => {
+            let ptr = format!("TableRowPointer{}", foreign_table.to_case(Case::Pascal));
+            (
+                ptr.clone(),
+                format!("edbPointers[{}](r)", ptr),
+                format!("fmt.Sprint(int({}))", value),
+            )
+        }
+        SerializationVector::FkeysOneToMany { foreign_table, .. } => {
+            let ptr = format!("TableRowPointer{}", foreign_table.to_case(Case::Pascal));
+            (
+                format!("[]{}", ptr),
+                format!("edbNested[{}](r)", ptr),
+                format!("edbDumpPointers({})", value),
+            )
+        }
+    };
+
+    GoColumn {
+        field_name,
+        storage_name,
+        value_type,
+        read_expr,
+        dump_expr,
+    }
+}
+
+fn database_field(table: &str) -> String {
+    // prefixed so that table names which are go keywords stay valid
+    format!("t{}", table.to_case(Case::Pascal))
+}
+
+fn table_columns<'a, 'b>(t: &str, vecs: &'a [SerializationVector<'b>]) -> Vec<&'a SerializationVector<'b>> {
+    vecs.iter().filter(|sv| sv.table_name() == t).collect()
+}
+
+fn table_pointer_types(data: &AllData) -> Vec<String> {
+    data.tables_sorted()
+        .iter()
+        .map(|t| format!("type TableRowPointer{} int\n", t.name.as_str().to_case(Case::Pascal)))
+        .collect()
+}
+
+fn table_row_constants(data: &AllData) -> Vec<String> {
+    let mut res = Vec::new();
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        for (idx, constant) in t.row_constant_names().iter().enumerate() {
+            res.push(format!(
+                "\t{} TableRowPointer{} = {}\n",
+                constant.to_case(Case::Pascal),
+                tname_pasc_case,
+                idx
+            ));
+        }
+    }
+    res
+}
+
+fn table_structs(data: &AllData, vecs: &[SerializationVector]) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let mut output = String::new();
+        output += &format!("type TableRow{} struct {{\n", t.name.as_str().to_case(Case::Pascal));
+        for sv in table_columns(t.name.as_str(), vecs) {
+            let col = go_column(sv);
+            output += &format!("\t{} {}\n", col.field_name, col.value_type);
+        }
+        output += "}\n";
+        res.push(output);
+    }
+    res
+}
+
+fn table_definitions(data: &AllData, vecs: &[SerializationVector]) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        let tdef = format!("TableDefinition{}", tname_pasc_case);
+        let trow_ptr = format!("TableRowPointer{}", tname_pasc_case);
+        let trow_def = format!("TableRow{}", tname_pasc_case);
+        let columns: Vec<_> = table_columns(t.name.as_str(), vecs)
+            .into_iter()
+            .map(go_column)
+            .collect();
+
+        let mut output = String::new();
+        output += &format!("type {} struct {{\n", tdef);
+        output += "\tlength int\n";
+        for col in &columns {
+            output += &format!("\t{} []{}\n", col.storage_name, col.value_type);
+        }
+        output += "}\n";
+        output += "\n";
+
+        output += &format!("func (t *{}) Len() int {{\n", tdef);
+        output += "\treturn t.length\n";
+        output += "}\n";
+        output += "\n";
+
+        output += &format!("func (t *{}) RowsIter() []{} {{\n", tdef, trow_ptr);
+        output += &format!("\tres := make([]{}, t.length)\n", trow_ptr);
+        output += "\tfor i := range res {\n";
+        output += &format!("\t\tres[i] = {}(i)\n", trow_ptr);
+        output += "\t}\n";
+        output += "\treturn res\n";
+        output += "}\n";
+        output += "\n";
+
+        output += &format!("func (t *{}) Row(ptr {}) {} {{\n", tdef, trow_ptr, trow_def);
+        output += &format!("\treturn {}{{\n", trow_def);
+        for col in &columns {
+            output += &format!("\t\t{}: t.{}[ptr],\n", col.field_name, col.storage_name);
+        }
+        output += "\t}\n";
+        output += "}\n";
+
+        for col in &columns {
+            output += "\n";
+            output += &format!(
+                "func (t *{}) C{}(ptr {}) {} {{\n",
+                tdef, col.field_name, trow_ptr, col.value_type
+            );
+            output += &format!("\treturn t.{}[ptr]\n", col.storage_name);
+            output += "}\n";
+        }
+
+        res.push(output);
+    }
+    res
+}
+
+fn database_definition(data: &AllData, vecs: &[SerializationVector], debug_dump: bool) -> String {
+    let mut output = String::new();
+
+    output += "type Database struct {\n";
+    for t in data.tables_sorted() {
+        output += &format!(
+            "\t{} TableDefinition{}\n",
+            database_field(t.name.as_str()),
+            t.name.as_str().to_case(Case::Pascal)
+        );
+    }
+    output += "}\n";
+
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        output += "\n";
+        output += &format!(
+            "func (db *Database) {}() *TableDefinition{} {{\n",
+            tname_pasc_case, tname_pasc_case
+        );
+        output += &format!("\treturn &db.{}\n", database_field(t.name.as_str()));
+        output += "}\n";
+    }
+    output += "\n";
+
+    output += "// LoadDatabase deserializes the embedded data file\n";
+    output += "func LoadDatabase() (*Database, error) {\n";
+    output += "\treturn DatabaseFromBytes(edbData)\n";
+    output += "}\n";
+    output += "\n";
+
+    output += "// DatabaseFromBytes deserializes zero copy format data\n";
+    output += "func DatabaseFromBytes(data []byte) (*Database, error) {\n";
+    output += "\tconst hashSize = 8\n";
+    output += "\tif len(data) < 16+hashSize {\n";
+    output += "\t\treturn nil, errors.New(\"EdenDB data is truncated\")\n";
+    output += "\t}\n";
+    output += "\tif edbRead64(data[len(data)-hashSize:]) != edbXxh3(data[:len(data)-hashSize]) {\n";
+    output += "\t\treturn nil, errors.New(\"EdenDB data is corrupted, checksum mismatch\")\n";
+    output += "\t}\n";
+    output += "\tif string(data[:8]) != \"EDBZCOPY\" {\n";
+    output += "\t\treturn nil, errors.New(\"EdenDB data is not in zero copy format\")\n";
+    output += "\t}\n";
+    output += "\tr := &edbReader{data: data[:len(data)-hashSize]}\n";
+    output += "\t// magic\n";
+    output += "\tr.u64()\n";
+    output += &format!("\tif r.u64() != {} {{\n", vecs.len());
+    output += "\t\treturn nil, errors.New(\"EdenDB data has unexpected column count\")\n";
+    output += "\t}\n";
+    output += "\n";
+    output += "\tdb := &Database{}\n";
+    for sv in vecs {
+        let col = go_column(sv);
+        output += &format!(
+            "\tdb.{}.{} = {}\n",
+            database_field(sv.table_name()),
+            col.storage_name,
+            col.read_expr
+        );
+    }
+    output += "\tif r.err != nil {\n";
+    output += "\t\treturn nil, r.err\n";
+    output += "\t}\n";
+    output += "\n";
+
+    for t in data.tables_sorted() {
+        let tname = database_field(t.name.as_str());
+        let columns: Vec<_> = table_columns(t.name.as_str(), vecs)
+            .into_iter()
+            .map(go_column)
+            .collect();
+        output += &format!(
+            "\tdb.{}.length = len(db.{}.{})\n",
+            tname, tname, columns[0].storage_name
+        );
+        for col in &columns[1..] {
+            output += &format!(
+                "\tif len(db.{}.{}) != db.{}.length {{\n",
+                tname, col.storage_name, tname
+            );
+            output += "\t\treturn nil, errors.New(\"EdenDB data is corrupted, column length mismatch\")\n";
+            output += "\t}\n";
+        }
+    }
+    output += "\n";
+    for sv in vecs {
+        let (check_fn, foreign_table) = match sv {
+            SerializationVector::Fkeys { foreign_table, .. } => ("edbCheckPointers", foreign_table),
+            SerializationVector::FkeysOneToMany { foreign_table, .. } => {
+                ("edbCheckNestedPointers", foreign_table)
+            }
+            _ => continue,
+        };
+        output += &format!(
+            "\tif err := {}(db.{}.{}, db.{}.length); err != nil {{\n",
+            check_fn,
+            database_field(sv.table_name()),
+            go_column(sv).storage_name,
+            database_field(foreign_table)
+        );
+        output += "\t\treturn nil, err\n";
+        output += "\t}\n";
+    }
+    output += "\n";
+    output += "\tif r.pos != uint64(len(r.data)) {\n";
+    output += "\t\treturn nil, errors.New(\"EdenDB data has unexpected trailing bytes\")\n";
+    output += "\t}\n";
+    output += "\n";
+    output += "\treturn db, nil\n";
+    output += "}\n";
+
+    if debug_dump {
+        output += "\n";
+        output += "func (db *Database) DebugDumpStdout() {\n";
+        for t in data.tables_sorted() {
+            let tname = t.name.as_str();
+            let tfield = database_field(tname);
+            output += &format!(
+                "\tfmt.Println(\"TABLE: {} <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<\")\n",
+                tname
+            );
+            output += &format!("\tfor _, ptr := range db.{}.RowsIter() {{\n", tfield);
+            output += &format!("\t\trow := db.{}.Row(ptr)\n", tfield);
+            output += &format!(
+                "\t\tout := \"TableRow{} {{ \"\n",
+                tname.to_case(Case::Pascal)
+            );
+            for (idx, sv) in table_columns(tname, vecs).iter().enumerate() {
+                let col = go_column(sv);
+                let prefix = if idx == 0 { "" } else { ", " };
+                output += &format!(
+                    "\t\tout += \"{}{}: \" + {}\n",
+                    prefix,
+                    sv.column_name(),
+                    col.dump_expr
+                );
+            }
+            output += "\t\tfmt.Println(out + \" }\")\n";
+            output += "\t}\n";
+        }
+        output += "}\n";
+    }
+
+    output
+}
+
+#[cfg(test)]
+fn go_run(tmp_dir: &std::path::Path, main_go_contents: &str, args: &[&str]) -> String {
+    use std::process::{Command, Stdio};
+
+    std::fs::write(tmp_dir.join("go.mod"), "module edbtest\n\ngo 1.18\n").unwrap();
+    std::fs::write(tmp_dir.join("main.go"), main_go_contents).unwrap();
+
+    let output = Command::new("go")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(tmp_dir)
+        .args(["run", "."])
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[cfg(test)]
+fn assert_go_db_compiled_dump_equals(source: &str, output_dump: &str) {
+    use crate::db_parser::{self, InputSource};
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(source.to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+
+    let gen = GoCodegen {
+        debug_dump_function: true,
+        ..Default::default()
+    };
+    let codegen_outputs = gen.generate(&data);
+    let db_dir = tmp_dir.join("database");
+    std::fs::create_dir_all(&db_dir).unwrap();
+    codegen_outputs.dump_to_dir(db_dir.to_str().unwrap());
+
+    let main_go_contents = r#"package main
+
+import "edbtest/database"
+
+func main() {
+	db, err := database.LoadDatabase()
+	if err != nil {
+		panic(err)
+	}
+	db.DebugDumpStdout()
+}
+"#;
+
+    let out_res = go_run(&tmp_dir, main_go_contents, &[]);
+    pretty_assertions::assert_eq!(out_res, output_dump);
+}
+
+#[test]
+#[ignore]
+fn test_go_codegen_integration() {
+    let source = r#"
+TABLE thic_boi {
+  id INT,
+  name TEXT,
+  b BOOL,
+  f FLOAT,
+  fk REF some_enum,
+}
+
+TABLE some_enum CONSTANTS {
+  name TEXT PRIMARY KEY,
+}
+
+TABLE enum_child_a {
+  inner_name_a TEXT PRIMARY KEY CHILD OF some_enum,
+}
+
+TABLE enum_child_b {
+  inner_name_b TEXT PRIMARY KEY CHILD OF some_enum,
+}
+
+DATA thic_boi {
+  1, hey ho, true, 1.23, warm;
+  2, here she goes, false, 3.21, hot;
+  3, either blah, true, 5.43, hot;
+}
+
+DATA enum_child_a(name, inner_name_a) {
+  warm, barely warm;
+  warm, medium warm;
+}
+
+DATA enum_child_b(name, inner_name_b) {
+  warm, barely degrees;
+  warm, medium degrees;
+}
+
+DATA EXCLUSIVE some_enum {
+  warm;
+  hot;
+}
+"#;
+    let output_dump = r#"TABLE: enum_child_a <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowEnumChildA { inner_name_a: "barely warm", parent: 0 }
+TableRowEnumChildA { inner_name_a: "medium warm", parent: 0 }
+TABLE: enum_child_b <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowEnumChildB { inner_name_b: "barely degrees", parent: 0 }
+TableRowEnumChildB { inner_name_b: "medium degrees", parent: 0 }
+TABLE: some_enum <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowSomeEnum { name: "warm", children_enum_child_a: [0, 1], children_enum_child_b: [0, 1], referrers_thic_boi__fk: [0] }
+TableRowSomeEnum { name: "hot", children_enum_child_a: [], children_enum_child_b: [], referrers_thic_boi__fk: [1, 2] }
+TABLE: thic_boi <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowThicBoi { id: 1, name: "hey ho", b: true, f: 1.23, fk: 0 }
+TableRowThicBoi { id: 2, name: "here she goes", b: false, f: 3.21, fk: 1 }
+TableRowThicBoi { id: 3, name: "either blah", b: true, f: 5.43, fk: 1 }
+"#;
+
+    assert_go_db_compiled_dump_equals(source, output_dump);
+}
+
+#[test]
+fn test_go_codegen_files_and_constants() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE some_enum CONSTANTS {
+  name TEXT PRIMARY KEY,
+}
+
+DATA some_enum {
+  warm;
+  hot;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let outputs = GoCodegen::default().generate(&data);
+
+    let filenames: Vec<_> = outputs.files.iter().map(|f| f.filename.as_str()).collect();
+    assert_eq!(filenames, vec!["database.go", "edb_data.bin"]);
+
+    let src = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+    assert!(src.starts_with("// EdenDB generated database\npackage database\n"));
+    assert!(src.contains("//go:embed edb_data.bin\n"));
+    assert!(src.contains("\tSomeEnumWarm TableRowPointerSomeEnum = 0\n"));
+    assert!(src.contains("\tSomeEnumHot TableRowPointerSomeEnum = 1\n"));
+    assert!(!src.contains("\"fmt\""));
+    assert!(src.contains("!= edbXxh3(data[:len(data)-hashSize]) {\n"));
+    assert_eq!(outputs.files[1].content, outputs.uncompressed_edb_data);
+}
+
+#[test]
+#[ignore]
+fn test_go_xxh3_matches_rust() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some("TABLE t { id INT }".to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    let db_dir = tmp_dir.join("database");
+    std::fs::create_dir_all(&db_dir).unwrap();
+    GoCodegen::default().generate(&data).dump_to_dir(db_dir.to_str().unwrap());
+    // hash function is private to the package
+    std::fs::write(
+        db_dir.join("xxh3_export.go"),
+        "package database\n\nfunc Xxh3(input []byte) uint64 {\n\treturn edbXxh3(input)\n}\n",
+    )
+    .unwrap();
+
+    let main_go_contents = r#"package main
+
+import (
+	"fmt"
+	"os"
+	"strconv"
+
+	"edbtest/database"
+)
+
+func main() {
+	for _, arg := range os.Args[1:] {
+		n, err := strconv.Atoi(arg)
+		if err != nil {
+			panic(err)
+		}
+		input := make([]byte, n)
+		for j := range input {
+			input[j] = byte(j*31 + 7)
+		}
+		fmt.Println(database.Xxh3(input))
+	}
+}
+"#;
+
+    let lengths = [
+        0, 1, 2, 3, 4, 7, 8, 9, 15, 16, 17, 31, 32, 33, 64, 65, 96, 97, 128, 129, 200, 239,
+        240, 241, 255, 256, 1023, 1024, 1025, 2048, 5000,
+    ];
+    let args: Vec<String> = lengths.iter().map(|l| l.to_string()).collect();
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+
+    let mut expected = String::new();
+    for len in lengths {
+        let input: Vec<u8> = (0..len).map(|j: usize| (j * 31 + 7) as u8).collect();
+        expected += &format!("{}\n", xxhash_rust::xxh3::xxh3_64(&input));
+    }
+
+    let out_res = go_run(&tmp_dir, main_go_contents, &args);
+    pretty_assertions::assert_eq!(out_res, expected);
+}
+
+#[test]
+#[ignore]
+fn test_go_codegen_rejects_corrupted_data() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+TABLE disk {
+  id INT PRIMARY KEY,
+  server REF server,
+}
+
+DATA server {
+  alpha;
+}
+
+DATA disk {
+  1, alpha;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    let outputs = GoCodegen::default().generate(&data);
+    let db_dir = tmp_dir.join("database");
+    std::fs::create_dir_all(&db_dir).unwrap();
+    outputs.dump_to_dir(db_dir.to_str().unwrap());
+
+    let valid = outputs.uncompressed_edb_data.clone();
+    let data_end = valid.len() - 8;
+
+    let mut bad_checksum = valid.clone();
+    bad_checksum[16] ^= 1;
+    std::fs::write(tmp_dir.join("bad_checksum.bin"), &bad_checksum).unwrap();
+
+    let mut truncated = valid[0..data_end].to_vec();
+    let hash = xxhash_rust::xxh3::xxh3_64(&truncated[0..data_end - 8]);
+    truncated[data_end - 8..].copy_from_slice(&hash.to_le_bytes());
+    std::fs::write(tmp_dir.join("truncated.bin"), &truncated).unwrap();
+
+    // last value is the only disk pointer of server.referrers_disk__server
+    let mut bad_pointer = valid[0..data_end].to_vec();
+    assert_eq!(bad_pointer[data_end - 8..], 0u64.to_le_bytes());
+    bad_pointer[data_end - 8..].copy_from_slice(&7u64.to_le_bytes());
+    let hash = xxhash_rust::xxh3::xxh3_64(&bad_pointer);
+    bad_pointer.extend_from_slice(&hash.to_le_bytes());
+    std::fs::write(tmp_dir.join("bad_pointer.bin"), &bad_pointer).unwrap();
+
+    let main_go_contents = r#"package main
+
+import (
+	"fmt"
+	"os"
+
+	"edbtest/database"
+)
+
+func main() {
+	for _, path := range os.Args[1:] {
+		data, err := os.ReadFile(path)
+		if err != nil {
+			panic(err)
+		}
+		if _, err := database.DatabaseFromBytes(data); err != nil {
+			fmt.Println(err)
+		} else {
+			fmt.Println("ok")
+		}
+	}
+}
+"#;
+
+    let out_res = go_run(
+        &tmp_dir,
+        main_go_contents,
+        &["database/edb_data.bin", "bad_checksum.bin", "truncated.bin", "bad_pointer.bin"],
+    );
+    pretty_assertions::assert_eq!(
+        out_res,
+        r#"ok
+EdenDB data is corrupted, checksum mismatch
+EdenDB data is corrupted, data is truncated
+EdenDB data is corrupted, pointer out of range
+"#
+    );
+}
+
+#[test]
+fn test_go_codegen_row_constant_identifier_collision() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE server CONSTANTS {
+  hostname TEXT PRIMARY KEY,
+}
+
+DATA server {
+  epyc-1;
+  epyc1;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    assert_eq!(
+        GoCodegen::default().check_identifiers(&data),
+        Err("row constants server_epyc_1 and server_epyc1 both become Go identifier ServerEpyc1"
+            .to_string())
+    );
+}
+
+#[test]
+fn test_go_codegen_column_identifier_collision() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE host {
+  name TEXT PRIMARY KEY,
+}
+
+TABLE a_b {
+  id INT PRIMARY KEY,
+  c_d REF host,
+}
+
+TABLE a_b_c {
+  id INT PRIMARY KEY,
+  d REF host,
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    assert_eq!(
+        GoCodegen::default().check_identifiers(&data),
+        Err("columns of table host referrers_a_b__c_d and referrers_a_b_c__d both become Go \
+             identifier ReferrersABCD"
+            .to_string())
+    );
+}
+
+#[test]
+fn test_go_codegen_identifiers_unique() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE server CONSTANTS {
+  hostname TEXT PRIMARY KEY,
+  disk_size INT,
+}
+
+DATA server {
+  epyc-1, 1;
+  epyc-2, 2;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    assert_eq!(GoCodegen::default().check_identifiers(&data), Ok(()));
+}
@@ -0,0 +1,729 @@
+use convert_case::{Case, Casing};
+
+use crate::checker::{logic::AllData, types::SerializationVector};
+
+use super::{CodeGenerator, CodegenOutputFile};
+
+pub struct HaskellCodegen {
+    pub debug_dump_function: bool,
+    pub edb_data_file_name: String,
+    /// .hs is added to the end of this string
+    pub module_name: String,
+}
+
+impl Default for HaskellCodegen {
+    fn default() -> Self {
+        Self {
+            debug_dump_function: false,
+            edb_data_file_name: "edb_data.bin".to_string(),
+            module_name: "Database".to_string(),
+        }
+    }
+}
+
+impl CodeGenerator for HaskellCodegen {
+    fn generate(&self, data: &crate::checker::logic::AllData) -> super::CodegenOutputs {
+        let mut content = String::with_capacity(1024);
+        let comp = HaskellCodegenCompute::new(data, self);
+
+        content += "{-# LANGUAGE TemplateHaskell #-}\n";
+        content += "-- EdenDB generated database\n";
+        content += &format!("module {}\n", self.module_name);
+        content += &comp.export_list;
+        content += "\n";
+        content += "import Data.Bits (shiftL, (.|.))\n";
+        content += "import qualified Data.ByteString as BS\n";
+        content += "import Data.FileEmbed (embedFile)\n";
+        content += "import Data.Int (Int64)\n";
+        content += "import qualified Data.Text as T\n";
+        content += "import qualified Data.Text.Encoding as TE\n";
+        content += "import qualified Data.Vector as V\n";
+        content += "import Data.Word (Word64)\n";
+        content += "import GHC.Float (castWord64ToDouble)\n";
+        content += "\n";
+
+        content += "-- DB bytes, path is relative to the compilation directory\n";
+        content += "dataBlob :: BS.ByteString\n";
+        content += &format!("dataBlob = $(embedFile \"{}\")\n", self.edb_data_file_name);
+        content += "\n";
+
+        content += haskell_mini_deserialization_library();
+        content += "\n";
+
+        content += "-- Table row pointer types\n";
+        for trow_pointer in &comp.table_pointer_types {
+            content += trow_pointer;
+        }
+        content += "\n";
+
+        if !comp.table_row_constants.is_empty() {
+            content += "-- Table row constants\n";
+            for trow_constant in &comp.table_row_constants {
+                content += trow_constant;
+            }
+            content += "\n";
+        }
+
+        content += "-- Table row types\n";
+        for tstruct in &comp.table_structs {
+            content += tstruct;
+            content += "\n";
+        }
+
+        content += "-- Table definitions\n";
+        for tdef in &comp.table_definitions {
+            content += tdef;
+            content += "\n";
+        }
+
+        content += "-- Database definition\n";
+        content += &comp.database_definition;
+        content += "\n";
+
+        content += "-- Deserialization function\n";
+        content += &comp.deserialization_function;
+        content += "\n";
+
+        content += "-- Database loading\n";
+        content += "db :: Database\n";
+        content += "db = deserialize dataBlob\n";
+
+        if self.debug_dump_function {
+            content += "\n";
+            content += "-- Dump function\n";
+            content += &comp.debug_dump_function;
+        }
+
+        let hs_src = CodegenOutputFile {
+            filename: format!("{}.hs", self.module_name),
+            content: content.into_bytes(),
+        };
+
+        let data_src = CodegenOutputFile {
+            filename: self.edb_data_file_name.clone(),
+            content: comp.uncompressed_data_bytes.clone(),
+        };
+
+        super::CodegenOutputs {
+            uncompressed_edb_data: comp.uncompressed_data_bytes,
+            files: vec![hs_src, data_src],
+        }
+    }
+}
+
+struct HaskellCodegenCompute {
+    export_list: String,
+    table_pointer_types: Vec<String>,
+    table_row_constants: Vec<String>,
+    table_structs: Vec<String>,
+    table_definitions: Vec<String>,
+    database_definition: String,
+    deserialization_function: String,
+    debug_dump_function: String,
+    uncompressed_data_bytes: Vec<u8>,
+}
+
+impl HaskellCodegenCompute {
+    fn new(data: &AllData, opt: &HaskellCodegen) -> HaskellCodegenCompute {
+        let serialization_vectors = data.serialization_vectors();
+        let export_list = export_list(data, opt.debug_dump_function);
+        let table_pointer_types = table_pointer_types(data);
+        let table_row_constants = table_row_constants(data);
+        let table_structs = table_structs(data, &serialization_vectors);
+        let table_definitions = table_definitions(data, &serialization_vectors);
+        let database_definition = database_definition(data);
+        let deserialization_function = deserialization_function(data, &serialization_vectors);
+        let debug_dump_function = debug_dump_function(data);
+        let uncompressed_data_bytes = super::dump_as_bytes(&serialization_vectors);
+        HaskellCodegenCompute {
+            export_list,
+            table_pointer_types,
+            table_row_constants,
+            table_structs,
+            table_definitions,
+            database_definition,
+            deserialization_function,
+            debug_dump_function,
+            uncompressed_data_bytes,
+        }
+    }
+}
+
+fn haskell_mini_deserialization_library() -> &'static str {
+    r#"-- Data reading library
+newtype EdbParser a = EdbParser (BS.ByteString -> Int -> (a, Int))
+
+instance Functor EdbParser where
+  fmap f (EdbParser p) = EdbParser $ \buffer cursor ->
+    let (a, cursor') = p buffer cursor
+    in (f a, cursor')
+
+instance Applicative EdbParser where
+  pure a = EdbParser $ \_ cursor -> (a, cursor)
+  EdbParser pf <*> EdbParser pa = EdbParser $ \buffer cursor ->
+    let (f, cursor') = pf buffer cursor
+        (a, cursor'') = pa buffer cursor'
+    in (f a, cursor'')
+
+instance Monad EdbParser where
+  EdbParser p >>= f = EdbParser $ \buffer cursor ->
+    let (a, cursor') = p buffer cursor
+        EdbParser p' = f a
+    in p' buffer cursor'
+
+runEdbParser :: EdbParser a -> BS.ByteString -> (a, Int)
+runEdbParser (EdbParser p) buffer = p buffer 0
+
+edbAssert :: Bool -> String -> EdbParser ()
+edbAssert ok msg = if ok then pure () else error msg
+
+fetchWord64 :: EdbParser Word64
+fetchWord64 = EdbParser $ \buffer cursor ->
+  if cursor + 8 > BS.length buffer
+    then error "EdenDB data is truncated"
+    else
+      let byte i = fromIntegral (BS.index buffer (cursor + i)) :: Word64
+          value = foldr (\i acc -> (acc `shiftL` 8) .|. byte i) 0 [0 .. 7]
+      in value `seq` (value, cursor + 8)
+
+fetchLength :: EdbParser Int
+fetchLength = fromIntegral <$> fetchWord64
+
+fetchI64 :: EdbParser Int64
+fetchI64 = fromIntegral <$> fetchWord64
+
+fetchF64 :: EdbParser Double
+fetchF64 = castWord64ToDouble <$> fetchWord64
+
+fetchBool :: EdbParser Bool
+fetchBool = EdbParser $ \buffer cursor ->
+  if cursor + 1 > BS.length buffer
+    then error "EdenDB data is truncated"
+    else (BS.index buffer cursor /= 0, cursor + 1)
+
+fetchText :: EdbParser T.Text
+fetchText = do
+  len <- fetchLength
+  EdbParser $ \buffer cursor ->
+    if cursor + len > BS.length buffer
+      then error "EdenDB data is truncated"
+      else (TE.decodeUtf8 (BS.take len (BS.drop cursor buffer)), cursor + len)
+
+fetchPointer :: (Int -> a) -> EdbParser a
+fetchPointer ptr = ptr . fromIntegral <$> fetchWord64
+
+fetchVector :: EdbParser a -> EdbParser (V.Vector a)
+fetchVector p = do
+  len <- fetchLength
+  V.replicateM len p
+"#
+}
+
+fn export_list(data: &AllData, debug_dump: bool) -> String {
+    let mut exports: Vec<String> = Vec::new();
+    for t in data.tables_sorted() {
+        exports.push(format!("TableRowPointer{}(..)", t.name.as_str().to_case(Case::Pascal)));
+    }
+    for t in data.tables_sorted() {
+        for constant in t.row_constant_names() {
+            exports.push(constant);
+        }
+    }
+    for t in data.tables_sorted() {
+        exports.push(format!("TableRow{}(..)", t.name.as_str().to_case(Case::Pascal)));
+    }
+    for t in data.tables_sorted() {
+        exports.push(format!("TableDefinition{}(..)", t.name.as_str().to_case(Case::Pascal)));
+    }
+    exports.push("Database(..)".to_string());
+    exports.push("db".to_string());
+    if debug_dump {
+        exports.push("dumpToStdout".to_string());
+    }
+
+    let mut output = String::new();
+    for (idx, e) in exports.iter().enumerate() {
+        output += if idx == 0 { "  ( " } else { "  , " };
+        output += e;
+        output += "\n";
+    }
+    output += "  ) where\n";
+    output
+}
+
+fn column_type(sv: &SerializationVector) -> String {
+    match sv {
+        SerializationVector::Strings(_) => "T.Text".to_string(),
+        SerializationVector::Ints(_) => "Int64".to_string(),
+        SerializationVector::Floats(_) => "Double".to_string(),
+        SerializationVector::Bools(_) => "Bool".to_string(),
+        SerializationVector::Fkeys { foreign_table, .. } => {
+            format!("TableRowPointer{}", foreign_table.to_case(Case::Pascal))
+        }
+        SerializationVector::FkeysOneToMany { foreign_table, .. } => {
+            format!("(V.Vector TableRowPointer{})", foreign_table.to_case(Case::Pascal))
+        }
+    }
+}
+
+fn column_fetch_expr(sv: &SerializationVector) -> String {
+    match sv {
+        SerializationVector::Strings(_) => "fetchVector fetchText".to_string(),
+        SerializationVector::Ints(_) => "fetchVector fetchI64".to_string(),
+        SerializationVector::Floats(_) => "fetchVector fetchF64".to_string(),
+        SerializationVector::Bools(_) => "fetchVector fetchBool".to_string(),
+        SerializationVector::Fkeys { foreign_table, .. } => format!(
+            "fetchVector (fetchPointer TableRowPointer{})",
+            foreign_table.to_case(Case::Pascal)
+        ),
+        SerializationVector::FkeysOneToMany { foreign_table, .. } => format!(
+            "fetchVector (fetchVector (fetchPointer TableRowPointer{}))",
+            foreign_table.to_case(Case::Pascal)
+        ),
+    }
+}
+
+// Record fields share one namespace in the module, snake case names separated
+// with `'` can't collide like camel case `server` + `disk_size` and `server_disk` + `size`
+fn row_field(sv: &SerializationVector) -> String {
+    format!("row_{}'{}", sv.table_name(), sv.column_name())
+}
+
+fn td_field(table: &str, field: &str) -> String {
+    format!("td_{}'{}", table, field)
+}
+
+fn td_column_field(sv: &SerializationVector) -> String {
+    td_field(sv.table_name(), &format!("c'{}", sv.column_name()))
+}
+
+fn table_pointer_types(data: &AllData) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let ptr = format!("TableRowPointer{}", t.name.as_str().to_case(Case::Pascal));
+        res.push(format!("newtype {} = {} Int deriving (Eq, Ord, Show)\n", ptr, ptr));
+    }
+    res
+}
+
+fn table_row_constants(data: &AllData) -> Vec<String> {
+    let mut res = Vec::new();
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        // snake case names are unique, camel case would merge server_epyc_1 and server_epyc1
+        for (idx, constant) in t.row_constant_names().iter().enumerate() {
+            res.push(format!(
+                "{} :: TableRowPointer{}\n{} = TableRowPointer{} {}\n",
+                constant, tname_pasc_case, constant, tname_pasc_case, idx
+            ));
+        }
+    }
+    res
+}
+
+fn table_structs(data: &AllData, ser_vecs: &[SerializationVector]) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        let mut output = String::new();
+        output += &format!("data TableRow{} = TableRow{}\n", tname_pasc_case, tname_pasc_case);
+
+        let mut first = true;
+        for sv in ser_vecs {
+            if sv.table_name() == t.name.as_str() {
+                output += if first { "  { " } else { "  , " };
+                first = false;
+                output += &format!("{} :: !{}\n", row_field(sv), column_type(sv));
+            }
+        }
+
+        output += "  } deriving (Eq, Show)\n";
+        res.push(output);
+    }
+    res
+}
+
+fn table_definitions(data: &AllData, ser_vecs: &[SerializationVector]) -> Vec<String> {
+    let mut res = Vec::with_capacity(data.tables.len());
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        let mut output = String::new();
+        output += &format!(
+            "data TableDefinition{} = TableDefinition{}\n",
+            tname_pasc_case, tname_pasc_case
+        );
+        output += &format!("  {{ {} :: Int\n", td_field(t.name.as_str(), "length"));
+        output += &format!(
+            "  , {} :: [TableRowPointer{}]\n",
+            td_field(t.name.as_str(), "pointers"),
+            tname_pasc_case
+        );
+        output += &format!(
+            "  , {} :: TableRowPointer{} -> TableRow{}\n",
+            td_field(t.name.as_str(), "row"),
+            tname_pasc_case,
+            tname_pasc_case
+        );
+
+        for sv in ser_vecs {
+            if sv.table_name() == t.name.as_str() {
+                output += &format!(
+                    "  , {} :: TableRowPointer{} -> {}\n",
+                    td_column_field(sv),
+                    tname_pasc_case,
+                    column_type(sv)
+                );
+            }
+        }
+
+        output += "  }\n";
+        res.push(output);
+    }
+    res
+}
+
+fn database_definition(data: &AllData) -> String {
+    let mut res = String::new();
+
+    res += "data Database = Database\n";
+    for (idx, t) in data.tables_sorted().iter().enumerate() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        res += if idx == 0 { "  { " } else { "  , " };
+        res += &format!("db{} :: TableDefinition{}\n", tname_pasc_case, tname_pasc_case);
+    }
+    res += "  }\n";
+
+    res
+}
+
+fn deserialization_function(data: &AllData, vecs: &[SerializationVector]) -> String {
+    let mut output = String::new();
+
+    output += "deserialize :: BS.ByteString -> Database\n";
+    output += "deserialize buffer =\n";
+    output += "  let (database, cursor) = runEdbParser parser buffer\n";
+    output += "  in if cursor /= BS.length buffer\n";
+    output += "       then error \"EdenDB data has unexpected trailing bytes\"\n";
+    output += "       else database\n";
+    output += "  where\n";
+    output += "    parser = do\n";
+
+    // variables are prefixed so that table names which are haskell keywords stay valid
+    let column_var =
+        |sv: &SerializationVector| format!("col_{}'{}", sv.table_name(), sv.column_name());
+
+    for sv in vecs {
+        output += &format!("      {} <- {}\n", column_var(sv), column_fetch_expr(sv));
+
+        if sv.last_for_table() {
+            let tname_pasc_case = sv.table_name().to_case(Case::Pascal);
+            let tlen_var = format!("len{}", tname_pasc_case);
+            output += &format!("      let {} = V.length {}\n", tlen_var, column_var(sv));
+            for other in vecs {
+                if other.table_name() == sv.table_name() && !other.last_for_table() {
+                    output += &format!(
+                        "      edbAssert ({} == V.length {}) \"EdenDB data is corrupted, column length mismatch\"\n",
+                        tlen_var,
+                        column_var(other)
+                    );
+                }
+            }
+        }
+    }
+
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        let columns: Vec<_> = vecs
+            .iter()
+            .filter(|sv| sv.table_name() == t.name.as_str())
+            .collect();
+
+        // generate rows
+        output += &format!(
+            "      let rows{} = V.generate len{} (\\i -> TableRow{}\n",
+            tname_pasc_case, tname_pasc_case, tname_pasc_case
+        );
+        for (idx, sv) in columns.iter().enumerate() {
+            output += if idx == 0 { "            { " } else { "            , " };
+            output += &format!("{} = {} V.! i\n", row_field(sv), column_var(sv));
+        }
+        output += "            })\n";
+
+        // generate table definition
+        output += &format!(
+            "      let tbl{} = TableDefinition{}\n",
+            tname_pasc_case, tname_pasc_case
+        );
+        output += &format!(
+            "            {{ {} = len{}\n",
+            td_field(t.name.as_str(), "length"),
+            tname_pasc_case
+        );
+        output += &format!(
+            "            , {} = map TableRowPointer{} [0 .. len{} - 1]\n",
+            td_field(t.name.as_str(), "pointers"),
+            tname_pasc_case,
+            tname_pasc_case
+        );
+        output += &format!(
+            "            , {} = \\(TableRowPointer{} ptr) -> rows{} V.! ptr\n",
+            td_field(t.name.as_str(), "row"),
+            tname_pasc_case,
+            tname_pasc_case
+        );
+        for sv in &columns {
+            output += &format!(
+                "            , {} = \\(TableRowPointer{} ptr) -> {} V.! ptr\n",
+                td_column_field(sv),
+                tname_pasc_case,
+                column_var(sv)
+            );
+        }
+        output += "            }\n";
+    }
+
+    output += "      pure Database\n";
+    for (idx, t) in data.tables_sorted().iter().enumerate() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        output += if idx == 0 { "        { " } else { "        , " };
+        output += &format!("db{} = tbl{}\n", tname_pasc_case, tname_pasc_case);
+    }
+    output += "        }\n";
+
+    output
+}
+
+fn debug_dump_function(data: &AllData) -> String {
+    let mut output = String::new();
+
+    output += "dumpToStdout :: Database -> IO ()\n";
+    output += "dumpToStdout database = do\n";
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        output += &format!(
+            "  putStrLn \"TABLE: {} <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<\"\n",
+            t.name.as_str()
+        );
+        output += &format!(
+            "  mapM_ (print . {} (db{} database)) ({} (db{} database))\n",
+            td_field(t.name.as_str(), "row"),
+            tname_pasc_case,
+            td_field(t.name.as_str(), "pointers"),
+            tname_pasc_case
+        );
+    }
+
+    output
+}
+
+#[cfg(test)]
+fn assert_haskell_db_compiled_dump_equals(source: &str, output_dump: &str) {
+    use std::process::{Command, Stdio};
+
+    use crate::db_parser::{self, InputSource};
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(source.to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+
+    let gen = HaskellCodegen {
+        debug_dump_function: true,
+        ..Default::default()
+    };
+    let codegen_outputs = gen.generate(&data);
+    codegen_outputs.dump_to_dir(tmp_dir.to_str().unwrap());
+
+    let main_hs_contents = r#"module Main where
+
+import qualified Database
+
+main :: IO ()
+main = Database.dumpToStdout Database.db
+"#;
+    std::fs::write(tmp_dir.join("Main.hs"), main_hs_contents).unwrap();
+
+    // needs vector, text and file-embed packages visible to ghc
+    let output = Command::new("ghc")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(&tmp_dir)
+        .args(["-o", "main", "Main.hs"])
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new("./main")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(&tmp_dir)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let out_res = String::from_utf8(output.stdout).unwrap();
+    pretty_assertions::assert_eq!(out_res, output_dump);
+}
+
+#[test]
+#[ignore]
+fn test_haskell_codegen_integration() {
+    let source = r#"
+TABLE thic_boi {
+  id INT,
+  name TEXT,
+  b BOOL,
+  f FLOAT,
+  fk REF some_enum,
+}
+
+TABLE some_enum CONSTANTS {
+  name TEXT PRIMARY KEY,
+}
+
+TABLE enum_child_a {
+  inner_name_a TEXT PRIMARY KEY CHILD OF some_enum,
+}
+
+TABLE enum_child_b {
+  inner_name_b TEXT PRIMARY KEY CHILD OF some_enum,
+}
+
+DATA thic_boi {
+  1, hey ho, true, 1.23, warm;
+  2, here she goes, false, 3.21, hot;
+  3, either blah, true, 5.43, hot;
+}
+
+DATA enum_child_a(name, inner_name_a) {
+  warm, barely warm;
+  warm, medium warm;
+}
+
+DATA enum_child_b(name, inner_name_b) {
+  warm, barely degrees;
+  warm, medium degrees;
+}
+
+DATA EXCLUSIVE some_enum {
+  warm;
+  hot;
+}
+"#;
+    let output_dump = r#"TABLE: enum_child_a <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowEnumChildA {row_enum_child_a'inner_name_a = "barely warm", row_enum_child_a'parent = TableRowPointerSomeEnum 0}
+TableRowEnumChildA {row_enum_child_a'inner_name_a = "medium warm", row_enum_child_a'parent = TableRowPointerSomeEnum 0}
+TABLE: enum_child_b <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowEnumChildB {row_enum_child_b'inner_name_b = "barely degrees", row_enum_child_b'parent = TableRowPointerSomeEnum 0}
+TableRowEnumChildB {row_enum_child_b'inner_name_b = "medium degrees", row_enum_child_b'parent = TableRowPointerSomeEnum 0}
+TABLE: some_enum <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowSomeEnum {row_some_enum'name = "warm", row_some_enum'children_enum_child_a = [TableRowPointerEnumChildA 0,TableRowPointerEnumChildA 1], row_some_enum'children_enum_child_b = [TableRowPointerEnumChildB 0,TableRowPointerEnumChildB 1], row_some_enum'referrers_thic_boi__fk = [TableRowPointerThicBoi 0]}
+TableRowSomeEnum {row_some_enum'name = "hot", row_some_enum'children_enum_child_a = [], row_some_enum'children_enum_child_b = [], row_some_enum'referrers_thic_boi__fk = [TableRowPointerThicBoi 1,TableRowPointerThicBoi 2]}
+TABLE: thic_boi <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowThicBoi {row_thic_boi'id = 1, row_thic_boi'name = "hey ho", row_thic_boi'b = True, row_thic_boi'f = 1.23, row_thic_boi'fk = TableRowPointerSomeEnum 0}
+TableRowThicBoi {row_thic_boi'id = 2, row_thic_boi'name = "here she goes", row_thic_boi'b = False, row_thic_boi'f = 3.21, row_thic_boi'fk = TableRowPointerSomeEnum 1}
+TableRowThicBoi {row_thic_boi'id = 3, row_thic_boi'name = "either blah", row_thic_boi'b = True, row_thic_boi'f = 5.43, row_thic_boi'fk = TableRowPointerSomeEnum 1}
+"#;
+
+    assert_haskell_db_compiled_dump_equals(source, output_dump);
+}
+
+#[test]
+fn test_haskell_codegen_row_constants() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE disk_manufacturer CONSTANTS {
+  model TEXT PRIMARY KEY,
+}
+
+DATA disk_manufacturer {
+  intel;
+  western-digital;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let outputs = HaskellCodegen::default().generate(&data);
+    assert_eq!(outputs.files[0].filename, "Database.hs");
+    let hs = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+
+    assert!(hs.contains("  , disk_manufacturer_intel\n"));
+    assert!(hs.contains("disk_manufacturer_intel :: TableRowPointerDiskManufacturer\ndisk_manufacturer_intel = TableRowPointerDiskManufacturer 0\n"));
+    assert!(hs.contains("disk_manufacturer_western_digital :: TableRowPointerDiskManufacturer\ndisk_manufacturer_western_digital = TableRowPointerDiskManufacturer 1\n"));
+}
+
+#[test]
+fn test_haskell_codegen_field_names_dont_collide() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(HASKELL_FIELD_COLLISION_SOURCE.to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let outputs = HaskellCodegen::default().generate(&data);
+    let hs = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+
+    assert!(hs.contains("row_server'disk_size :: !Int64\n"));
+    assert!(hs.contains("row_server_disk'size :: !Int64\n"));
+    assert!(hs.contains("td_server'c'disk_size :: TableRowPointerServer -> Int64\n"));
+    assert!(hs.contains("td_server_disk'c'size :: TableRowPointerServerDisk -> Int64\n"));
+}
+
+#[cfg(test)]
+const HASKELL_FIELD_COLLISION_SOURCE: &str = r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  disk_size INT,
+}
+
+TABLE server_disk {
+  size INT,
+}
+
+DATA server {
+  epyc-1, 128;
+}
+
+DATA server_disk {
+  256;
+}
+"#;
+
+#[test]
+#[ignore]
+fn test_haskell_codegen_field_names_dont_collide_integration() {
+    let output_dump = r#"TABLE: server <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowServer {row_server'hostname = "epyc-1", row_server'disk_size = 128}
+TABLE: server_disk <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowServerDisk {row_server_disk'size = 256}
+"#;
+
+    assert_haskell_db_compiled_dump_equals(HASKELL_FIELD_COLLISION_SOURCE, output_dump);
+}
@@ -0,0 +1,352 @@
+use serde_json::{json, Map, Number, Value};
+
+use crate::checker::{
+    logic::AllData,
+    types::{ColumnVector, DataTable, SerializationVector},
+};
+
+/// How `parent`, `children_*` and `referrers_*` relations are exported
+#[derive(Clone, Copy, PartialEq, Eq, Debug, clap::ValueEnum)]
+pub enum JsonRelations {
+    /// Only table columns are exported
+    None,
+    /// Relations are row indexes in the related table
+    Indices,
+    /// Relations are primary keys of related rows, with parent
+    /// keys joined by `=>`, rows of tables without primary key are
+    /// referred to by index
+    PrimaryKeys,
+}
+
+fn is_exported_relation(sv: &SerializationVector) -> bool {
+    match sv {
+        SerializationVector::Fkeys { sv, .. } => sv.column_name == "parent",
+        SerializationVector::FkeysOneToMany { sv, .. } => {
+            sv.column_name.starts_with("children_") || sv.column_name.starts_with("referrers_")
+        }
+        _ => false,
+    }
+}
+
+fn relation_vectors<'a, 'b>(
+    t: &DataTable,
+    vecs: &'a [SerializationVector<'b>],
+) -> Vec<&'a SerializationVector<'b>> {
+    vecs.iter()
+        .filter(|sv| sv.table_name() == t.name.as_str() && is_exported_relation(sv))
+        .collect()
+}
+
+fn row_primary_key(t: &DataTable, row: usize) -> Option<String> {
+    let keys = t.primary_keys_with_parents();
+    if keys.is_empty() {
+        return None;
+    }
+
+    let segments: Vec<_> = keys
+        .iter()
+        .map(|k| match &t.columns[*k].data {
+            ColumnVector::Strings(v) => v.v[row].clone(),
+            ColumnVector::Ints(v) => v.v[row].to_string(),
+            ColumnVector::Floats(v) => v.v[row].to_string(),
+            ColumnVector::Bools(v) => v.v[row].to_string(),
+        })
+        .collect();
+    Some(segments.join("=>"))
+}
+
+fn relation_value(data: &AllData, foreign_table: &str, row: usize, relations: JsonRelations) -> Value {
+    if relations == JsonRelations::PrimaryKeys {
+        let t = data
+            .tables
+            .iter()
+            .find(|t| t.name.as_str() == foreign_table)
+            .unwrap();
+        if let Some(key) = row_primary_key(t, row) {
+            return Value::String(key);
+        }
+    }
+
+    Value::Number(Number::from(row))
+}
+
+/// Every table with rows as objects, tables and object keys are sorted
+pub fn json_dump(data: &AllData, relations: JsonRelations) -> Value {
+    let vecs = data.serialization_vectors();
+    let mut tables = Map::new();
+
+    for t in data.tables_sorted() {
+        let relation_vecs = if relations != JsonRelations::None {
+            relation_vectors(t, &vecs)
+        } else {
+            Vec::new()
+        };
+
+        let mut rows = Vec::with_capacity(t.len());
+        for row in 0..t.len() {
+            let mut row_value = Map::new();
+
+            for col in &t.columns {
+                let value = match &col.data {
+                    ColumnVector::Strings(v) => Value::String(v.v[row].clone()),
+                    ColumnVector::Ints(v) => Value::Number(Number::from(v.v[row])),
+                    // NaN and infinities are rejected when validating data,
+                    // so every float is a json number like the schema says
+                    ColumnVector::Floats(v) => Value::Number(
+                        Number::from_f64(v.v[row]).expect("non finite floats fail validation"),
+                    ),
+                    ColumnVector::Bools(v) => Value::Bool(v.v[row]),
+                };
+                row_value.insert(col.column_name.as_str().to_string(), value);
+            }
+
+            for sv in &relation_vecs {
+                let value = match sv {
+                    SerializationVector::Fkeys { sv, foreign_table } => {
+                        relation_value(data, foreign_table, sv.v[row], relations)
+                    }
+                    SerializationVector::FkeysOneToMany { sv, foreign_table } => Value::Array(
+                        sv.v[row]
+                            .iter()
+                            .map(|i| relation_value(data, foreign_table, *i, relations))
+                            .collect(),
+                    ),
+                    _ => unreachable!(),
+                };
+                row_value.insert(sv.column_name().to_string(), value);
+            }
+
+            rows.push(Value::Object(row_value));
+        }
+
+        tables.insert(t.name.as_str().to_string(), Value::Array(rows));
+    }
+
+    Value::Object(tables)
+}
+
+/// JSON schema describing document produced by `json_dump` with the same relations option
+pub fn json_schema(data: &AllData, relations: JsonRelations) -> Value {
+    let vecs = data.serialization_vectors();
+    let mut properties = Map::new();
+
+    let relation_schema = |foreign_table: &str| -> Value {
+        let t = data
+            .tables
+            .iter()
+            .find(|t| t.name.as_str() == foreign_table)
+            .unwrap();
+        if relations == JsonRelations::PrimaryKeys && !t.primary_keys_with_parents().is_empty() {
+            json!({ "type": "string" })
+        } else {
+            json!({ "type": "integer", "minimum": 0 })
+        }
+    };
+
+    for t in data.tables_sorted() {
+        let mut columns = Map::new();
+
+        for col in &t.columns {
+            let ctype = match &col.data {
+                ColumnVector::Strings(_) => "string",
+                ColumnVector::Ints(_) => "integer",
+                ColumnVector::Floats(_) => "number",
+                ColumnVector::Bools(_) => "boolean",
+            };
+            columns.insert(col.column_name.as_str().to_string(), json!({ "type": ctype }));
+        }
+
+        if relations != JsonRelations::None {
+            for sv in relation_vectors(t, &vecs) {
+                let schema = match sv {
+                    SerializationVector::Fkeys { foreign_table, .. } => relation_schema(foreign_table),
+                    SerializationVector::FkeysOneToMany { foreign_table, .. } => json!({
+                        "type": "array",
+                        "items": relation_schema(foreign_table),
+                    }),
+                    _ => unreachable!(),
+                };
+                columns.insert(sv.column_name().to_string(), schema);
+            }
+        }
+
+        let required: Vec<_> = columns.keys().cloned().collect();
+        properties.insert(
+            t.name.as_str().to_string(),
+            json!({
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": columns,
+                    "required": required,
+                    "additionalProperties": false,
+                },
+            }),
+        );
+    }
+
+    let required: Vec<_> = properties.keys().cloned().collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+#[cfg(test)]
+fn json_test_data() -> AllData {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE thic_boi {
+  id INT,
+  f FLOAT,
+  fk REF some_enum,
+}
+
+TABLE some_enum {
+  name TEXT PRIMARY KEY,
+}
+
+TABLE enum_child_a {
+  inner_name_a TEXT PRIMARY KEY CHILD OF some_enum,
+  b BOOL,
+}
+
+DATA thic_boi {
+  1, 1.5, warm;
+}
+
+DATA enum_child_a(name, inner_name_a, b) {
+  warm, barely warm, true;
+}
+
+DATA EXCLUSIVE some_enum {
+  warm;
+  hot;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    AllData::new(sources).unwrap()
+}
+
+#[test]
+fn test_json_dump_relations() {
+    let data = json_test_data();
+
+    pretty_assertions::assert_eq!(
+        json_dump(&data, JsonRelations::None),
+        json!({
+            "enum_child_a": [
+                { "name": "warm", "inner_name_a": "barely warm", "b": true },
+            ],
+            "some_enum": [
+                { "name": "warm" },
+                { "name": "hot" },
+            ],
+            "thic_boi": [
+                { "id": 1, "f": 1.5, "fk": "warm" },
+            ],
+        })
+    );
+
+    pretty_assertions::assert_eq!(
+        json_dump(&data, JsonRelations::Indices),
+        json!({
+            "enum_child_a": [
+                { "name": "warm", "inner_name_a": "barely warm", "b": true, "parent": 0 },
+            ],
+            "some_enum": [
+                { "name": "warm", "children_enum_child_a": [0], "referrers_thic_boi__fk": [0] },
+                { "name": "hot", "children_enum_child_a": [], "referrers_thic_boi__fk": [] },
+            ],
+            "thic_boi": [
+                { "id": 1, "f": 1.5, "fk": "warm" },
+            ],
+        })
+    );
+
+    pretty_assertions::assert_eq!(
+        json_dump(&data, JsonRelations::PrimaryKeys),
+        json!({
+            "enum_child_a": [
+                { "name": "warm", "inner_name_a": "barely warm", "b": true, "parent": "warm" },
+            ],
+            "some_enum": [
+                { "name": "warm", "children_enum_child_a": ["warm=>barely warm"], "referrers_thic_boi__fk": [0] },
+                { "name": "hot", "children_enum_child_a": [], "referrers_thic_boi__fk": [] },
+            ],
+            "thic_boi": [
+                { "id": 1, "f": 1.5, "fk": "warm" },
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_json_schema() {
+    let data = json_test_data();
+
+    pretty_assertions::assert_eq!(
+        json_schema(&data, JsonRelations::PrimaryKeys),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "enum_child_a": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "inner_name_a": { "type": "string" },
+                            "b": { "type": "boolean" },
+                            "parent": { "type": "string" },
+                        },
+                        "required": ["b", "inner_name_a", "name", "parent"],
+                        "additionalProperties": false,
+                    },
+                },
+                "some_enum": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "children_enum_child_a": { "type": "array", "items": { "type": "string" } },
+                            "referrers_thic_boi__fk": { "type": "array", "items": { "type": "integer", "minimum": 0 } },
+                        },
+                        "required": ["children_enum_child_a", "name", "referrers_thic_boi__fk"],
+                        "additionalProperties": false,
+                    },
+                },
+                "thic_boi": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "integer" },
+                            "f": { "type": "number" },
+                            "fk": { "type": "string" },
+                        },
+                        "required": ["f", "fk", "id"],
+                        "additionalProperties": false,
+                    },
+                },
+            },
+            "required": ["enum_child_a", "some_enum", "thic_boi"],
+            "additionalProperties": false,
+        })
+    );
+}
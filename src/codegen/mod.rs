@@ -2,9 +2,15 @@ use std::path::PathBuf;
 
 use crate::checker::{logic::AllData, types::SerializationVector};
 
+pub mod cpp;
+pub mod go;
+pub mod haskell;
+pub mod json;
 pub mod ocaml;
 pub mod ocaml_data_module;
+pub mod protobuf;
 pub mod rust;
+pub mod sqlite;
 
 pub struct CodegenOutputFile {
     filename: String,
@@ -47,6 +53,22 @@ impl CodegenOutputs {
             write_file_check_if_different(&fpath, &i.content);
         }
     }
+
+    /// Remove files with extension left in the directory by earlier runs which
+    /// are not generated anymore, like modules of removed tables
+    pub fn remove_stale_files(&self, output_dir: &str, extension: &str) {
+        for entry in std::fs::read_dir(output_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some(extension) {
+                continue;
+            }
+
+            let file_name = path.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+            if !self.files.iter().any(|f| f.filename == file_name) {
+                std::fs::remove_file(&path).unwrap();
+            }
+        }
+    }
 }
 
 pub trait CodeGenerator {
@@ -83,6 +105,92 @@ pub fn dump_as_bytes(vecs: &Vec<SerializationVector>) -> Vec<u8> {
     output
 }
 
+/// Magic bytes at the start of zero copy data
+pub const ZERO_COPY_MAGIC: &[u8; 8] = b"EDBZCOPY";
+
+/// Returns aligned uncompressed columnar data which can be read without copying
+/// from 8 byte aligned memory, like `include_bytes!` wrapper or mmap.
+/// All numbers are little endian, every section is padded to 8 bytes.
+/// | magic 8 bytes | vector count u64 | vectors... | checksum (xxhash of all previous bytes, 8 bytes) |
+/// Every vector starts with row count u64, then:
+///  ints, floats, foreign keys: values, 8 bytes each
+///  bools: values, 1 byte each, then padding
+///  strings: row count + 1 offsets u64, utf8 bytes of all strings, then padding
+///  one to many foreign keys: row count + 1 offsets u64, all values u64
+pub fn dump_as_bytes_zero_copy(vecs: &Vec<SerializationVector>) -> Vec<u8> {
+    fn pad(output: &mut Vec<u8>) {
+        let padding = (8 - output.len() % 8) % 8;
+        output.resize(output.len() + padding, 0);
+    }
+
+    fn push_u64(output: &mut Vec<u8>, v: u64) {
+        output.extend_from_slice(&v.to_le_bytes());
+    }
+
+    let mut output = Vec::with_capacity(1024);
+    output.extend_from_slice(ZERO_COPY_MAGIC);
+    push_u64(&mut output, vecs.len() as u64);
+
+    for sv in vecs {
+        match sv {
+            crate::checker::types::SerializationVector::Strings(v) => {
+                push_u64(&mut output, v.v.len() as u64);
+                let mut offset = 0;
+                push_u64(&mut output, offset);
+                for s in v.v.iter() {
+                    offset += s.len() as u64;
+                    push_u64(&mut output, offset);
+                }
+                for s in v.v.iter() {
+                    output.extend_from_slice(s.as_bytes());
+                }
+                pad(&mut output);
+            }
+            crate::checker::types::SerializationVector::Ints(v) => {
+                push_u64(&mut output, v.v.len() as u64);
+                for i in v.v.iter() {
+                    output.extend_from_slice(&i.to_le_bytes());
+                }
+            }
+            crate::checker::types::SerializationVector::Floats(v) => {
+                push_u64(&mut output, v.v.len() as u64);
+                for f in v.v.iter() {
+                    output.extend_from_slice(&f.to_le_bytes());
+                }
+            }
+            crate::checker::types::SerializationVector::Bools(v) => {
+                push_u64(&mut output, v.v.len() as u64);
+                for b in v.v.iter() {
+                    output.push(*b as u8);
+                }
+                pad(&mut output);
+            }
+            crate::checker::types::SerializationVector::Fkeys { sv, .. } => {
+                push_u64(&mut output, sv.v.len() as u64);
+                for ptr in sv.v.iter() {
+                    push_u64(&mut output, *ptr as u64);
+                }
+            }
+            crate::checker::types::SerializationVector::FkeysOneToMany { sv, .. } => {
+                push_u64(&mut output, sv.v.len() as u64);
+                let mut offset = 0;
+                push_u64(&mut output, offset);
+                for ptrs in sv.v.iter() {
+                    offset += ptrs.len() as u64;
+                    push_u64(&mut output, offset);
+                }
+                for ptr in sv.v.iter().flatten() {
+                    push_u64(&mut output, *ptr as u64);
+                }
+            }
+        }
+    }
+
+    let hash = xxhash_rust::xxh3::xxh3_64(&output);
+    push_u64(&mut output, hash);
+    output
+}
+
 /// output binary format:
 /// | lz4 compressed data | checksum (xxhash 8 bytes) |
 /// Data is checksummed after compression.
@@ -234,3 +342,77 @@ DATA EXCLUSIVE some_enum {
 
     assert_eden_db_binary_dump_equals(source, expected_dump);
 }
+
+#[test]
+fn test_binary_dump_zero_copy_assumptions() {
+    use crate::db_parser::{self, InputSource};
+
+    let source = r#"
+TABLE some_enum {
+  name TEXT PRIMARY KEY,
+  flag BOOL,
+}
+
+TABLE enum_child {
+  inner INT PRIMARY KEY CHILD OF some_enum,
+}
+
+DATA some_enum {
+  a, true;
+  bcd, false;
+}
+
+DATA enum_child(name, inner) {
+  a, 7;
+}
+"#;
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(source.to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let gen = rust::RustCodegen {
+        zero_copy_data: true,
+        ..Default::default()
+    };
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let codegen_outputs = gen.generate(&data);
+
+    let mut expected_dump: Vec<u8> = vec![
+        // magic
+        b'E', b'D', b'B', b'Z', b'C', b'O', b'P', b'Y',
+        // vector count
+        5, 0, 0, 0, 0, 0, 0, 0,
+        // enum_child table
+        //  inner column size
+        1, 0, 0, 0, 0, 0, 0, 0, //  inner column elements
+        7, 0, 0, 0, 0, 0, 0, 0,
+        //  parent column size
+        1, 0, 0, 0, 0, 0, 0, 0, //  parent column elements
+        0, 0, 0, 0, 0, 0, 0, 0,
+        // some_enum table
+        //  name column size
+        2, 0, 0, 0, 0, 0, 0, 0, //  name column offsets
+        0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0,
+        //  name column bytes with padding
+        b'a', b'b', b'c', b'd', 0, 0, 0, 0,
+        //  flag column size
+        2, 0, 0, 0, 0, 0, 0, 0, //  flag column elements with padding
+        1, 0, 0, 0, 0, 0, 0, 0,
+        //  children_enum_child column size
+        2, 0, 0, 0, 0, 0, 0, 0, //  children_enum_child offsets
+        0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+        //  children_enum_child elements
+        0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    let hash = xxhash_rust::xxh3::xxh3_64(&expected_dump);
+    expected_dump.extend_from_slice(&hash.to_le_bytes());
+
+    pretty_assertions::assert_eq!(codegen_outputs.uncompressed_edb_data, expected_dump);
+    // data file is written uncompressed for zero copy reads
+    pretty_assertions::assert_eq!(codegen_outputs.files[1].content, expected_dump);
+}
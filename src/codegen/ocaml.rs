@@ -40,6 +40,15 @@ let data_blob = [%blob "edb_data.bin"]
         }
         impl_content += "\n";
 
+        if !comp.table_row_constants.is_empty() {
+            impl_content += "(* Table row constants *)\n";
+            for trow_constant in &comp.table_row_constants {
+                impl_content += trow_constant;
+                impl_content += "\n";
+            }
+            impl_content += "\n";
+        }
+
         impl_content += "(* Table row types *)\n";
         for trow_pointer in &comp.table_structs {
             impl_content += trow_pointer;
@@ -77,6 +86,15 @@ let data_blob = [%blob "edb_data.bin"]
         }
         mli_content += "\n";
 
+        if !comp.table_row_constants_decl.is_empty() {
+            mli_content += "(* Table row constants *)\n";
+            for trow_constant in &comp.table_row_constants_decl {
+                mli_content += trow_constant;
+                mli_content += "\n";
+            }
+            mli_content += "\n";
+        }
+
         mli_content += "(* Table row types *)\n";
         for trow_pointer in &comp.table_structs {
             mli_content += trow_pointer;
@@ -127,6 +145,8 @@ let data_blob = [%blob "edb_data.bin"]
 struct OcamlCodegenCompute {
     table_pointer_types: Vec<String>,
     table_pointer_types_decl: Vec<String>,
+    table_row_constants: Vec<String>,
+    table_row_constants_decl: Vec<String>,
     table_structs: Vec<String>,
     table_definitions: Vec<String>,
     database_definition: String,
@@ -140,6 +160,8 @@ impl OcamlCodegenCompute {
         let serialization_vectors = data.serialization_vectors();
         let table_pointer_types_decl = table_pointer_types(data, false, opt.debug_dump_function);
         let table_pointer_types = table_pointer_types(data, true, opt.debug_dump_function);
+        let table_row_constants_decl = table_row_constants(data, false);
+        let table_row_constants = table_row_constants(data, true);
         let table_structs = table_structs(data, opt.debug_dump_function, &serialization_vectors);
         let table_definitions = table_definitions(data, &serialization_vectors);
         let database_definition = database_definition(data);
@@ -150,6 +172,8 @@ impl OcamlCodegenCompute {
         OcamlCodegenCompute {
             table_pointer_types,
             table_pointer_types_decl,
+            table_row_constants,
+            table_row_constants_decl,
             table_structs,
             table_definitions,
             database_definition,
@@ -250,6 +274,21 @@ fn table_pointer_types(
     res
 }
 
+fn table_row_constants(data: &AllData, with_implementation: bool) -> Vec<String> {
+    let mut res = Vec::new();
+    for t in data.tables_sorted() {
+        let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
+        for (idx, constant) in t.row_constant_names().iter().enumerate() {
+            if with_implementation {
+                res.push(format!("let {} = TableRowPointer{} {}", constant, tname_pasc_case, idx));
+            } else {
+                res.push(format!("val {}: table_row_pointer_{}", constant, t.name.as_str()));
+            }
+        }
+    }
+    res
+}
+
 fn table_structs(
     data: &AllData,
     with_yojson: bool,
@@ -768,3 +807,59 @@ TABLE: thic_boi <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
 
     assert_ocaml_db_compiled_dump_equals(source, output_dump);
 }
+
+#[test]
+#[ignore]
+fn test_ocaml_codegen_row_constants_integration() {
+    let source = r#"
+TABLE some_enum CONSTANTS {
+  name TEXT PRIMARY KEY,
+}
+
+DATA some_enum {
+  warm;
+  hot;
+}
+"#;
+    let output_dump = r#"TABLE: some_enum <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+{"name":"warm"}
+{"name":"hot"}
+"#;
+
+    assert_ocaml_db_compiled_dump_equals(source, output_dump);
+}
+
+#[test]
+fn test_ocaml_codegen_row_constants() {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE disk_manufacturer CONSTANTS {
+  model TEXT PRIMARY KEY,
+}
+
+DATA disk_manufacturer {
+  intel;
+  western-digital;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let outputs = OCamlCodegen::default().generate(&data);
+    let ml = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+    let mli = String::from_utf8(outputs.files[1].content.clone()).unwrap();
+
+    assert!(ml.contains("let disk_manufacturer_intel = TableRowPointerDiskManufacturer 0\n"));
+    assert!(ml.contains("let disk_manufacturer_western_digital = TableRowPointerDiskManufacturer 1\n"));
+    assert!(mli.contains("val disk_manufacturer_intel: table_row_pointer_disk_manufacturer\n"));
+    assert!(mli.contains("val disk_manufacturer_western_digital: table_row_pointer_disk_manufacturer\n"));
+}
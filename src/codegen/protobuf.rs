@@ -0,0 +1,616 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use convert_case::{Case, Casing};
+
+use crate::checker::{
+    logic::AllData,
+    types::{DataTable, SerializationVector},
+};
+
+use super::{CodeGenerator, CodegenOutputFile};
+
+/// Exports proto3 schema and the whole database encoded
+/// as a single `Database` message, readable by any protobuf toolchain.
+/// Foreign keys, children and referrers are row indexes in the target table.
+/// Field numbers are kept in a lock file next to the schema which should be
+/// committed, new fields get new numbers and numbers of removed fields or
+/// fields with changed type are reserved, so they are never reused.
+pub struct ProtobufCodegen {
+    pub package_name: String,
+    pub proto_file_name: String,
+    pub data_file_name: String,
+    pub lock_file_name: String,
+    /// Field numbers from previous generation
+    pub field_numbers: ProtoFieldNumbers,
+}
+
+impl Default for ProtobufCodegen {
+    fn default() -> Self {
+        Self {
+            package_name: "edendb".to_string(),
+            proto_file_name: "database.proto".to_string(),
+            data_file_name: "edb_data.pb".to_string(),
+            lock_file_name: "database.proto.lock".to_string(),
+            field_numbers: ProtoFieldNumbers::default(),
+        }
+    }
+}
+
+impl ProtobufCodegen {
+    /// Reads field numbers lock file from output directory if it exists
+    pub fn load_field_numbers(&mut self, output_dir: &str) -> Result<(), String> {
+        let path = std::path::PathBuf::from(output_dir).join(&self.lock_file_name);
+        match std::fs::read(&path) {
+            Ok(bytes) => {
+                self.field_numbers = serde_json::from_slice(&bytes)
+                    .map_err(|e| format!("cannot parse {}: {}", path.display(), e))?;
+                Ok(())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("cannot read {}: {}", path.display(), e)),
+        }
+    }
+}
+
+#[derive(Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ProtoFieldNumbers {
+    /// `Database` message fields
+    tables: ProtoMessageNumbers,
+    /// Table row message fields by table name
+    columns: BTreeMap<String, ProtoMessageNumbers>,
+}
+
+#[derive(Default, Clone, serde::Serialize, serde::Deserialize)]
+struct ProtoMessageNumbers {
+    fields: BTreeMap<String, ProtoField>,
+    reserved: BTreeSet<u32>,
+}
+
+#[derive(Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct ProtoField {
+    number: u32,
+    #[serde(rename = "type")]
+    field_type: String,
+}
+
+impl ProtoMessageNumbers {
+    /// Field numbers for `fields` in the same order
+    fn assign(&mut self, fields: &[(&str, String)]) -> Vec<u32> {
+        let removed: Vec<String> = self
+            .fields
+            .iter()
+            .filter(|(name, f)| !fields.iter().any(|(n, t)| n == name && *t == f.field_type))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in removed {
+            let f = self.fields.remove(&name).unwrap();
+            let _ = self.reserved.insert(f.number);
+        }
+
+        let mut next = self
+            .fields
+            .values()
+            .map(|f| f.number)
+            .chain(self.reserved.iter().copied())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        fields
+            .iter()
+            .map(|(name, field_type)| {
+                let f = self.fields.entry(name.to_string()).or_insert_with(|| {
+                    // implementation reserved range
+                    if (19000..20000).contains(&next) {
+                        next = 20000;
+                    }
+                    next += 1;
+                    ProtoField { number: next - 1, field_type: field_type.clone() }
+                });
+                f.number
+            })
+            .collect()
+    }
+}
+
+struct ProtoTable<'a, 'b> {
+    table: &'a DataTable,
+    number: u32,
+    /// columns with field numbers, sorted by field number
+    columns: Vec<(u32, &'a SerializationVector<'b>)>,
+    reserved: Vec<u32>,
+}
+
+fn proto_field_type(sv: &SerializationVector) -> &'static str {
+    match sv {
+        SerializationVector::Strings(_) => "string",
+        SerializationVector::Ints(_) => "int64",
+        SerializationVector::Floats(_) => "double",
+        SerializationVector::Bools(_) => "bool",
+        SerializationVector::Fkeys { .. } => "uint64",
+        SerializationVector::FkeysOneToMany { .. } => "repeated uint64",
+    }
+}
+
+fn row_message_name(t: &DataTable) -> String {
+    format!("TableRow{}", t.name.as_str().to_case(Case::Pascal))
+}
+
+/// Tables sorted by field number with updated field numbers lock
+fn proto_tables<'a, 'b>(
+    data: &'a AllData,
+    vecs: &'a [SerializationVector<'b>],
+    lock: &ProtoFieldNumbers,
+) -> (Vec<ProtoTable<'a, 'b>>, ProtoFieldNumbers) {
+    let mut lock = lock.clone();
+    let tables = data.tables_sorted();
+    let table_fields: Vec<_> = tables.iter().map(|t| (t.name.as_str(), row_message_name(t))).collect();
+    let table_numbers = lock.tables.assign(&table_fields);
+    lock.columns.retain(|t, _| lock.tables.fields.contains_key(t));
+
+    let mut res = Vec::with_capacity(tables.len());
+    for (t, number) in tables.into_iter().zip(table_numbers) {
+        let columns: Vec<_> = vecs.iter().filter(|sv| sv.table_name() == t.name.as_str()).collect();
+        let column_fields: Vec<_> = columns
+            .iter()
+            .map(|sv| (sv.column_name(), proto_field_type(sv).to_string()))
+            .collect();
+        let message = lock.columns.entry(t.name.as_str().to_string()).or_default();
+        let column_numbers = message.assign(&column_fields);
+        let mut columns: Vec<_> = column_numbers.into_iter().zip(columns).collect();
+        columns.sort_by_key(|(n, _)| *n);
+        res.push(ProtoTable {
+            table: t,
+            number,
+            columns,
+            reserved: message.reserved.iter().copied().collect(),
+        });
+    }
+    res.sort_by_key(|t| t.number);
+
+    (res, lock)
+}
+
+impl CodeGenerator for ProtobufCodegen {
+    fn generate(&self, data: &AllData) -> super::CodegenOutputs {
+        let vecs = data.serialization_vectors();
+        let (tables, lock) = proto_tables(data, &vecs, &self.field_numbers);
+        let schema = proto_schema(&tables, &lock, &self.package_name);
+        let data_bytes = proto_encode_database(&tables);
+
+        let schema_src = CodegenOutputFile {
+            filename: self.proto_file_name.clone(),
+            content: schema.into_bytes(),
+        };
+
+        let data_src = CodegenOutputFile {
+            filename: self.data_file_name.clone(),
+            content: data_bytes.clone(),
+        };
+
+        let mut lock_bytes = serde_json::to_vec_pretty(&lock).unwrap();
+        lock_bytes.push(b'\n');
+        let lock_src = CodegenOutputFile {
+            filename: self.lock_file_name.clone(),
+            content: lock_bytes,
+        };
+
+        super::CodegenOutputs {
+            uncompressed_edb_data: data_bytes,
+            files: vec![schema_src, data_src, lock_src],
+        }
+    }
+}
+
+fn push_reserved(output: &mut String, reserved: &[u32]) {
+    if !reserved.is_empty() {
+        let numbers: Vec<_> = reserved.iter().map(|n| n.to_string()).collect();
+        *output += &format!("  reserved {};\n", numbers.join(", "));
+    }
+}
+
+fn proto_schema(tables: &[ProtoTable], lock: &ProtoFieldNumbers, package_name: &str) -> String {
+    let mut output = String::new();
+
+    output += "// EdenDB generated database\n";
+    output += "syntax = \"proto3\";\n";
+    output += "\n";
+    output += &format!("package {};\n", package_name);
+
+    let mut by_name: Vec<_> = tables.iter().collect();
+    by_name.sort_by_key(|t| t.table.name.as_str());
+    for t in by_name {
+        output += "\n";
+        output += &format!("message {} {{\n", row_message_name(t.table));
+        push_reserved(&mut output, &t.reserved);
+        for (number, sv) in &t.columns {
+            match sv {
+                SerializationVector::Fkeys { foreign_table, .. }
+                | SerializationVector::FkeysOneToMany { foreign_table, .. } => {
+                    output += &format!("  // row index in {}\n", foreign_table);
+                }
+                _ => {}
+            }
+            output += &format!("  {} {} = {};\n", proto_field_type(sv), sv.column_name(), number);
+        }
+        output += "}\n";
+    }
+
+    output += "\n";
+    output += "message Database {\n";
+    push_reserved(&mut output, &lock.tables.reserved.iter().copied().collect::<Vec<_>>());
+    for t in tables {
+        output += &format!(
+            "  repeated {} {} = {};\n",
+            row_message_name(t.table),
+            t.table.name.as_str(),
+            t.number
+        );
+    }
+    output += "}\n";
+
+    output
+}
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_FIXED64: u64 = 1;
+const WIRE_LEN: u64 = 2;
+
+fn push_varint(output: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        output.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    output.push(v as u8);
+}
+
+fn push_tag(output: &mut Vec<u8>, field: usize, wire_type: u64) {
+    push_varint(output, ((field as u64) << 3) | wire_type);
+}
+
+fn push_len_delimited(output: &mut Vec<u8>, field: usize, bytes: &[u8]) {
+    push_tag(output, field, WIRE_LEN);
+    push_varint(output, bytes.len() as u64);
+    output.extend_from_slice(bytes);
+}
+
+/// Canonical proto3 encoding, default values are omitted
+/// and fields are written in field number order
+fn proto_encode_row(output: &mut Vec<u8>, columns: &[(u32, &SerializationVector)], row: usize) {
+    for (field, sv) in columns {
+        let field = *field as usize;
+        match sv {
+            SerializationVector::Strings(v) => {
+                let s = &v.v[row];
+                if !s.is_empty() {
+                    push_len_delimited(output, field, s.as_bytes());
+                }
+            }
+            SerializationVector::Ints(v) => {
+                let i = v.v[row];
+                if i != 0 {
+                    push_tag(output, field, WIRE_VARINT);
+                    push_varint(output, i as u64);
+                }
+            }
+            SerializationVector::Floats(v) => {
+                let f = v.v[row];
+                // negative zero is not a default value
+                if f.to_bits() != 0 {
+                    push_tag(output, field, WIRE_FIXED64);
+                    output.extend_from_slice(&f.to_le_bytes());
+                }
+            }
+            SerializationVector::Bools(v) => {
+                if v.v[row] {
+                    push_tag(output, field, WIRE_VARINT);
+                    push_varint(output, 1);
+                }
+            }
+            SerializationVector::Fkeys { sv, .. } => {
+                let ptr = sv.v[row];
+                if ptr != 0 {
+                    push_tag(output, field, WIRE_VARINT);
+                    push_varint(output, ptr as u64);
+                }
+            }
+            SerializationVector::FkeysOneToMany { sv, .. } => {
+                let ptrs = &sv.v[row];
+                if !ptrs.is_empty() {
+                    let mut packed = Vec::new();
+                    for ptr in ptrs {
+                        push_varint(&mut packed, *ptr as u64);
+                    }
+                    push_len_delimited(output, field, &packed);
+                }
+            }
+        }
+    }
+}
+
+fn proto_encode_database(tables: &[ProtoTable]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(1024);
+    let mut row_buf = Vec::new();
+
+    for t in tables {
+        for row in 0..t.table.len() {
+            row_buf.clear();
+            proto_encode_row(&mut row_buf, &t.columns, row);
+            // empty rows are still written to preserve row indexes
+            push_len_delimited(&mut output, t.number as usize, &row_buf);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+fn protobuf_outputs(source: &str) -> super::CodegenOutputs {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(source.to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    ProtobufCodegen::default().generate(&data)
+}
+
+#[cfg(test)]
+const PROTOBUF_TEST_SOURCE: &str = r#"
+TABLE thic_boi {
+  id INT,
+  name TEXT,
+  b BOOL,
+  f FLOAT,
+  fk REF some_enum,
+}
+
+TABLE some_enum {
+  name TEXT PRIMARY KEY,
+}
+
+TABLE enum_child_a {
+  inner_name_a TEXT PRIMARY KEY CHILD OF some_enum,
+}
+
+DATA thic_boi {
+  -1, hey ho, true, 1.5, warm;
+  0, here she goes, false, 0.0, hot;
+}
+
+DATA enum_child_a(name, inner_name_a) {
+  hot, barely warm;
+}
+
+DATA EXCLUSIVE some_enum {
+  warm;
+  hot;
+}
+"#;
+
+#[test]
+fn test_protobuf_schema() {
+    let outputs = protobuf_outputs(PROTOBUF_TEST_SOURCE);
+    assert_eq!(outputs.files[0].filename, "database.proto");
+    let schema = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+    pretty_assertions::assert_eq!(
+        schema,
+        r#"// EdenDB generated database
+syntax = "proto3";
+
+package edendb;
+
+message TableRowEnumChildA {
+  string inner_name_a = 1;
+  // row index in some_enum
+  uint64 parent = 2;
+}
+
+message TableRowSomeEnum {
+  string name = 1;
+  // row index in enum_child_a
+  repeated uint64 children_enum_child_a = 2;
+  // row index in thic_boi
+  repeated uint64 referrers_thic_boi__fk = 3;
+}
+
+message TableRowThicBoi {
+  int64 id = 1;
+  string name = 2;
+  bool b = 3;
+  double f = 4;
+  // row index in some_enum
+  uint64 fk = 5;
+}
+
+message Database {
+  repeated TableRowEnumChildA enum_child_a = 1;
+  repeated TableRowSomeEnum some_enum = 2;
+  repeated TableRowThicBoi thic_boi = 3;
+}
+"#
+    );
+}
+
+#[test]
+fn test_protobuf_data_encoding() {
+    let outputs = protobuf_outputs(PROTOBUF_TEST_SOURCE);
+    assert_eq!(outputs.files[1].filename, "edb_data.pb");
+
+    let mut expected: Vec<u8> = Vec::new();
+    // enum_child_a { inner_name_a: "barely warm", parent: 1 }
+    expected.extend_from_slice(&[0x0a, 15, 0x0a, 11]);
+    expected.extend_from_slice(b"barely warm");
+    expected.extend_from_slice(&[0x10, 1]);
+    // some_enum { name: "warm", referrers_thic_boi__fk: [0] }
+    expected.extend_from_slice(&[0x12, 9, 0x0a, 4]);
+    expected.extend_from_slice(b"warm");
+    expected.extend_from_slice(&[0x1a, 1, 0]);
+    // some_enum { name: "hot", children_enum_child_a: [0], referrers_thic_boi__fk: [1] }
+    expected.extend_from_slice(&[0x12, 11, 0x0a, 3]);
+    expected.extend_from_slice(b"hot");
+    expected.extend_from_slice(&[0x12, 1, 0, 0x1a, 1, 1]);
+    // thic_boi { id: -1, name: "hey ho", b: true, f: 1.5 }, fk 0 is default
+    expected.extend_from_slice(&[0x1a, 30, 0x08]);
+    expected.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+    expected.extend_from_slice(&[0x12, 6]);
+    expected.extend_from_slice(b"hey ho");
+    expected.extend_from_slice(&[0x18, 1, 0x21]);
+    expected.extend_from_slice(&1.5f64.to_le_bytes());
+    // thic_boi { name: "here she goes", fk: 1 }, rest are defaults
+    expected.extend_from_slice(&[0x1a, 17, 0x12, 13]);
+    expected.extend_from_slice(b"here she goes");
+    expected.extend_from_slice(&[0x28, 1]);
+
+    assert_eq!(outputs.files[1].content, expected);
+    assert_eq!(outputs.uncompressed_edb_data, expected);
+}
+
+#[test]
+fn test_protobuf_field_numbers_are_stable() {
+    use crate::db_parser::{self, InputSource};
+
+    let outputs = protobuf_outputs(PROTOBUF_TEST_SOURCE);
+    assert_eq!(outputs.files[2].filename, "database.proto.lock");
+    let lock: ProtoFieldNumbers = serde_json::from_slice(&outputs.files[2].content).unwrap();
+
+    // table sorting first, column added in the middle,
+    // column removed and column type changed
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE aaa_first {
+  x INT,
+}
+
+TABLE thic_boi {
+  id INT,
+  added TEXT,
+  name TEXT,
+  f INT,
+  fk REF some_enum,
+}
+
+TABLE some_enum {
+  name TEXT PRIMARY KEY,
+}
+
+TABLE enum_child_a {
+  inner_name_a TEXT PRIMARY KEY CHILD OF some_enum,
+}
+
+DATA aaa_first {
+  7;
+}
+
+DATA thic_boi {
+  1, new, hey ho, 2, hot;
+}
+
+DATA enum_child_a(name, inner_name_a) {
+  hot, barely warm;
+}
+
+DATA EXCLUSIVE some_enum {
+  warm;
+  hot;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let cgen = ProtobufCodegen { field_numbers: lock, ..Default::default() };
+    let outputs = cgen.generate(&data);
+
+    let schema = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+    pretty_assertions::assert_eq!(
+        schema,
+        r#"// EdenDB generated database
+syntax = "proto3";
+
+package edendb;
+
+message TableRowAaaFirst {
+  int64 x = 1;
+}
+
+message TableRowEnumChildA {
+  string inner_name_a = 1;
+  // row index in some_enum
+  uint64 parent = 2;
+}
+
+message TableRowSomeEnum {
+  string name = 1;
+  // row index in enum_child_a
+  repeated uint64 children_enum_child_a = 2;
+  // row index in thic_boi
+  repeated uint64 referrers_thic_boi__fk = 3;
+}
+
+message TableRowThicBoi {
+  reserved 3, 4;
+  int64 id = 1;
+  string name = 2;
+  // row index in some_enum
+  uint64 fk = 5;
+  string added = 6;
+  int64 f = 7;
+}
+
+message Database {
+  repeated TableRowEnumChildA enum_child_a = 1;
+  repeated TableRowSomeEnum some_enum = 2;
+  repeated TableRowThicBoi thic_boi = 3;
+  repeated TableRowAaaFirst aaa_first = 4;
+}
+"#
+    );
+
+    let mut expected: Vec<u8> = Vec::new();
+    // enum_child_a { inner_name_a: "barely warm", parent: 1 }
+    expected.extend_from_slice(&[0x0a, 15, 0x0a, 11]);
+    expected.extend_from_slice(b"barely warm");
+    expected.extend_from_slice(&[0x10, 1]);
+    // some_enum { name: "warm" }
+    expected.extend_from_slice(&[0x12, 6, 0x0a, 4]);
+    expected.extend_from_slice(b"warm");
+    // some_enum { name: "hot", children_enum_child_a: [0], referrers_thic_boi__fk: [0] }
+    expected.extend_from_slice(&[0x12, 11, 0x0a, 3]);
+    expected.extend_from_slice(b"hot");
+    expected.extend_from_slice(&[0x12, 1, 0, 0x1a, 1, 0]);
+    // thic_boi { id: 1, name: "hey ho", fk: 1, added: "new", f: 2 }
+    expected.extend_from_slice(&[0x1a, 19, 0x08, 1, 0x12, 6]);
+    expected.extend_from_slice(b"hey ho");
+    expected.extend_from_slice(&[0x28, 1, 0x32, 3]);
+    expected.extend_from_slice(b"new");
+    expected.extend_from_slice(&[0x38, 2]);
+    // aaa_first { x: 7 }
+    expected.extend_from_slice(&[0x22, 2, 0x08, 7]);
+
+    assert_eq!(outputs.files[1].content, expected);
+}
+
+#[test]
+fn test_protobuf_removed_table_number_is_reserved() {
+    let outputs = protobuf_outputs(PROTOBUF_TEST_SOURCE);
+    let lock: ProtoFieldNumbers = serde_json::from_slice(&outputs.files[2].content).unwrap();
+
+    let mut message = lock.tables.clone();
+    let numbers = message.assign(&[
+        ("some_enum", "TableRowSomeEnum".to_string()),
+        ("thic_boi", "TableRowThicBoi".to_string()),
+        ("zzz", "TableRowZzz".to_string()),
+    ]);
+    assert_eq!(numbers, vec![2, 3, 4]);
+    assert_eq!(message.reserved.iter().copied().collect::<Vec<_>>(), vec![1]);
+}
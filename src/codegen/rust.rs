@@ -16,6 +16,13 @@ pub struct RustCodegen {
     pub db_source_file_name: String,
     // for testing, undocumented
     pub expose_deserialization_function: bool,
+    /// Emit aligned uncompressed data which is read without copying,
+    /// see `codegen::dump_as_bytes_zero_copy`
+    pub zero_copy_data: bool,
+    /// Emit `mod.rs` with database and a module file per table
+    /// instead of single `db_source_file_name` file,
+    /// output directory is owned by the module tree, see `CodegenOutputs::remove_stale_files`
+    pub split_table_modules: bool,
 }
 
 impl Default for RustCodegen {
@@ -25,6 +32,8 @@ impl Default for RustCodegen {
             edb_data_file_name: "edb_data.bin".to_string(),
             db_source_file_name: "database.rs".to_string(),
             expose_deserialization_function: false,
+            zero_copy_data: false,
+            split_table_modules: false,
         }
     }
 }
@@ -34,13 +43,37 @@ impl CodeGenerator for RustCodegen {
         let mut content = String::new();
         let comp = RustCodegenCompute::new(data, self);
 
-        content += r#"// Test db content
+        if self.zero_copy_data {
+            content += r#"// Test db content
+#[cfg(not(all(target_endian = "little", target_pointer_width = "64")))]
+compile_error!("EdenDB zero copy data can only be read on 64 bit little endian targets");
+
+#[repr(C, align(8))]
+struct EdbAligned<T: ?Sized>(T);
+
+static DB_BYTES: &EdbAligned<[u8]> = &EdbAligned(*include_bytes!("edb_data.bin"));
+lazy_static!{
+    pub static ref DB: Database = Database::from_bytes(&DB_BYTES.0).unwrap();
+}
+"#;
+            content += "\n";
+
+            content += "// Zero copy data reader\n";
+            content += zero_copy_reader_library();
+            content += "\n";
+        } else {
+            content += r#"// Test db content
 const DB_BYTES: &[u8] = include_bytes!("edb_data.bin");
 lazy_static!{
     pub static ref DB: Database = Database::deserialize_compressed(DB_BYTES).unwrap();
 }
 "#;
-        content += "\n";
+            content += "\n";
+        }
+
+        if self.split_table_modules {
+            return self.generate_table_modules(data, content, comp);
+        }
 
         content += "// Table row pointer types\n";
         for trow_pointer in &comp.table_pointer_types {
@@ -98,6 +131,74 @@ lazy_static!{
     }
 }
 
+impl RustCodegen {
+    fn generate_table_modules(
+        &self,
+        data: &AllData,
+        mut content: String,
+        comp: RustCodegenCompute,
+    ) -> super::CodegenOutputs {
+        let mut files = Vec::with_capacity(data.tables.len() + 2);
+
+        content += "// Table modules\n";
+        for t in data.tables_sorted() {
+            content += &format!("mod {};\n", t.name.as_str());
+            content += &format!("pub use {}::*;\n", t.name.as_str());
+        }
+        content += "\n";
+
+        content += "// Database definition\n";
+        content += &comp.database_definition;
+        content += "\n";
+
+        content += "// Database implementation\n";
+        content += &comp.database_impl;
+
+        files.push(CodegenOutputFile {
+            filename: "mod.rs".to_string(),
+            content: content.into_bytes(),
+        });
+
+        // every vector is ordered by sorted tables
+        for (idx, t) in data.tables_sorted().iter().enumerate() {
+            let mut table_content = String::new();
+            table_content += "use super::*;\n";
+            table_content += "\n";
+
+            table_content += "// Table row pointer type\n";
+            table_content += &comp.table_pointer_types[idx];
+            table_content += "\n\n";
+
+            table_content += "// Table struct type\n";
+            table_content += &comp.table_structs[idx];
+            table_content += "\n\n";
+
+            table_content += "// Table definition\n";
+            table_content += &comp.table_definitions[idx];
+            table_content += "\n\n";
+
+            table_content += "// Table definition implementation\n";
+            table_content += &comp.table_definition_impls[idx];
+            table_content += "\n";
+
+            files.push(CodegenOutputFile {
+                filename: format!("{}.rs", t.name.as_str()),
+                content: table_content.into_bytes(),
+            });
+        }
+
+        files.push(CodegenOutputFile {
+            filename: self.edb_data_file_name.clone(),
+            content: comp.data_bytes,
+        });
+
+        super::CodegenOutputs {
+            uncompressed_edb_data: comp.uncompressed_data_bytes,
+            files,
+        }
+    }
+}
+
 struct RustCodegenCompute {
     table_pointer_types: Vec<String>,
     table_structs: Vec<String>,
@@ -112,13 +213,21 @@ struct RustCodegenCompute {
 impl RustCodegenCompute {
     fn new(data: &AllData, opt: &RustCodegen) -> RustCodegenCompute {
         let vecs = data.serialization_vectors();
-        let table_pointer_types = table_pointer_types(data);
-        let table_structs = table_structs(data, &vecs);
-        let table_definitions = table_definitions(data, &vecs);
+        let zero_copy = opt.zero_copy_data;
+        let table_pointer_types = table_pointer_types(data, zero_copy);
+        let table_structs = table_structs(data, &vecs, zero_copy);
+        // database in parent module constructs table definitions
+        let field_visibility = if opt.split_table_modules { "pub(super) " } else { "" };
+        let table_definitions = table_definitions(data, &vecs, zero_copy, field_visibility);
         let database_definition = database_definition(data);
         let database_impl = database_impl(data, opt, &vecs);
-        let table_definition_impls = table_definition_impls(data, &vecs);
-        let (data_bytes, uncompressed_data_bytes) = super::dump_as_bytes_lz4_checksum_xxh(&vecs);
+        let table_definition_impls = table_definition_impls(data, &vecs, zero_copy);
+        let (data_bytes, uncompressed_data_bytes) = if zero_copy {
+            let bytes = super::dump_as_bytes_zero_copy(&vecs);
+            (bytes.clone(), bytes)
+        } else {
+            super::dump_as_bytes_lz4_checksum_xxh(&vecs)
+        };
         RustCodegenCompute {
             table_pointer_types,
             table_structs,
@@ -132,29 +241,162 @@ impl RustCodegenCompute {
     }
 }
 
-fn table_pointer_types(data: &AllData) -> Vec<String> {
+fn zero_copy_reader_library() -> &'static str {
+    r#"struct EdbStrColumn {
+    offsets: &'static [u64],
+    bytes: &'static str,
+}
+
+impl EdbStrColumn {
+    fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn get(&self, idx: usize) -> &'static str {
+        &self.bytes[self.offsets[idx] as usize..self.offsets[idx + 1] as usize]
+    }
+}
+
+struct EdbNestedColumn<T: 'static> {
+    offsets: &'static [u64],
+    values: &'static [T],
+}
+
+impl<T> EdbNestedColumn<T> {
+    fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn get(&self, idx: usize) -> &'static [T] {
+        &self.values[self.offsets[idx] as usize..self.offsets[idx + 1] as usize]
+    }
+}
+
+struct EdbReader {
+    input: &'static [u8],
+    pos: usize,
+}
+
+impl EdbReader {
+    fn u64(&mut self) -> Result<u64, Box<dyn ::std::error::Error>> {
+        let bytes = self.input.get(self.pos..self.pos + 8).ok_or("EdenDB data is truncated")?;
+        self.pos += 8;
+        Ok(u64::from_le_bytes(bytes.try_into()?))
+    }
+
+    fn count(&mut self) -> Result<usize, Box<dyn ::std::error::Error>> {
+        Ok(usize::try_from(self.u64()?)?)
+    }
+
+    /// Safety: every bit pattern must be valid for T and its alignment must be at most 8 bytes
+    unsafe fn slice<T>(&mut self, count: usize) -> Result<&'static [T], Box<dyn ::std::error::Error>> {
+        let size = count.checked_mul(::std::mem::size_of::<T>()).ok_or("EdenDB data is corrupted")?;
+        let end = self.pos.checked_add(size).ok_or("EdenDB data is corrupted")?;
+        let bytes = self.input.get(self.pos..end).ok_or("EdenDB data is truncated")?;
+        // every section is padded to 8 bytes
+        self.pos = (end + 7) & !7;
+        Ok(::std::slice::from_raw_parts(bytes.as_ptr() as *const T, count))
+    }
+
+    /// Safety: same as for slice
+    unsafe fn fixed<T>(&mut self) -> Result<&'static [T], Box<dyn ::std::error::Error>> {
+        let count = self.count()?;
+        self.slice(count)
+    }
+
+    fn bools(&mut self) -> Result<&'static [bool], Box<dyn ::std::error::Error>> {
+        let bytes: &'static [u8] = unsafe { self.fixed()? };
+        if bytes.iter().any(|b| *b > 1) {
+            return Err("EdenDB data is corrupted, invalid bool value".into());
+        }
+        Ok(unsafe { ::std::slice::from_raw_parts(bytes.as_ptr() as *const bool, bytes.len()) })
+    }
+
+    fn offsets(&mut self, count: usize) -> Result<&'static [u64], Box<dyn ::std::error::Error>> {
+        let count = count.checked_add(1).ok_or("EdenDB data is corrupted")?;
+        let offsets: &'static [u64] = unsafe { self.slice(count)? };
+        if offsets[0] != 0 || offsets.windows(2).any(|w| w[0] > w[1]) {
+            return Err("EdenDB data is corrupted, invalid offsets".into());
+        }
+        Ok(offsets)
+    }
+
+    fn strings(&mut self) -> Result<EdbStrColumn, Box<dyn ::std::error::Error>> {
+        let count = self.count()?;
+        let offsets = self.offsets(count)?;
+        let bytes: &'static [u8] = unsafe { self.slice(usize::try_from(offsets[count])?)? };
+        let bytes = ::std::str::from_utf8(bytes)?;
+        if offsets.iter().any(|o| !bytes.is_char_boundary(*o as usize)) {
+            return Err("EdenDB data is corrupted, invalid string offsets".into());
+        }
+        Ok(EdbStrColumn { offsets, bytes })
+    }
+
+    /// Safety: same as for slice
+    unsafe fn nested<T>(&mut self) -> Result<EdbNestedColumn<T>, Box<dyn ::std::error::Error>> {
+        let count = self.count()?;
+        let offsets = self.offsets(count)?;
+        let values = self.slice(usize::try_from(offsets[count])?)?;
+        Ok(EdbNestedColumn { offsets, values })
+    }
+}
+
+/// Safety: T must be a table row pointer read from u64 column
+unsafe fn edb_check_pointers<T>(values: &[T], len: usize) -> Result<(), Box<dyn ::std::error::Error>> {
+    let raw = ::std::slice::from_raw_parts(values.as_ptr() as *const u64, values.len());
+    if raw.iter().any(|p| *p >= len as u64) {
+        return Err("EdenDB data is corrupted, pointer out of range".into());
+    }
+    Ok(())
+}
+"#
+}
+
+fn table_pointer_types(data: &AllData, zero_copy: bool) -> Vec<String> {
     let mut res = Vec::with_capacity(data.tables.len());
     for t in data.tables_sorted() {
         let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
         let mut output = String::new();
-        output += "#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, ::std::hash::Hash, serde::Deserialize)]\n";
+        if zero_copy {
+            // pointers are read directly from u64 columns
+            output += "#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, ::std::hash::Hash)]\n";
+            output += "#[repr(transparent)]\n";
+        } else {
+            output += "#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, ::std::hash::Hash, serde::Deserialize)]\n";
+        }
         output += &format!("pub struct TableRowPointer{}(usize);", tname_pasc_case);
+        for (idx, constant) in t.row_constant_names().iter().enumerate() {
+            output += &format!(
+                "\npub const {}: TableRowPointer{} = TableRowPointer{}({});",
+                constant.to_uppercase(),
+                tname_pasc_case,
+                tname_pasc_case,
+                idx
+            );
+        }
         res.push(output);
     }
     res
 }
 
-fn table_structs(data: &AllData, vecs: &Vec<SerializationVector>) -> Vec<String> {
+fn table_structs(data: &AllData, vecs: &Vec<SerializationVector>, zero_copy: bool) -> Vec<String> {
     let mut res = Vec::with_capacity(data.tables.len());
     for t in data.tables_sorted() {
         let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
         let mut output = String::new();
-        output += "#[derive(Debug)]\n";
+        if zero_copy {
+            output += "#[derive(Debug, Clone, Copy)]\n";
+        } else {
+            output += "#[derive(Debug)]\n";
+        }
         output += &format!("pub struct TableRow{} {{\n", tname_pasc_case);
 
         for sv in vecs {
             if sv.table_name() == t.name.as_str() {
                 let (cname, ctype) = match sv {
+                    SerializationVector::Strings(sv) if zero_copy => {
+                        (&sv.column_name, "&'static str".to_string())
+                    }
                     SerializationVector::Strings(sv) => {
                         (&sv.column_name, "::std::string::String".to_string())
                     }
@@ -168,6 +410,13 @@ fn table_structs(data: &AllData, vecs: &Vec<SerializationVector>) -> Vec<String>
                             foreign_table.as_str().to_case(Case::Pascal)
                         ),
                     ),
+                    SerializationVector::FkeysOneToMany { sv, foreign_table } if zero_copy => (
+                        &sv.column_name,
+                        format!(
+                            "&'static [TableRowPointer{}]",
+                            foreign_table.as_str().to_case(Case::Pascal)
+                        ),
+                    ),
                     SerializationVector::FkeysOneToMany { sv, foreign_table } => (
                         &sv.column_name,
                         format!(
@@ -191,17 +440,46 @@ fn table_structs(data: &AllData, vecs: &Vec<SerializationVector>) -> Vec<String>
     res
 }
 
-fn table_definitions(data: &AllData, vecs: &Vec<SerializationVector>) -> Vec<String> {
+fn table_definitions(
+    data: &AllData,
+    vecs: &Vec<SerializationVector>,
+    zero_copy: bool,
+    field_visibility: &str,
+) -> Vec<String> {
     let mut res = Vec::with_capacity(data.tables.len());
     for t in data.tables_sorted() {
         let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
         let mut output = String::new();
         output += &format!("pub struct TableDefinition{} {{\n", tname_pasc_case);
-        output += &format!("    rows: Vec<TableRow{}>,\n", tname_pasc_case);
+        if zero_copy {
+            output += &format!("    {}len: usize,\n", field_visibility);
+        } else {
+            output += &format!("    {}rows: Vec<TableRow{}>,\n", field_visibility, tname_pasc_case);
+        }
 
         for sv in vecs {
             if sv.table_name() == t.name.as_str() {
                 let (cname, ctype) = match sv {
+                    SerializationVector::Strings(sv) if zero_copy => {
+                        (&sv.column_name, "EdbStrColumn".to_string())
+                    }
+                    SerializationVector::Ints(sv) if zero_copy => (&sv.column_name, "&'static [i64]".to_string()),
+                    SerializationVector::Floats(sv) if zero_copy => (&sv.column_name, "&'static [f64]".to_string()),
+                    SerializationVector::Bools(sv) if zero_copy => (&sv.column_name, "&'static [bool]".to_string()),
+                    SerializationVector::Fkeys { sv, foreign_table } if zero_copy => (
+                        &sv.column_name,
+                        format!(
+                            "&'static [TableRowPointer{}]",
+                            foreign_table.as_str().to_case(Case::Pascal)
+                        ),
+                    ),
+                    SerializationVector::FkeysOneToMany { sv, foreign_table } if zero_copy => (
+                        &sv.column_name,
+                        format!(
+                            "EdbNestedColumn<TableRowPointer{}>",
+                            foreign_table.as_str().to_case(Case::Pascal)
+                        ),
+                    ),
                     SerializationVector::Strings(sv) => {
                         (&sv.column_name, "Vec<::std::string::String>".to_string())
                     }
@@ -224,7 +502,9 @@ fn table_definitions(data: &AllData, vecs: &Vec<SerializationVector>) -> Vec<Str
                     ),
                 };
 
-                output += "    c_";
+                output += "    ";
+                output += field_visibility;
+                output += "c_";
                 output += cname;
                 output += ": ";
                 output += &ctype;
@@ -278,7 +558,11 @@ fn database_impl(data: &AllData, opt: &RustCodegen, vecs: &[SerializationVector]
     }
 
     // database deserialization function
-    database_deserialization_function(&mut res, data, vecs, opt.expose_deserialization_function);
+    if opt.zero_copy_data {
+        database_zero_copy_function(&mut res, data, vecs);
+    } else {
+        database_deserialization_function(&mut res, data, vecs, opt.expose_deserialization_function);
+    }
     if opt.debug_dump_function {
         database_dump_function(&mut res, data);
     }
@@ -538,7 +822,147 @@ fn database_deserialization_function(
     output.push_str("    }\n");
 }
 
-fn table_definition_impls(data: &AllData, vecs: &Vec<SerializationVector>) -> Vec<String> {
+fn database_zero_copy_function(output: &mut String, data: &AllData, vecs: &[SerializationVector]) {
+    output.push_str("    /// Reads database from 8 byte aligned zero copy data without copying,\n");
+    output.push_str("    /// data can come from aligned `include_bytes!` or a memory mapped file\n");
+    output.push_str("    pub fn from_bytes(input: &'static [u8]) -> Result<Database, Box<dyn ::std::error::Error>> {\n");
+    output.push_str("        if input.as_ptr() as usize % 8 != 0 {\n");
+    output.push_str("            return Err(\"EdenDB data must be 8 byte aligned\".into());\n");
+    output.push_str("        }\n");
+    output.push_str("        let hash_size = ::std::mem::size_of::<u64>();\n");
+    output.push_str("        if input.len() < 16 + hash_size {\n");
+    output.push_str("            return Err(\"EdenDB data is truncated\".into());\n");
+    output.push_str("        }\n");
+    output.push_str("        let data_end = input.len() - hash_size;\n");
+    output.push_str("        let encoded_hash = u64::from_le_bytes(input[data_end..].try_into()?);\n");
+    output.push_str("        let computed_hash = ::xxhash_rust::xxh3::xxh3_64(&input[0..data_end]);\n");
+    output.push_str("        if encoded_hash != computed_hash {\n");
+    output.push_str("            return Err(\"EdenDB data is corrupted, checksum mismatch.\".into());\n");
+    output.push_str("        }\n");
+    output.push_str("        if &input[0..8] != b\"EDBZCOPY\" {\n");
+    output.push_str("            return Err(\"EdenDB data is not in zero copy format\".into());\n");
+    output.push_str("        }\n");
+    output.push_str("        let mut reader = EdbReader { input: &input[0..data_end], pos: 8 };\n");
+    output.push_str(&format!("        if reader.u64()? != {} {{\n", vecs.len()));
+    output.push_str("            return Err(\"EdenDB data has unexpected column count\".into());\n");
+    output.push_str("        }\n");
+    output.push('\n');
+
+    struct ColumnVar<'a> {
+        cvar: String,
+        row_var: String,
+        table_name: &'a str,
+    }
+
+    let mut column_vars: Vec<ColumnVar> = Vec::new();
+    for sv in vecs {
+        let cvar = format!("{}_{}", sv.table_name(), sv.column_name());
+        let read_expr = match sv {
+            SerializationVector::Strings(_) => "reader.strings()?".to_string(),
+            SerializationVector::Ints(_) => "unsafe { reader.fixed::<i64>()? }".to_string(),
+            SerializationVector::Floats(_) => "unsafe { reader.fixed::<f64>()? }".to_string(),
+            SerializationVector::Bools(_) => "reader.bools()?".to_string(),
+            SerializationVector::Fkeys { foreign_table, .. } => format!(
+                "unsafe {{ reader.fixed::<TableRowPointer{}>()? }}",
+                foreign_table.to_case(Case::Pascal)
+            ),
+            SerializationVector::FkeysOneToMany { foreign_table, .. } => format!(
+                "unsafe {{ reader.nested::<TableRowPointer{}>()? }}",
+                foreign_table.to_case(Case::Pascal)
+            ),
+        };
+
+        output.push_str("        let ");
+        output.push_str(&cvar);
+        output.push_str(" = ");
+        output.push_str(&read_expr);
+        output.push_str(";\n");
+        column_vars.push(ColumnVar {
+            cvar,
+            row_var: sv.column_name().to_string(),
+            table_name: sv.table_name(),
+        });
+
+        if sv.last_for_table() {
+            let last_var = column_vars.last().unwrap();
+            let tlen_var = format!("{}_len", last_var.table_name);
+            output.push('\n');
+            output.push_str("        let ");
+            output.push_str(&tlen_var);
+            output.push_str(" = ");
+            output.push_str(&last_var.cvar);
+            output.push_str(".len();\n");
+
+            for i in &column_vars {
+                if i.table_name == last_var.table_name && last_var.cvar != i.cvar {
+                    output.push_str("        if ");
+                    output.push_str(&i.cvar);
+                    output.push_str(".len() != ");
+                    output.push_str(&tlen_var);
+                    output.push_str(" {\n");
+                    output.push_str("            return Err(\"EdenDB data is corrupted, column length mismatch\".into());\n");
+                    output.push_str("        }\n");
+                }
+            }
+
+            output.push('\n');
+        }
+    }
+
+    for sv in vecs {
+        let (values, foreign_table) = match sv {
+            SerializationVector::Fkeys { foreign_table, .. } => {
+                (format!("{}_{}", sv.table_name(), sv.column_name()), foreign_table)
+            }
+            SerializationVector::FkeysOneToMany { foreign_table, .. } => {
+                (format!("{}_{}.values", sv.table_name(), sv.column_name()), foreign_table)
+            }
+            _ => continue,
+        };
+        output.push_str(&format!(
+            "        unsafe {{ edb_check_pointers({}, {}_len)? }};\n",
+            values, foreign_table
+        ));
+    }
+    output.push('\n');
+
+    output.push_str("        if reader.pos != data_end {\n");
+    output.push_str("            return Err(\"EdenDB data has unexpected trailing bytes\".into());\n");
+    output.push_str("        }\n");
+    output.push('\n');
+    output.push_str("        Ok(Database {\n");
+
+    for t in data.tables_sorted() {
+        let tname_pascal = t.name.as_str().to_case(Case::Pascal);
+        output.push_str("            ");
+        output.push_str(t.name.as_str());
+        output.push_str(": TableDefinition");
+        output.push_str(&tname_pascal);
+        output.push_str(" {\n");
+
+        output.push_str("                len: ");
+        output.push_str(t.name.as_str());
+        output.push_str("_len,\n");
+
+        for v in &column_vars {
+            if v.table_name == t.name.as_str() {
+                output.push_str("                c_");
+                output.push_str(&v.row_var);
+                output.push_str(": ");
+                output.push_str(&v.cvar);
+                output.push_str(",\n");
+            }
+        }
+
+        output.push_str("            },\n");
+    }
+
+    output.push_str("        })\n");
+    output.push_str("    }\n");
+    output.push('\n');
+}
+
+fn table_definition_impls(data: &AllData, vecs: &Vec<SerializationVector>, zero_copy: bool) -> Vec<String> {
     let mut res = Vec::with_capacity(data.tables.len());
     for t in data.tables_sorted() {
         let tname_pasc_case = t.name.as_str().to_case(Case::Pascal);
@@ -552,8 +976,11 @@ fn table_definition_impls(data: &AllData, vecs: &Vec<SerializationVector>) -> Ve
         // pub fn len(&self) -> usize {
         //     self.rows.len()
         // }
+        let len_expr = if zero_copy { "self.len" } else { "self.rows.len()" };
         output += "    pub fn len(&self) -> usize {\n";
-        output += "        self.rows.len()\n";
+        output += "        ";
+        output += len_expr;
+        output += "\n";
         output += "    }\n";
         output += "\n";
 
@@ -568,7 +995,9 @@ fn table_definition_impls(data: &AllData, vecs: &Vec<SerializationVector>) -> Ve
         output += &trow_ptr;
         output += "> {\n";
 
-        output += "        (0..self.rows.len()).map(|idx| {\n";
+        output += "        (0..";
+        output += len_expr;
+        output += ").map(|idx| {\n";
         output += "            ";
         output += &trow_ptr;
         output += "(idx)\n";
@@ -581,14 +1010,38 @@ fn table_definition_impls(data: &AllData, vecs: &Vec<SerializationVector>) -> Ve
         //     &self.rows[ptr.0]
         // }
 
-        output += "    pub fn row(&self, ptr: ";
-        output += &trow_ptr;
-        output += ") -> &";
-        output += &trow_def;
-        output += " {\n";
-        output += "        &self.rows[ptr.0]\n";
-        output += "    }\n";
-        output += "\n";
+        if zero_copy {
+            // rows are not stored in zero copy mode, assemble row from columns
+            output += "    pub fn row(&self, ptr: ";
+            output += &trow_ptr;
+            output += ") -> ";
+            output += &trow_def;
+            output += " {\n";
+            output += "        ";
+            output += &trow_def;
+            output += " {\n";
+            for sv in vecs {
+                if sv.table_name() == t.name.as_str() {
+                    output += "            ";
+                    output += sv.column_name();
+                    output += ": self.c_";
+                    output += sv.column_name();
+                    output += "(ptr),\n";
+                }
+            }
+            output += "        }\n";
+            output += "    }\n";
+            output += "\n";
+        } else {
+            output += "    pub fn row(&self, ptr: ";
+            output += &trow_ptr;
+            output += ") -> &";
+            output += &trow_def;
+            output += " {\n";
+            output += "        &self.rows[ptr.0]\n";
+            output += "    }\n";
+            output += "\n";
+        }
 
         struct ColumnVar {
             row_var: String,
@@ -596,6 +1049,48 @@ fn table_definition_impls(data: &AllData, vecs: &Vec<SerializationVector>) -> Ve
             return_ref: bool,
         }
 
+        if zero_copy {
+            for sv in vecs {
+                if sv.table_name() == t.name.as_str() {
+                    let (raw_column_type, getter) = match sv {
+                        SerializationVector::Strings(_) => ("&'static str".to_string(), true),
+                        SerializationVector::Ints(_) => ("i64".to_string(), false),
+                        SerializationVector::Floats(_) => ("f64".to_string(), false),
+                        SerializationVector::Bools(_) => ("bool".to_string(), false),
+                        SerializationVector::Fkeys { foreign_table, .. } => (
+                            format!("TableRowPointer{}", foreign_table.to_case(Case::Pascal)),
+                            false,
+                        ),
+                        SerializationVector::FkeysOneToMany { foreign_table, .. } => (
+                            format!("&'static [TableRowPointer{}]", foreign_table.to_case(Case::Pascal)),
+                            true,
+                        ),
+                    };
+
+                    output += "    pub fn c_";
+                    output += sv.column_name();
+                    output += "(&self, ptr: ";
+                    output += &trow_ptr;
+                    output += ") -> ";
+                    output += &raw_column_type;
+                    output += " {\n";
+                    output += "        self.c_";
+                    output += sv.column_name();
+                    if getter {
+                        output += ".get(ptr.0)\n";
+                    } else {
+                        output += "[ptr.0]\n";
+                    }
+                    output += "    }\n";
+                    output += "\n";
+                }
+            }
+
+            output += "}";
+            res.push(output);
+            continue;
+        }
+
         for sv in vecs {
             if sv.table_name() == t.name.as_str() {
                 let cv = match sv {
@@ -703,7 +1198,7 @@ fn main() {
 }
 
 #[cfg(test)]
-fn assert_rust_db_compiled_dump_equals(source: &str, output_dump: &str) {
+fn assert_rust_db_compiled_dump_equals(source: &str, output_dump: &str, mut gen: RustCodegen) {
     let tmp_dir = crate::checker::tests::common::random_test_dir();
     let src_dir = init_cargo_project(&tmp_dir);
     let inputs = &mut [InputSource {
@@ -716,10 +1211,15 @@ fn assert_rust_db_compiled_dump_equals(source: &str, output_dump: &str) {
     let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
     let data = AllData::new(sources).unwrap();
 
-    let mut gen = RustCodegen::default();
     gen.debug_dump_function = true;
     let codegen_outputs = gen.generate(&data);
-    codegen_outputs.dump_to_dir(src_dir.to_str().unwrap());
+    if gen.split_table_modules {
+        let module_dir = src_dir.join("database");
+        std::fs::create_dir(&module_dir).unwrap();
+        codegen_outputs.dump_to_dir(module_dir.to_str().unwrap());
+    } else {
+        codegen_outputs.dump_to_dir(src_dir.to_str().unwrap());
+    }
 
     let output = Command::new("cargo")
         .stdout(Stdio::piped())
@@ -735,10 +1235,8 @@ fn assert_rust_db_compiled_dump_equals(source: &str, output_dump: &str) {
     pretty_assertions::assert_eq!(out_res, output_dump);
 }
 
-#[test]
-#[ignore]
-fn test_rust_codegen_integration() {
-    let source = r#"
+#[cfg(test)]
+const INTEGRATION_TEST_SOURCE: &str = r#"
 TABLE thic_boi {
   id INT,
   name TEXT,
@@ -780,7 +1278,9 @@ DATA EXCLUSIVE some_enum {
   hot;
 }
 "#;
-    let output_dump = r#"TABLE: enum_child_a <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+
+#[cfg(test)]
+const INTEGRATION_TEST_DUMP: &str = r#"TABLE: enum_child_a <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
 TableRowEnumChildA { inner_name_a: "barely warm", parent: TableRowPointerSomeEnum(0) }
 TableRowEnumChildA { inner_name_a: "medium warm", parent: TableRowPointerSomeEnum(0) }
 TABLE: enum_child_b <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
@@ -795,5 +1295,339 @@ TableRowThicBoi { id: 2, name: "here she goes", b: false, f: 3.21, fk: TableRowP
 TableRowThicBoi { id: 3, name: "either blah", b: true, f: 5.43, fk: TableRowPointerSomeEnum(1) }
 "#;
 
-    assert_rust_db_compiled_dump_equals(source, output_dump);
+#[test]
+#[ignore]
+fn test_rust_codegen_integration() {
+    assert_rust_db_compiled_dump_equals(INTEGRATION_TEST_SOURCE, INTEGRATION_TEST_DUMP, RustCodegen::default());
+}
+
+#[test]
+#[ignore]
+fn test_rust_codegen_zero_copy_integration() {
+    let gen = RustCodegen {
+        zero_copy_data: true,
+        ..Default::default()
+    };
+    assert_rust_db_compiled_dump_equals(INTEGRATION_TEST_SOURCE, INTEGRATION_TEST_DUMP, gen);
+}
+
+#[test]
+#[ignore]
+fn test_rust_codegen_zero_copy_rejects_corrupted_pointers() {
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    let src_dir = init_cargo_project(&tmp_dir);
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+}
+
+TABLE disk {
+  id INT PRIMARY KEY,
+  server REF server,
+}
+
+DATA server {
+  alpha;
+}
+
+DATA disk {
+  1, alpha;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let gen = RustCodegen {
+        zero_copy_data: true,
+        ..Default::default()
+    };
+    let outputs = gen.generate(&data);
+    outputs.dump_to_dir(src_dir.to_str().unwrap());
+
+    let content = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+    assert!(content.contains("        unsafe { edb_check_pointers(disk_server, server_len)? };\n"));
+    assert!(content.contains(
+        "        unsafe { edb_check_pointers(server_referrers_disk__server.values, disk_len)? };\n"
+    ));
+
+    // last value is the only disk pointer of server.referrers_disk__server
+    let valid = outputs.uncompressed_edb_data.clone();
+    let data_end = valid.len() - 8;
+    let mut bad_pointer = valid[0..data_end].to_vec();
+    assert_eq!(bad_pointer[data_end - 8..], 0u64.to_le_bytes());
+    bad_pointer[data_end - 8..].copy_from_slice(&7u64.to_le_bytes());
+    let hash = xxhash_rust::xxh3::xxh3_64(&bad_pointer);
+    bad_pointer.extend_from_slice(&hash.to_le_bytes());
+    std::fs::write(tmp_dir.join("bad_pointer.bin"), &bad_pointer).unwrap();
+    std::fs::copy(src_dir.join("edb_data.bin"), tmp_dir.join("valid.bin")).unwrap();
+
+    let main_rs_contents = r#"
+#[macro_use]
+extern crate lazy_static;
+
+mod database;
+
+fn main() {
+    for path in std::env::args().skip(1) {
+        let bytes = std::fs::read(&path).unwrap();
+        // from_bytes needs 8 byte aligned static data
+        let words: &'static mut [u64] =
+            Box::leak(vec![0u64; (bytes.len() + 7) / 8].into_boxed_slice());
+        let input: &'static mut [u8] =
+            unsafe { std::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, bytes.len()) };
+        input.copy_from_slice(&bytes);
+        match database::Database::from_bytes(input) {
+            Ok(_) => println!("ok"),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+"#;
+    std::fs::write(src_dir.join("main.rs"), main_rs_contents).unwrap();
+
+    let output = Command::new("cargo")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(&tmp_dir)
+        .args(["run", "--", "valid.bin", "bad_pointer.bin"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let out_res = String::from_utf8(output.stdout).unwrap();
+    pretty_assertions::assert_eq!(out_res, "ok\nEdenDB data is corrupted, pointer out of range\n");
+}
+
+#[test]
+#[ignore]
+fn test_rust_codegen_split_table_modules_integration() {
+    let gen = RustCodegen {
+        split_table_modules: true,
+        ..Default::default()
+    };
+    assert_rust_db_compiled_dump_equals(INTEGRATION_TEST_SOURCE, INTEGRATION_TEST_DUMP, gen);
+}
+
+#[test]
+#[ignore]
+fn test_rust_codegen_split_table_modules_zero_copy_integration() {
+    let gen = RustCodegen {
+        split_table_modules: true,
+        zero_copy_data: true,
+        ..Default::default()
+    };
+    assert_rust_db_compiled_dump_equals(INTEGRATION_TEST_SOURCE, INTEGRATION_TEST_DUMP, gen);
+}
+
+#[test]
+#[ignore]
+fn test_rust_codegen_row_constants_integration() {
+    let source =
+        INTEGRATION_TEST_SOURCE.replace("TABLE some_enum {", "TABLE some_enum CONSTANTS {");
+    assert_rust_db_compiled_dump_equals(&source, INTEGRATION_TEST_DUMP, RustCodegen::default());
+}
+
+#[test]
+fn test_rust_codegen_row_constants() {
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE disk_manufacturer CONSTANTS {
+  model TEXT PRIMARY KEY,
+}
+
+DATA disk_manufacturer {
+  intel;
+  western-digital;
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let outputs = RustCodegen::default().generate(&data);
+    let content = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+
+    assert!(content.contains("pub const DISK_MANUFACTURER_INTEL: TableRowPointerDiskManufacturer = TableRowPointerDiskManufacturer(0);\n"));
+    assert!(content.contains("pub const DISK_MANUFACTURER_WESTERN_DIGITAL: TableRowPointerDiskManufacturer = TableRowPointerDiskManufacturer(1);\n"));
+}
+
+#[test]
+fn test_rust_codegen_split_table_modules_files() {
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(INTEGRATION_TEST_SOURCE.to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let gen = RustCodegen {
+        split_table_modules: true,
+        ..Default::default()
+    };
+    let outputs = gen.generate(&data);
+    let file_names: Vec<_> = outputs.files.iter().map(|i| i.filename.as_str()).collect();
+    assert_eq!(
+        file_names,
+        vec![
+            "mod.rs",
+            "enum_child_a.rs",
+            "enum_child_b.rs",
+            "some_enum.rs",
+            "thic_boi.rs",
+            "edb_data.bin"
+        ]
+    );
+
+    let mod_rs = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+    assert!(mod_rs.contains("mod thic_boi;\npub use thic_boi::*;\n"));
+    assert!(mod_rs.contains("pub struct Database {\n"));
+    assert!(!mod_rs.contains("pub struct TableRowThicBoi"));
+
+    let thic_boi_rs = String::from_utf8(outputs.files[4].content.clone()).unwrap();
+    assert!(thic_boi_rs.starts_with("use super::*;\n"));
+    assert!(thic_boi_rs.contains("pub struct TableRowPointerThicBoi(usize);"));
+    assert!(thic_boi_rs.contains("pub struct TableRowThicBoi {\n"));
+    assert!(thic_boi_rs.contains("    pub(super) c_fk: Vec<TableRowPointerSomeEnum>,\n"));
+    assert!(thic_boi_rs.contains("impl TableDefinitionThicBoi {\n"));
+}
+
+#[test]
+fn test_rust_codegen_split_table_modules_remove_stale_files() {
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(INTEGRATION_TEST_SOURCE.to_string()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let gen = RustCodegen {
+        split_table_modules: true,
+        ..Default::default()
+    };
+    let outputs = gen.generate(&data);
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    std::fs::write(tmp_dir.join("removed_table.rs"), "use super::*;\n").unwrap();
+    std::fs::write(tmp_dir.join("notes.txt"), "keep me").unwrap();
+    outputs.dump_to_dir(tmp_dir.to_str().unwrap());
+    outputs.remove_stale_files(tmp_dir.to_str().unwrap(), "rs");
+
+    let mut file_names: Vec<_> = std::fs::read_dir(&tmp_dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    file_names.sort();
+    assert_eq!(
+        file_names,
+        vec![
+            "edb_data.bin",
+            "enum_child_a.rs",
+            "enum_child_b.rs",
+            "mod.rs",
+            "notes.txt",
+            "some_enum.rs",
+            "thic_boi.rs",
+        ]
+    );
+}
+
+#[test]
+fn test_rust_codegen_mat_view_foreign_keys() {
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  ram_mb INT,
+}
+
+DATA server {
+  epyc-1, 4096;
+  epyc-2, 8192;
+}
+
+MATERIALIZED VIEW big_servers {
+  server REF server,
+} AS {
+  SELECT hostname FROM server WHERE ram_mb > 4096
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    let outputs = RustCodegen::default().generate(&data);
+    let content = String::from_utf8(outputs.files[0].content.clone()).unwrap();
+
+    assert!(content.contains("    pub fn c_server(&self, ptr: TableRowPointerBigServers) -> TableRowPointerServer {\n"));
+    assert!(content.contains("    pub fn c_referrers_big_servers__server(&self, ptr: TableRowPointerServer) -> &[TableRowPointerBigServers] {\n"));
+}
+
+#[test]
+#[ignore]
+fn test_rust_codegen_ancestors_descendants_integration() {
+    let source = r#"
+TABLE datacenter {
+  dc_name TEXT PRIMARY KEY,
+}
+
+TABLE rack {
+  rack_name TEXT PRIMARY KEY CHILD OF datacenter,
+}
+
+TABLE server {
+  hostname TEXT PRIMARY KEY CHILD OF rack,
+}
+
+DATA datacenter {
+  dc1 WITH rack {
+    r1 WITH server {
+      srv1;
+      srv2;
+    };
+  };
+  dc2 WITH rack {
+    r2 WITH server {
+      srv3;
+    };
+  };
+}
+"#;
+    let output_dump = r#"TABLE: datacenter <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowDatacenter { dc_name: "dc1", children_rack: [TableRowPointerRack(0)], descendants_server: [TableRowPointerServer(0), TableRowPointerServer(1)] }
+TableRowDatacenter { dc_name: "dc2", children_rack: [TableRowPointerRack(1)], descendants_server: [TableRowPointerServer(2)] }
+TABLE: rack <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowRack { rack_name: "r1", parent: TableRowPointerDatacenter(0), children_server: [TableRowPointerServer(0), TableRowPointerServer(1)] }
+TableRowRack { rack_name: "r2", parent: TableRowPointerDatacenter(1), children_server: [TableRowPointerServer(2)] }
+TABLE: server <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+TableRowServer { hostname: "srv1", parent: TableRowPointerRack(0), ancestor_datacenter: TableRowPointerDatacenter(0) }
+TableRowServer { hostname: "srv2", parent: TableRowPointerRack(0), ancestor_datacenter: TableRowPointerDatacenter(0) }
+TableRowServer { hostname: "srv3", parent: TableRowPointerRack(1), ancestor_datacenter: TableRowPointerDatacenter(1) }
+"#;
+
+    assert_rust_db_compiled_dump_equals(source, output_dump, RustCodegen::default());
 }
@@ -0,0 +1,278 @@
+use std::path::Path;
+
+use crate::checker::{
+    logic::{insert_sqlite_data, AllData},
+    types::{DataTable, KeyType},
+};
+
+struct SqliteForeignKey<'a> {
+    columns: Vec<&'a str>,
+    foreign_table: &'a DataTable,
+    foreign_columns: Vec<&'a str>,
+}
+
+fn find_table<'a>(data: &'a AllData, name: &str) -> &'a DataTable {
+    data.tables.iter().find(|t| t.name.as_str() == name).unwrap()
+}
+
+fn key_column_names(t: &DataTable) -> Vec<&str> {
+    t.primary_keys_with_parents()
+        .into_iter()
+        .map(|i| t.columns[i].column_name.as_str())
+        .collect()
+}
+
+fn parent_table<'a>(data: &'a AllData, t: &DataTable) -> Option<&'a DataTable> {
+    t.columns.iter().find_map(|c| match &c.key_type {
+        KeyType::ChildPrimary { parent_table } => Some(find_table(data, parent_table.as_str())),
+        _ => None,
+    })
+}
+
+/// Child tables refer to parent by all parent key columns,
+/// references to child tables are stored as a single
+/// column and cannot be expressed as sqlite foreign keys
+fn foreign_keys<'a>(data: &'a AllData, t: &'a DataTable) -> Vec<SqliteForeignKey<'a>> {
+    let mut res = Vec::new();
+
+    if let Some(parent) = parent_table(data, t) {
+        let parent_keys = key_column_names(parent);
+        res.push(SqliteForeignKey {
+            columns: parent_keys.clone(),
+            foreign_table: parent,
+            foreign_columns: parent_keys,
+        });
+    }
+
+    for c in &t.columns {
+        if let Some(fk) = &c.maybe_foreign_key {
+            let foreign_table = find_table(data, fk.foreign_table.as_str());
+            let foreign_keys = key_column_names(foreign_table);
+            if foreign_keys.len() == 1 {
+                res.push(SqliteForeignKey {
+                    columns: vec![c.column_name.as_str()],
+                    foreign_table,
+                    foreign_columns: foreign_keys,
+                });
+            }
+        }
+    }
+
+    res
+}
+
+/// Schema with declared keys, typed columns and views
+/// joining child tables with their parents
+pub fn sqlite_dump_schema(data: &AllData) -> String {
+    let mut output = String::new();
+
+    for t in data.tables_sorted() {
+        let tname = t.name.as_str();
+        let mut lines: Vec<String> = Vec::new();
+        for c in &t.columns {
+            lines.push(format!("{} {} NOT NULL", c.column_name.as_str(), c.sqlite_type_name()));
+        }
+
+        let keys = key_column_names(t);
+        if !keys.is_empty() {
+            lines.push(format!("PRIMARY KEY ({})", keys.join(", ")));
+        }
+
+        for uc in &t.uniq_constraints {
+            let fields = uc.fields.iter().map(|f| f.as_str()).collect::<Vec<_>>();
+            lines.push(format!("UNIQUE ({})", fields.join(", ")));
+        }
+
+        let fkeys = foreign_keys(data, t);
+        for fk in &fkeys {
+            lines.push(format!(
+                "FOREIGN KEY ({}) REFERENCES {} ({})",
+                fk.columns.join(", "),
+                fk.foreign_table.name.as_str(),
+                fk.foreign_columns.join(", ")
+            ));
+        }
+
+        output += &format!("CREATE TABLE {tname} (\n  {}\n) STRICT;\n", lines.join(",\n  "));
+
+        // primary key index covers columns which are its prefix
+        for fk in &fkeys {
+            if !keys.starts_with(&fk.columns) {
+                output += &format!(
+                    "CREATE INDEX {tname}_fk_{} ON {tname} ({});\n",
+                    fk.columns.join("_"),
+                    fk.columns.join(", ")
+                );
+            }
+        }
+
+        output += "\n";
+    }
+
+    for t in data.tables_sorted() {
+        if let Some(parent) = parent_table(data, t) {
+            let parent_keys = key_column_names(parent);
+            let mut columns = vec!["c.*".to_string()];
+            for c in &parent.columns {
+                let cname = c.column_name.as_str();
+                if !parent_keys.contains(&cname) {
+                    columns.push(format!("p.{cname} AS parent__{cname}"));
+                }
+            }
+            let join = parent_keys
+                .iter()
+                .map(|k| format!("c.{k} = p.{k}"))
+                .collect::<Vec<_>>()
+                .join(" AND ");
+
+            output += &format!(
+                "CREATE VIEW {}__with_parent AS\nSELECT {}\nFROM {} c\nJOIN {} p ON {};\n\n",
+                t.name.as_str(),
+                columns.join(", "),
+                t.name.as_str(),
+                parent.name.as_str(),
+                join
+            );
+        }
+    }
+
+    output
+}
+
+pub fn sqlite_dump(data: &AllData, path: &Path) -> Result<(), rusqlite::Error> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+    conn.execute_batch(&sqlite_dump_schema(data))?;
+    for t in &data.tables {
+        // internal bug if this fails, schema is derived from checked data
+        insert_sqlite_data(t, &mut conn).unwrap();
+    }
+
+    let mut out = rusqlite::Connection::open(path)?;
+    let backup = rusqlite::backup::Backup::new(&conn, &mut out)?;
+    backup.run_to_completion(9999999, std::time::Duration::from_secs(0), None)
+}
+
+#[cfg(test)]
+fn sqlite_test_data() -> AllData {
+    use crate::db_parser::{self, InputSource};
+
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(
+            r#"
+TABLE server {
+  hostname TEXT PRIMARY KEY,
+  ram_mb INT,
+  kind REF server_kind,
+}
+
+TABLE server_kind {
+  name TEXT PRIMARY KEY,
+}
+
+TABLE disk {
+  dev_slot TEXT PRIMARY KEY CHILD OF server,
+  size_gb FLOAT,
+  serial TEXT,
+  UNIQUE(serial),
+}
+
+TABLE partition {
+  part_no INT PRIMARY KEY CHILD OF disk,
+}
+
+DATA server_kind {
+  metal;
+}
+
+DATA STRUCT server {
+  hostname: epyc1, ram_mb: 4096, kind: metal WITH disk {
+    dev_slot: sda, size_gb: 512.5, serial: abc WITH partition {
+      part_no: 1,
+    }
+  }
+}
+"#
+            .to_string(),
+        ),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    AllData::new(sources).unwrap()
+}
+
+#[test]
+fn test_sqlite_dump_schema() {
+    let data = sqlite_test_data();
+
+    pretty_assertions::assert_eq!(
+        sqlite_dump_schema(&data),
+        r#"CREATE TABLE disk (
+  hostname TEXT NOT NULL,
+  dev_slot TEXT NOT NULL,
+  size_gb REAL NOT NULL,
+  serial TEXT NOT NULL,
+  PRIMARY KEY (hostname, dev_slot),
+  UNIQUE (serial),
+  FOREIGN KEY (hostname) REFERENCES server (hostname)
+) STRICT;
+
+CREATE TABLE partition (
+  hostname TEXT NOT NULL,
+  dev_slot TEXT NOT NULL,
+  part_no INTEGER NOT NULL,
+  PRIMARY KEY (hostname, dev_slot, part_no),
+  FOREIGN KEY (hostname, dev_slot) REFERENCES disk (hostname, dev_slot)
+) STRICT;
+
+CREATE TABLE server (
+  hostname TEXT NOT NULL,
+  ram_mb INTEGER NOT NULL,
+  kind TEXT NOT NULL,
+  PRIMARY KEY (hostname),
+  FOREIGN KEY (kind) REFERENCES server_kind (name)
+) STRICT;
+CREATE INDEX server_fk_kind ON server (kind);
+
+CREATE TABLE server_kind (
+  name TEXT NOT NULL,
+  PRIMARY KEY (name)
+) STRICT;
+
+CREATE VIEW disk__with_parent AS
+SELECT c.*, p.ram_mb AS parent__ram_mb, p.kind AS parent__kind
+FROM disk c
+JOIN server p ON c.hostname = p.hostname;
+
+CREATE VIEW partition__with_parent AS
+SELECT c.*, p.size_gb AS parent__size_gb, p.serial AS parent__serial
+FROM partition c
+JOIN disk p ON c.hostname = p.hostname AND c.dev_slot = p.dev_slot;
+
+"#
+    );
+}
+
+#[test]
+fn test_sqlite_dump_foreign_keys_hold() {
+    let data = sqlite_test_data();
+    let path = crate::checker::tests::common::random_test_dir().join("dump.sqlite");
+    sqlite_dump(&data, &path).unwrap();
+
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    let violations: i64 = conn
+        .query_row("SELECT count(*) FROM pragma_foreign_key_check", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(violations, 0);
+
+    let joined: (String, i64, f64) = conn
+        .query_row(
+            "SELECT dev_slot, parent__ram_mb, parent__size_gb FROM partition__with_parent JOIN disk__with_parent USING (hostname, dev_slot)",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(joined, ("sda".to_string(), 4096, 512.5));
+}
@@ -9,7 +9,7 @@ use std::{
 
 use nom::{
     branch::alt,
-    bytes::complete::{escaped, tag, take_while1},
+    bytes::complete::{escaped, tag, take_while1, take_while_m_n},
     character::complete::{char, multispace0, multispace1, none_of, one_of, space1},
     combinator::{cut, fail, map, opt, recognize},
     error::{ErrorKind, ParseError, VerboseError, VerboseErrorKind},
@@ -57,6 +57,8 @@ pub struct TableDefinition {
     pub uniq_constraints: Vec<UniqConstraint>,
     pub row_checks: Vec<TableRowCheck>,
     pub mat_view_expression: Option<String>,
+    pub mat_view_language: ValidExpressions,
+    pub row_constants: bool,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -84,6 +86,14 @@ pub struct TableDataStructFields {
     pub extra_data: Vec<TableDataStruct>,
 }
 
+/// `source_file_id` of rows from `INCLUDE JSON/YAML/TOML` documents,
+/// their values have no offsets in edl sources
+pub const STRUCTURED_DOCUMENT_SOURCE_FILE_ID: i32 = -2;
+
+/// `source_file_id` in replacement maps of rows read from csv files and sqlite tables,
+/// their values can't be replaced in source
+pub const IMPORTED_ROW_SOURCE_FILE_ID: i32 = -3;
+
 #[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct TableDataStruct {
     pub target_table_name: String,
@@ -101,6 +111,35 @@ pub struct TableData {
     pub source_file_id: i32,
 }
 
+/// `DATA FROM CSV` statement, rows are read into the `TableData`
+/// placeholder at `segment_idx` once the csv file is loaded
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CsvImport {
+    pub source: InputSource,
+    /// csv header to table column, all headers are columns if empty
+    pub column_mapping: Vec<(String, String)>,
+    pub segment_idx: usize,
+    pub source_file_id: i32,
+    /// csv line number of every imported row
+    pub row_lines: Vec<usize>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub enum StructuredDataFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+/// `INCLUDE JSON/YAML/TOML` statement, document is converted to
+/// `TableDataStruct` segments inserted at `segment_idx` once the file is loaded
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct StructuredDataImport {
+    pub source: InputSource,
+    pub format: StructuredDataFormat,
+    pub segment_idx: usize,
+}
+
 #[derive(PartialEq, Eq, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InputSource {
     pub path: String,
@@ -125,7 +164,7 @@ pub fn parse_sources(input: &mut [InputSource]) -> Result<SourceOutputs, Box<dyn
 
 pub fn parse_sources_with_external(
     input: &mut [InputSource],
-) -> Result<SourceOutputs, Box<dyn Error + '_>> {
+) -> Result<SourceOutputs, Box<dyn Error>> {
     parse_sources_inner(input, true, 0)
 }
 
@@ -176,7 +215,7 @@ pub fn parse_sources_inner(
     input: &mut [InputSource],
     read_external_files: bool,
     source_id_offset: i32,
-) -> Result<SourceOutputs, Box<dyn Error + '_>> {
+) -> Result<SourceOutputs, Box<dyn Error>> {
     let mut result = SourceOutputs {
         table_definitions: Vec::new(),
         table_data_segments: Vec::new(),
@@ -184,10 +223,14 @@ pub fn parse_sources_inner(
         data_segments: Vec::new(),
         sql_proofs: Vec::new(),
         datalog_proofs: Vec::new(),
+        lua_proofs: Vec::new(),
         detached_defaults: Vec::new(),
         sources_db: Vec::new(),
         value_replacements: BTreeMap::new(),
         data_modules: Vec::new(),
+        csv_imports: Vec::new(),
+        structured_imports: Vec::new(),
+        lua_function_exports: Vec::new(),
     };
 
     let mut queue: VecDeque<SourceOutputs> = VecDeque::new();
@@ -214,6 +257,33 @@ pub fn parse_sources_inner(
             result.sources_db.push(lua_seg.clone());
         }
 
+        for csv in &mut current.csv_imports {
+            read_external_source(&mut csv.source, read_external_files, &mut read_sources)?;
+
+            let source_id = result.sources_db.len() as i32;
+            let TableDataSegment::DataFrame(td) = &mut current.table_data_segments[csv.segment_idx] else {
+                panic!("Csv import placeholder must be a data frame")
+            };
+            td.source_file_id = source_id;
+            csv.source_file_id = source_id;
+            read_csv_table_data(csv, td)?;
+
+            let lines = csv.source.contents.as_ref().unwrap().lines().count();
+            csv.source.line_comments = vec![String::new(); lines];
+            result.sources_db.push(csv.source.clone());
+        }
+
+        // in reverse so that earlier segment indexes stay valid
+        let structured_imports = std::mem::take(&mut current.structured_imports);
+        for mut si in structured_imports.into_iter().rev() {
+            read_external_source(&mut si.source, read_external_files, &mut read_sources)?;
+            let segments = structured_data_segments(&si.source, si.format)?;
+            let _ = current.table_data_segments.splice(
+                si.segment_idx..si.segment_idx,
+                segments.into_iter().map(TableDataSegment::StructuredData),
+            );
+        }
+
         for d_seg in &mut current.data_segments {
             maybe_read_input_source(d_seg, read_external_files, &mut read_sources).unwrap();
 
@@ -229,6 +299,7 @@ pub fn parse_sources_inner(
             result.sources_db.push(d_seg.clone());
         }
 
+        validate_proof_exception_dates(&current)?;
         finalized.push(current);
     }
 
@@ -418,6 +489,14 @@ fn parse_source_with_path<'a>(
         data_seg.source_dir = source_path.clone();
     }
 
+    for csv in &mut res.csv_imports {
+        csv.source.source_dir = source_path.clone();
+    }
+
+    for si in &mut res.structured_imports {
+        si.source.source_dir = source_path.clone();
+    }
+
     Ok((txt, res))
 }
 
@@ -426,6 +505,22 @@ fn maybe_read_input_source<'a>(
     reading_ext_enabled: bool,
     already_read_register: &mut HashSet<String>,
 ) -> Result<(), Box<dyn Error + 'a>> {
+    read_external_source(seg, reading_ext_enabled, already_read_register)?;
+
+    if let Some(r) = seg.contents.as_mut() {
+        let (mut stripped, comments) = strip_source_comments(r.as_str());
+        std::mem::swap(r, &mut stripped);
+        seg.line_comments = comments;
+    }
+
+    Ok(())
+}
+
+fn read_external_source(
+    seg: &mut InputSource,
+    reading_ext_enabled: bool,
+    already_read_register: &mut HashSet<String>,
+) -> Result<(), DatabaseValidationError> {
     if seg.contents.is_none() {
         if reading_ext_enabled {
             let path = seg.path.clone();
@@ -462,20 +557,193 @@ fn maybe_read_input_source<'a>(
         }
     }
 
-    if let Some(r) = seg.contents.as_mut() {
-        let (mut stripped, comments) = strip_source_comments(r.as_str());
-        std::mem::swap(r, &mut stripped);
-        seg.line_comments = comments;
+    Ok(())
+}
+
+/// Document maps table names to arrays of objects, arrays or objects
+/// under object keys are rows of the child table, like `WITH`
+fn structured_data_segments(
+    source: &InputSource,
+    format: StructuredDataFormat,
+) -> Result<Vec<TableDataStruct>, DatabaseValidationError> {
+    let contents = source.contents.as_ref().unwrap();
+    let to_error = |error: String| DatabaseValidationError::StructuredDataFileError {
+        path: source.path.clone(),
+        error,
+    };
+
+    let document: serde_json::Value = match format {
+        StructuredDataFormat::Json => serde_json::from_str(contents).map_err(|e| to_error(e.to_string()))?,
+        StructuredDataFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| to_error(e.to_string()))?,
+        StructuredDataFormat::Toml => toml::from_str(contents).map_err(|e| to_error(e.to_string()))?,
+    };
+
+    let serde_json::Value::Object(tables) = document else {
+        return Err(to_error("document root must be an object of tables".to_string()));
+    };
+
+    let mut res = Vec::with_capacity(tables.len());
+    for (table, rows) in &tables {
+        res.push(TableDataStruct {
+            target_table_name: table.clone(),
+            is_exclusive: false,
+            map: structured_data_rows(table, rows).map_err(to_error)?,
+            source_file_id: STRUCTURED_DOCUMENT_SOURCE_FILE_ID,
+        });
     }
 
-    Ok(())
+    Ok(res)
 }
 
+fn structured_data_rows(path: &str, rows: &serde_json::Value) -> Result<Vec<TableDataStructFields>, String> {
+    match rows {
+        serde_json::Value::Array(rows) => rows
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| match row {
+                serde_json::Value::Object(row) => structured_data_row(&format!("{path}[{idx}]"), row),
+                _ => Err(format!("{path}[{idx}]: table row must be an object")),
+            })
+            .collect(),
+        serde_json::Value::Object(row) => Ok(vec![structured_data_row(path, row)?]),
+        _ => Err(format!("{path}: table rows must be an array of objects")),
+    }
+}
+
+fn structured_data_row(
+    path: &str,
+    row: &serde_json::Map<String, serde_json::Value>,
+) -> Result<TableDataStructFields, String> {
+    let mut res = TableDataStructFields {
+        value_fields: Vec::new(),
+        extra_data: Vec::new(),
+    };
+
+    for (key, value) in row {
+        let value = match value {
+            serde_json::Value::String(v) => v.clone(),
+            serde_json::Value::Number(v) => v.to_string(),
+            serde_json::Value::Bool(v) => v.to_string(),
+            serde_json::Value::Null => {
+                return Err(format!("{path}.{key}: null values are not supported"));
+            }
+            serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+                res.extra_data.push(TableDataStruct {
+                    target_table_name: key.clone(),
+                    is_exclusive: false,
+                    map: structured_data_rows(&format!("{path}.{key}"), value)?,
+                    source_file_id: STRUCTURED_DOCUMENT_SOURCE_FILE_ID,
+                });
+                continue;
+            }
+        };
+
+        res.value_fields.push(TableDataStructField {
+            key: key.clone(),
+            value: ValueWithPos {
+                value,
+                offset_start: 0,
+                offset_end: 0,
+            },
+        });
+    }
+
+    Ok(res)
+}
+
+fn read_csv_table_data(csv: &mut CsvImport, td: &mut TableData) -> Result<(), DatabaseValidationError> {
+    let contents = csv.source.contents.as_ref().unwrap();
+    let csv_file = csv.source.path.clone();
+    let to_error = |e: csv::Error| {
+        let line = e.position().map(|p| p.line() as usize).unwrap_or(1);
+        let error = match e.kind() {
+            csv::ErrorKind::UnequalLengths { expected_len, len, .. } => {
+                format!("expected {expected_len} fields, found {len}")
+            }
+            _ => e.to_string(),
+        };
+        DatabaseValidationError::CsvParsingError { csv_file: csv_file.clone(), line, error }
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::Headers)
+        .from_reader(contents.as_bytes());
+    let headers = reader.headers().map_err(to_error)?.clone();
+
+    let mut header_idxs: Vec<usize> = Vec::new();
+    if csv.column_mapping.is_empty() {
+        for (idx, h) in headers.iter().enumerate() {
+            header_idxs.push(idx);
+            td.target_fields.push(h.to_string());
+        }
+    } else {
+        for (header, column) in &csv.column_mapping {
+            match headers.iter().position(|h| h == header) {
+                Some(idx) => {
+                    header_idxs.push(idx);
+                    td.target_fields.push(column.clone());
+                }
+                None => {
+                    return Err(DatabaseValidationError::CsvHeaderNotFound {
+                        csv_file,
+                        header: header.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    for record in reader.records() {
+        let record = record.map_err(to_error)?;
+        // values can't be replaced in csv, offsets are not needed
+        let value_fields = header_idxs
+            .iter()
+            .map(|idx| ValueWithPos {
+                value: record[*idx].to_string(),
+                offset_start: 0,
+                offset_end: 0,
+            })
+            .collect();
+
+        td.data.push(TableDataRow { value_fields, extra_data: Vec::new() });
+        // record position starts at skipped empty lines before the record
+        let pos = record.position().unwrap();
+        let empty_lines = contents[pos.byte() as usize..]
+            .split('\n')
+            .take_while(|l| l.is_empty() || *l == "\r")
+            .count();
+        csv.row_lines.push(pos.line() as usize + empty_lines);
+    }
+
+    Ok(())
+}
 
 #[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum TableDataSegment {
     DataFrame(TableData),
     StructuredData(TableDataStruct),
+    SqliteImport(SqliteImport),
+}
+
+/// `IMPORT SQLITE` statement, rows are read when data is inserted
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SqliteImport {
+    pub path: String,
+    pub sqlite_table: String,
+    pub target_table_name: String,
+    /// target column to sqlite column, all sqlite columns
+    /// are imported with the same names if empty
+    pub column_mapping: Vec<(String, String)>,
+    pub source_file_id: i32,
+}
+
+/// `EXPORT LUA FUNCTION` statement, global lua function
+/// registered as sqlite scalar function
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct LuaFunctionExport {
+    pub function_name: String,
+    pub argument_types: Vec<String>,
+    pub return_type: String,
 }
 
 pub type Replacements = BTreeMap<String, Vec<TableReplacement>>;
@@ -494,10 +762,14 @@ pub struct SourceOutputs {
     data_segments: Vec<InputSource>,
     sql_proofs: Vec<ExpressionProof>,
     datalog_proofs: Vec<ExpressionProof>,
+    lua_proofs: Vec<ExpressionProof>,
     detached_defaults: Vec<DetachedDefaults>,
     data_modules: Vec<DataModules>,
     sources_db: Vec<InputSource>,
     value_replacements: Replacements,
+    csv_imports: Vec<CsvImport>,
+    structured_imports: Vec<StructuredDataImport>,
+    lua_function_exports: Vec<LuaFunctionExport>,
 }
 
 impl SourceOutputs {
@@ -509,9 +781,13 @@ impl SourceOutputs {
         self.data_segments.extend(to_merge.data_segments);
         self.sql_proofs.extend(to_merge.sql_proofs);
         self.datalog_proofs.extend(to_merge.datalog_proofs);
+        self.lua_proofs.extend(to_merge.lua_proofs);
         self.detached_defaults.extend(to_merge.detached_defaults);
         self.sources_db.extend(to_merge.sources_db);
         self.data_modules.extend(to_merge.data_modules);
+        self.csv_imports.extend(to_merge.csv_imports);
+        self.structured_imports.extend(to_merge.structured_imports);
+        self.lua_function_exports.extend(to_merge.lua_function_exports);
     }
 
     pub fn parse_into_external<'a>(&mut self, input: &'a mut [InputSource]) -> Result<(), Box<dyn Error + 'a>> {
@@ -542,6 +818,14 @@ impl SourceOutputs {
         &self.datalog_proofs
     }
 
+    pub fn lua_proofs(&self) -> &[ExpressionProof] {
+        &self.lua_proofs
+    }
+
+    pub fn lua_function_exports(&self) -> &[LuaFunctionExport] {
+        &self.lua_function_exports
+    }
+
     pub fn detached_defaults(&self) -> &[DetachedDefaults] {
         &self.detached_defaults
     }
@@ -561,12 +845,45 @@ impl SourceOutputs {
     pub fn data_modules(&self) -> &[DataModules] {
         &self.data_modules
     }
+
+    pub fn csv_imports(&self) -> &[CsvImport] {
+        &self.csv_imports
+    }
 }
 
-#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum ValidExpressions {
     Sql,
     Datalog,
+    Lua,
+}
+
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ProofSeverity {
+    Error,
+    // offenders are reported but compilation succeeds
+    Warn,
+}
+
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ProofQuantifier {
+    // NONE EXIST OF, every output row is an offender
+    None,
+    // ALL EXIST OF, every table row must be in the output
+    All,
+    // EXIST AT LEAST n OF
+    AtLeast(usize),
+    // EXIST EXACTLY n OF
+    Exactly(usize),
+}
+
+/// Known offenders allowed by proof, listed by primary keys from the root
+/// parent joined with `=>`
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ProofExceptions {
+    pub keys: Vec<String>,
+    // YYYY-MM-DD
+    pub until: Option<String>,
 }
 
 #[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
@@ -575,6 +892,9 @@ pub struct ExpressionProof {
     pub output_table_name: String,
     pub expression: String,
     pub expression_type: ValidExpressions,
+    pub severity: ProofSeverity,
+    pub quantifier: ProofQuantifier,
+    pub exceptions: Option<ProofExceptions>,
 }
 
 #[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
@@ -608,6 +928,10 @@ enum ValidSourceSegments {
     ExpressionProof(ExpressionProof),
     DetachedDefaults(DetachedDefaults),
     DataModule(DataModules),
+    CsvImport(TableData, CsvImport),
+    StructuredImport(StructuredDataImport),
+    SqliteImport(SqliteImport),
+    LuaFunctionExport(LuaFunctionExport),
 }
 
 
@@ -625,18 +949,25 @@ fn parse_source(input: Span, source_file_id: i32) -> IResult<Span, SourceOutputs
         data_segments: Vec::new(),
         sql_proofs: Vec::new(),
         datalog_proofs: Vec::new(),
+        lua_proofs: Vec::new(),
         detached_defaults: Vec::new(),
         sources_db: Vec::new(),
         value_replacements: BTreeMap::new(),
         data_modules: Vec::new(),
+        csv_imports: Vec::new(),
+        structured_imports: Vec::new(),
+        lua_function_exports: Vec::new(),
     };
 
     let (tail, output) = many0(preceded(
         multispace0,
         alt((
+            parse_include_structured_data,
             parse_include_segment,
             map(parse_table, ValidSourceSegments::TDef),
             map(parse_materialized_view, ValidSourceSegments::TDef),
+            parse_csv_import,
+            map(|i| { parse_sqlite_import(i, source_file_id) }, ValidSourceSegments::SqliteImport),
             map(|i| { parse_table_data(i, source_file_id) }, ValidSourceSegments::TData),
             map(|i| { parse_table_data_structs(i, source_file_id) }, ValidSourceSegments::TDataStruct),
             map(parse_sql_proof, ValidSourceSegments::ExpressionProof),
@@ -645,6 +976,7 @@ fn parse_source(input: Span, source_file_id: i32) -> IResult<Span, SourceOutputs
                 ValidSourceSegments::DetachedDefaults,
             ),
             map(|i| { parse_data_module(i, source_file_id) }, ValidSourceSegments::DataModule),
+            map(parse_lua_function_export, ValidSourceSegments::LuaFunctionExport),
         )),
     ))
     .parse(input)?;
@@ -675,6 +1007,9 @@ fn parse_source(input: Span, source_file_id: i32) -> IResult<Span, SourceOutputs
                 ValidExpressions::Datalog => {
                     res.datalog_proofs.push(sp);
                 }
+                ValidExpressions::Lua => {
+                    res.lua_proofs.push(sp);
+                }
             },
             ValidSourceSegments::DetachedDefaults(dd) => {
                 res.detached_defaults.push(dd);
@@ -682,6 +1017,23 @@ fn parse_source(input: Span, source_file_id: i32) -> IResult<Span, SourceOutputs
             ValidSourceSegments::DataModule(dm) => {
                 res.data_modules.push(dm);
             }
+            ValidSourceSegments::CsvImport(td, mut csv) => {
+                csv.segment_idx = res.table_data_segments.len();
+                res.table_data_segments
+                    .push(TableDataSegment::DataFrame(td));
+                res.csv_imports.push(csv);
+            }
+            ValidSourceSegments::SqliteImport(si) => {
+                res.table_data_segments
+                    .push(TableDataSegment::SqliteImport(si));
+            }
+            ValidSourceSegments::StructuredImport(mut si) => {
+                si.segment_idx = res.table_data_segments.len();
+                res.structured_imports.push(si);
+            }
+            ValidSourceSegments::LuaFunctionExport(lf) => {
+                res.lua_function_exports.push(lf);
+            }
         }
     }
 
@@ -704,7 +1056,7 @@ fn curly_braces_expression(input: Span) -> IResult<Span, Span> {
 }
 
 fn parse_materialized_view(input: Span) -> IResult<Span, TableDefinition> {
-    let (tail, (_, _, _, _, table_name, _, rows, _, _, _, sql_expression)) = tuple((
+    let (tail, (_, _, _, _, table_name, _, rows, _, _, _, maybe_lang, sql_expression)) = tuple((
         tag("MATERIALIZED"),
         multispace1,
         tag("VIEW"),
@@ -715,6 +1067,7 @@ fn parse_materialized_view(input: Span) -> IResult<Span, TableDefinition> {
         multispace1,
         tag("AS"),
         multispace1,
+        opt(tuple((alt((tag("SQL"), tag("DATALOG"), tag("LUA"))), multispace1))),
         curly_braces_expression,
     ))
     .parse(input)?;
@@ -739,16 +1092,26 @@ fn parse_materialized_view(input: Span) -> IResult<Span, TableDefinition> {
             uniq_constraints,
             row_checks,
             mat_view_expression: Some(sql_expression.to_string()),
+            mat_view_language: maybe_lang
+                .map(|(t, _)| match *t {
+                    "SQL" => ValidExpressions::Sql,
+                    "DATALOG" => ValidExpressions::Datalog,
+                    "LUA" => ValidExpressions::Lua,
+                    _ => panic!("Must have matched some, bug in code."),
+                })
+                .unwrap_or(ValidExpressions::Sql),
+            row_constants: false,
         },
     ))
 }
 
 fn parse_table(input: Span) -> IResult<Span, TableDefinition> {
-    let (tail, (_, _, table_name, _, rows)) = tuple((
+    let (tail, (_, _, table_name, _, row_constants, rows)) = tuple((
         tag("TABLE"),
         multispace1,
         valid_table_or_column_name,
         multispace1,
+        opt(tuple((tag("CONSTANTS"), multispace1))),
         parse_table_definition,
     ))
     .parse(input)?;
@@ -773,6 +1136,8 @@ fn parse_table(input: Span) -> IResult<Span, TableDefinition> {
             uniq_constraints,
             row_checks,
             mat_view_expression: None,
+            mat_view_language: ValidExpressions::Sql,
+            row_constants: row_constants.is_some(),
         },
     ))
 }
@@ -805,6 +1170,176 @@ fn parse_table_data<'a>(input: Span<'a>, source_file_id: i32) -> IResult<Span, T
     Ok((tail, res))
 }
 
+fn parse_csv_column_mapping(input: Span) -> IResult<Span, Vec<(String, String)>> {
+    let (tail, (_, _, out, ..)) = tuple((
+        char('{'),
+        multispace0,
+        separated_list1(
+            tuple((multispace0, char(','), multispace0)),
+            tuple((
+                parse_quoted_text,
+                multispace0,
+                tag("=>"),
+                multispace0,
+                valid_table_or_column_name,
+            )),
+        ),
+        opt(tuple((multispace0, char(',')))),
+        multispace0,
+        char('}'),
+    ))
+    .parse(input)?;
+
+    let res = out
+        .into_iter()
+        .map(|(header, _, _, _, column)| (header.to_string(), column.to_string()))
+        .collect::<Vec<_>>();
+
+    Ok((tail, res))
+}
+
+fn parse_csv_import(input: Span) -> IResult<Span, ValidSourceSegments> {
+    let (tail, (_, _, is_exclusive, _, _, _, _, path, _, _, _, table_name, mapping)) = tuple((
+        tag("DATA"),
+        multispace1,
+        opt(tuple((tag("EXCLUSIVE"), multispace1))),
+        tag("FROM"),
+        multispace1,
+        tag("CSV"),
+        multispace1,
+        parse_quoted_text,
+        multispace1,
+        tag("INTO"),
+        multispace1,
+        valid_table_or_column_name,
+        opt(tuple((multispace0, parse_csv_column_mapping))),
+    ))
+    .parse(input)?;
+
+    // rows and source id are filled in when csv file is read
+    let td = TableData {
+        target_table_name: table_name.to_string(),
+        target_fields: Vec::new(),
+        data: Vec::new(),
+        is_exclusive: is_exclusive.is_some(),
+        source_file_id: -1,
+    };
+    let csv = CsvImport {
+        source: InputSource {
+            path: path.to_string(),
+            contents: None,
+            source_dir: None,
+            line_comments: Vec::new(),
+        },
+        column_mapping: mapping.map(|i| i.1).unwrap_or_default(),
+        segment_idx: 0,
+        source_file_id: -1,
+        row_lines: Vec::new(),
+    };
+
+    Ok((tail, ValidSourceSegments::CsvImport(td, csv)))
+}
+
+fn parse_sqlite_name(input: Span) -> IResult<Span, Span> {
+    alt((valid_table_or_column_name, parse_quoted_text)).parse(input)
+}
+
+fn parse_sqlite_column_mapping(input: Span) -> IResult<Span, Vec<(String, String)>> {
+    let (tail, (_, _, out, ..)) = tuple((
+        char('('),
+        multispace0,
+        separated_list1(
+            tuple((multispace0, char(','), multispace0)),
+            tuple((
+                valid_table_or_column_name,
+                multispace0,
+                char('='),
+                multispace0,
+                parse_sqlite_name,
+            )),
+        ),
+        opt(tuple((multispace0, char(',')))),
+        multispace0,
+        char(')'),
+    ))
+    .parse(input)?;
+
+    let res = out
+        .into_iter()
+        .map(|(column, _, _, _, sqlite_column)| (column.to_string(), sqlite_column.to_string()))
+        .collect::<Vec<_>>();
+
+    Ok((tail, res))
+}
+
+fn parse_sqlite_import(input: Span, source_file_id: i32) -> IResult<Span, SqliteImport> {
+    let (tail, (_, _, _, _, path, _, _, _, sqlite_table, _, _, _, table_name, mapping)) = tuple((
+        tag("IMPORT"),
+        multispace1,
+        tag("SQLITE"),
+        multispace1,
+        parse_quoted_text,
+        multispace1,
+        tag("TABLE"),
+        multispace1,
+        parse_sqlite_name,
+        multispace1,
+        tag("INTO"),
+        multispace1,
+        valid_table_or_column_name,
+        opt(tuple((multispace0, parse_sqlite_column_mapping))),
+    ))
+    .parse(input)?;
+
+    let res = SqliteImport {
+        path: path.to_string(),
+        sqlite_table: sqlite_table.to_string(),
+        target_table_name: table_name.to_string(),
+        column_mapping: mapping.map(|i| i.1).unwrap_or_default(),
+        source_file_id,
+    };
+
+    Ok((tail, res))
+}
+
+fn parse_lua_function_export_type(input: Span) -> IResult<Span, Span> {
+    alt((tag("TEXT"), tag("INT"), tag("FLOAT"), tag("BOOL"))).parse(input)
+}
+
+fn parse_lua_function_export(input: Span) -> IResult<Span, LuaFunctionExport> {
+    let (tail, (_, _, _, _, _, _, function_name, _, _, args, _, _, _, _, _, return_type)) =
+        tuple((
+            tag("EXPORT"),
+            multispace1,
+            tag("LUA"),
+            multispace1,
+            tag("FUNCTION"),
+            multispace1,
+            take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+            multispace0,
+            char('('),
+            separated_list0(
+                tuple((multispace0, char(','), multispace0)),
+                preceded(multispace0, parse_lua_function_export_type),
+            ),
+            multispace0,
+            char(')'),
+            multispace0,
+            tag("->"),
+            multispace0,
+            parse_lua_function_export_type,
+        ))
+        .parse(input)?;
+
+    let res = LuaFunctionExport {
+        function_name: function_name.to_string(),
+        argument_types: args.iter().map(|i| i.to_string()).collect(),
+        return_type: return_type.to_string(),
+    };
+
+    Ok((tail, res))
+}
+
 fn parse_table_data_struct_literals<'a>(input: Span<'a>, source_file_id: i32) -> IResult<Span, Vec<TableDataStructFields>> {
     let parse_table_data_structs_inner = |input: Span<'a>| {
         parse_table_data_structs_with_inner(input, source_file_id)
@@ -861,24 +1396,27 @@ fn parse_table_data_structs<'a>(input: Span<'a>, source_file_id: i32) -> IResult
 }
 
 fn parse_sql_proof(input: Span) -> IResult<Span, ExpressionProof> {
-    let (tail, (_, _, comment, _, _, _, _, _, _, _, tname, maybe_lang, _, sql_expression)) =
-        tuple((
-            tag("PROOF"),
-            multispace1,
-            parse_quoted_text,
-            multispace1,
-            tag("NONE"),
-            multispace1,
-            tag("EXIST"),
-            multispace1,
-            tag("OF"),
-            multispace1,
-            valid_table_or_column_name,
-            opt(tuple((multispace1, alt((tag("SQL"), tag("DATALOG")))))),
-            multispace1,
-            curly_braces_expression,
-        ))
-        .parse(input)?;
+    let (
+        tail,
+        (_, _, maybe_severity, comment, _, quantifier, _, _, _, tname, exceptions, maybe_lang, _,
+         sql_expression),
+    ) = tuple((
+        tag("PROOF"),
+        multispace1,
+        opt(tuple((alt((tag("WARN"), tag("ERROR"))), multispace1))),
+        parse_quoted_text,
+        multispace1,
+        parse_proof_quantifier,
+        multispace1,
+        tag("OF"),
+        multispace1,
+        valid_table_or_column_name,
+        opt(tuple((multispace1, parse_proof_exceptions))),
+        opt(tuple((multispace1, alt((tag("SQL"), tag("DATALOG"), tag("LUA")))))),
+        multispace1,
+        curly_braces_expression,
+    ))
+    .parse(input)?;
 
     Ok((
         tail,
@@ -890,13 +1428,132 @@ fn parse_sql_proof(input: Span) -> IResult<Span, ExpressionProof> {
                 .map(|(_, t)| match *t {
                     "SQL" => ValidExpressions::Sql,
                     "DATALOG" => ValidExpressions::Datalog,
+                    "LUA" => ValidExpressions::Lua,
                     _ => panic!("Must have matched some, bug in code."),
                 })
                 .unwrap_or(ValidExpressions::Sql),
+            severity: maybe_severity
+                .map(|(s, _)| match *s {
+                    "WARN" => ProofSeverity::Warn,
+                    "ERROR" => ProofSeverity::Error,
+                    _ => panic!("Must have matched some, bug in code."),
+                })
+                .unwrap_or(ProofSeverity::Error),
+            quantifier,
+            exceptions: exceptions.map(|(_, e)| e),
+        },
+    ))
+}
+
+fn parse_proof_quantifier(input: Span) -> IResult<Span, ProofQuantifier> {
+    let row_count = |input| {
+        map(take_while1(|c: char| c.is_ascii_digit()), |n: Span| {
+            n.parse::<usize>().unwrap()
+        })
+        .parse(input)
+    };
+
+    alt((
+        map(
+            tuple((tag("NONE"), multispace1, tag("EXIST"))),
+            |_| ProofQuantifier::None,
+        ),
+        map(
+            tuple((tag("ALL"), multispace1, tag("EXIST"))),
+            |_| ProofQuantifier::All,
+        ),
+        map(
+            tuple((
+                tag("EXIST"),
+                multispace1,
+                tag("AT"),
+                multispace1,
+                tag("LEAST"),
+                multispace1,
+                row_count,
+            )),
+            |(.., n)| ProofQuantifier::AtLeast(n),
+        ),
+        map(
+            tuple((tag("EXIST"), multispace1, tag("EXACTLY"), multispace1, row_count)),
+            |(.., n)| ProofQuantifier::Exactly(n),
+        ),
+    ))
+    .parse(input)
+}
+
+fn parse_proof_exceptions(input: Span) -> IResult<Span, ProofExceptions> {
+    let is_digit = |c: char| c.is_ascii_digit();
+    let (tail, (_, _, _, _, keys, _, _, _, until)) = tuple((
+        tag("EXCEPT"),
+        multispace1,
+        char('('),
+        multispace0,
+        separated_list1(
+            tuple((multispace0, char(','), multispace0)),
+            alt((parse_quoted_text, take_while1(valid_unquoted_data_char))),
+        ),
+        opt(tuple((multispace0, char(',')))),
+        multispace0,
+        char(')'),
+        opt(tuple((
+            multispace1,
+            tag("UNTIL"),
+            multispace1,
+            recognize(tuple((
+                take_while_m_n(4, 4, is_digit),
+                char('-'),
+                take_while_m_n(2, 2, is_digit),
+                char('-'),
+                take_while_m_n(2, 2, is_digit),
+            ))),
+        ))),
+    ))
+    .parse(input)?;
+
+    Ok((
+        tail,
+        ProofExceptions {
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+            until: until.map(|(_, _, _, d)| d.to_string()),
         },
     ))
 }
 
+fn validate_proof_exception_dates(outputs: &SourceOutputs) -> Result<(), DatabaseValidationError> {
+    let proofs = outputs.sql_proofs.iter().chain(&outputs.datalog_proofs).chain(&outputs.lua_proofs);
+    for proof in proofs {
+        if let Some(until) = proof.exceptions.as_ref().and_then(|e| e.until.as_ref()) {
+            if !is_valid_calendar_date(until) {
+                return Err(DatabaseValidationError::ProofExceptionsInvalidDate {
+                    table_name: proof.output_table_name.clone(),
+                    comment: proof.comment.clone(),
+                    date: until.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Date in YYYY-MM-DD format exists in gregorian calendar
+fn is_valid_calendar_date(date: &str) -> bool {
+    let year = date[0..4].parse::<u32>().unwrap();
+    let month = date[5..7].parse::<u32>().unwrap();
+    let day = date[8..10].parse::<u32>().unwrap();
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year => 29,
+        2 => 28,
+        _ => return false,
+    };
+
+    (1..=days_in_month).contains(&day)
+}
+
 fn parse_detached_defaults(input: Span) -> IResult<Span, DetachedDefaults> {
     let (full_tail, (_, _, cb)) =
         tuple((tag("DEFAULTS"), multispace1, curly_braces_expression)).parse(input)?;
@@ -985,6 +1642,39 @@ fn parse_include_segment(input: Span) -> IResult<Span, ValidSourceSegments> {
     Ok((tail, seg))
 }
 
+fn parse_include_structured_data(input: Span) -> IResult<Span, ValidSourceSegments> {
+    let (tail, (_, _, format, _, path)) = tuple((
+        tag("INCLUDE"),
+        multispace1,
+        alt((tag("JSON"), tag("YAML"), tag("TOML"))),
+        multispace1,
+        parse_quoted_text,
+    ))
+    .parse(input)?;
+
+    let format = match *format {
+        "JSON" => StructuredDataFormat::Json,
+        "YAML" => StructuredDataFormat::Yaml,
+        "TOML" => StructuredDataFormat::Toml,
+        _ => {
+            panic!("Should never be reached")
+        }
+    };
+
+    let si = StructuredDataImport {
+        source: InputSource {
+            path: path.to_string(),
+            contents: None,
+            source_dir: None,
+            line_comments: Vec::new(),
+        },
+        format,
+        segment_idx: 0,
+    };
+
+    Ok((tail, ValidSourceSegments::StructuredImport(si)))
+}
+
 fn parse_table_row(input: Span) -> IResult<Span, TableRowReturn> {
     alt((
         parse_table_column,
@@ -2013,6 +2703,23 @@ fn test_parse_example_table() {
     assert_eq!(td.columns[1].name, "full_name");
     assert_eq!(td.columns[1].the_type, "String");
     assert!(!td.columns[1].is_primary_key);
+    assert!(!td.row_constants);
+}
+
+#[test]
+fn test_parse_constants_table() {
+    let test_table = r#"TABLE disk_manufacturer CONSTANTS {
+        model TEXT PRIMARY KEY,
+    }"#;
+    let res = parse_table(Span::new(test_table));
+
+    assert!(res.is_ok());
+
+    let (input, td) = res.unwrap();
+    assert_eq!(*input, "");
+    assert_eq!(td.name, "disk_manufacturer");
+    assert!(td.row_constants);
+    assert_eq!(td.columns.len(), 1);
 }
 
 #[test]
@@ -2300,3 +3007,25 @@ fn test_parse_row_check() {
         panic!()
     }
 }
+
+#[test]
+fn test_parse_lua_function_export() {
+    let res = parse_lua_function_export(Span::new(
+        "EXPORT LUA FUNCTION parse_version(TEXT, INT) -> INT",
+    ));
+    let (tail, res) = res.unwrap();
+    assert_eq!(*tail, "");
+    assert_eq!(
+        res,
+        LuaFunctionExport {
+            function_name: "parse_version".to_string(),
+            argument_types: vec!["TEXT".to_string(), "INT".to_string()],
+            return_type: "INT".to_string(),
+        }
+    );
+
+    let (tail, res) =
+        parse_lua_function_export(Span::new("EXPORT LUA FUNCTION now() -> FLOAT")).unwrap();
+    assert_eq!(*tail, "");
+    assert!(res.argument_types.is_empty());
+}
@@ -4,3 +4,4 @@ extern crate lazy_static;
 pub mod checker;
 pub mod codegen;
 pub mod db_parser;
+pub mod sqlite_import;
@@ -11,6 +11,7 @@ mod checker;
 mod cli;
 mod codegen;
 mod db_parser;
+mod sqlite_import;
 
 fn main() {
     // we serialize/deserialize usize, 64 bit platform assumed
@@ -18,6 +19,19 @@ fn main() {
 
     let args = cli::get_args();
 
+    if let Some(cli::Command::ImportSqlite { database, out }) = &args.command {
+        match sqlite_import::edl_from_sqlite(database) {
+            Ok(source) => {
+                codegen::write_file_check_if_different(&PathBuf::from(out), source.as_bytes());
+            }
+            Err(e) => {
+                err_print("sqlite import error", &e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     let mut inputs: Vec<_> = args
         .inputs
         .iter()
@@ -67,22 +81,33 @@ fn main() {
         }
     }
 
-    let sqlite_needed = args.sqlite_output_file.is_some();
-
-    let data = AllData::new_with_flags(sources, sqlite_needed);
+    let data = AllData::new(sources);
     if let Err(e) = data.as_ref() {
         err_print("validation error", &e);
         std::process::exit(1);
     }
     let data = data.unwrap();
 
+    for w in data.proof_warnings() {
+        err_print("proof warning", w);
+    }
+    if args.deny_warnings && !data.proof_warnings().is_empty() {
+        eprintln!("proof warnings are denied, {} found", data.proof_warnings().len());
+        std::process::exit(1);
+    }
+
     if let Some(rt) = &args.rust_output_directory {
         let cgen = codegen::rust::RustCodegen {
             expose_deserialization_function: std::env::var("EDB_EXPOSE_DESER").is_ok(),
+            zero_copy_data: args.rust_zero_copy_data,
+            split_table_modules: args.rust_split_table_modules,
             ..Default::default()
         };
         let gen_src = cgen.generate(&data);
         gen_src.dump_to_dir(rt.as_str());
+        if cgen.split_table_modules {
+            gen_src.remove_stale_files(rt.as_str(), "rs");
+        }
     }
 
     if let Some(oc) = &args.ocaml_output_directory {
@@ -91,12 +116,55 @@ fn main() {
         gen_src.dump_to_dir(oc.as_str());
     }
 
+    if let Some(cpp) = &args.cpp_output_directory {
+        let cgen = codegen::cpp::CppCodegen::default();
+        let gen_src = cgen.generate(&data);
+        gen_src.dump_to_dir(cpp.as_str());
+    }
+
+    if let Some(go) = &args.go_output_directory {
+        let cgen = codegen::go::GoCodegen::default();
+        if let Err(e) = cgen.check_identifiers(&data) {
+            eprintln!("Cannot generate Go code: {e}");
+            std::process::exit(1);
+        }
+        let gen_src = cgen.generate(&data);
+        gen_src.dump_to_dir(go.as_str());
+    }
+
+    if let Some(hs) = &args.haskell_output_directory {
+        let cgen = codegen::haskell::HaskellCodegen::default();
+        let gen_src = cgen.generate(&data);
+        gen_src.dump_to_dir(hs.as_str());
+    }
+
+    if let Some(pb) = &args.protobuf_output_directory {
+        let mut cgen = codegen::protobuf::ProtobufCodegen::default();
+        if let Err(e) = cgen.load_field_numbers(pb.as_str()) {
+            eprintln!("Cannot load protobuf field numbers: {e}");
+            std::process::exit(1);
+        }
+        let gen_src = cgen.generate(&data);
+        gen_src.dump_to_dir(pb.as_str());
+    }
+
+    if let Some(json_file) = &args.json_output_file {
+        let json = codegen::json::json_dump(&data, args.json_relations);
+        let bytes = serde_json::to_vec_pretty(&json).unwrap();
+        crate::codegen::write_file_check_if_different(&PathBuf::from(json_file), &bytes);
+    }
+
+    if let Some(json_schema_file) = &args.json_schema_output_file {
+        let json = codegen::json::json_schema(&data, args.json_relations);
+        let bytes = serde_json::to_vec_pretty(&json).unwrap();
+        crate::codegen::write_file_check_if_different(&PathBuf::from(json_schema_file), &bytes);
+    }
+
     if let Some(sqlite) = &args.sqlite_output_file {
-        let db = data.sqlite_db.ro.lock().unwrap();
-        let mut backup = rusqlite::Connection::open(sqlite).unwrap();
-        let b = rusqlite::backup::Backup::new(&db, &mut backup).unwrap();
-        b.run_to_completion(9999999, std::time::Duration::from_secs(0), None)
-            .unwrap();
+        if let Err(e) = codegen::sqlite::sqlite_dump(&data, &PathBuf::from(sqlite)) {
+            err_print("sqlite dump error", &e);
+            std::process::exit(1);
+        }
     }
 }
 
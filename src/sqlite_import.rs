@@ -0,0 +1,742 @@
+use std::collections::{BTreeMap, HashSet};
+
+use rusqlite::{types::ValueRef, Connection, OpenFlags};
+
+use crate::{
+    checker::{errors::DatabaseValidationError, logic::is_reserved_column_name},
+    db_parser::valid_unquoted_data_char,
+};
+
+struct SqliteColumn {
+    name: String,
+    edl_name: String,
+    edl_type: &'static str,
+    has_nulls: bool,
+    reference: Option<String>,
+    comment: Option<String>,
+}
+
+struct SqliteTable {
+    name: String,
+    edl_name: String,
+    columns: Vec<SqliteColumn>,
+    primary_key: Option<usize>,
+    uniq_constraints: Vec<Vec<usize>>,
+}
+
+/// EDL identifiers are lowercase alphanumeric with underscores, names starting
+/// with a digit get `sqlite_` prefix so they are valid in generated code
+fn edl_identifier(name: &str) -> String {
+    let res: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if res.is_empty() || res.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("sqlite_{res}")
+    } else {
+        res
+    }
+}
+
+/// Column names reserved for generated columns and language keywords get `sqlite_` prefix
+fn edl_column_identifier(name: &str) -> String {
+    let res = edl_identifier(name);
+    if is_reserved_column_name(&res) {
+        format!("sqlite_{res}")
+    } else {
+        res
+    }
+}
+
+/// Different sqlite names may sanitize to the same identifier,
+/// later ones get numeric suffix like `host_name_2`
+fn unique_identifier(ident: String, taken: &mut HashSet<String>) -> String {
+    let mut res = ident.clone();
+    let mut suffix = 2;
+    while !taken.insert(res.clone()) {
+        res = format!("{ident}_{suffix}");
+        suffix += 1;
+    }
+    res
+}
+
+/// Sqlite type affinity rules, booleans are recognized by name
+fn edl_type(declared_type: &str) -> &'static str {
+    let t = declared_type.to_uppercase();
+    if t.contains("BOOL") {
+        "BOOL"
+    } else if t.contains("INT") {
+        "INT"
+    } else if t.contains("CHAR") || t.contains("CLOB") || t.contains("TEXT") {
+        "TEXT"
+    } else if t.contains("REAL") || t.contains("FLOA") || t.contains("DOUB") {
+        "FLOAT"
+    } else {
+        "TEXT"
+    }
+}
+
+/// Sqlite storage classes which can be parsed as values of edl type
+fn storage_classes(edl_type: &str) -> &'static str {
+    match edl_type {
+        "INT" | "BOOL" => "'integer', 'null'",
+        "FLOAT" => "'integer', 'real', 'null'",
+        _ => "'integer', 'real', 'text', 'null'",
+    }
+}
+
+fn default_value(edl_type: &str) -> &'static str {
+    match edl_type {
+        "INT" => "0",
+        "FLOAT" => "0.0",
+        "BOOL" => "false",
+        _ => "''",
+    }
+}
+
+fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn edl_value(value: &str) -> Option<String> {
+    // comments are stripped before parsing and quotes can't be escaped
+    if value.contains("//") || (value.contains('"') && value.contains('\'')) {
+        return None;
+    }
+
+    if !value.is_empty() && value.chars().all(valid_unquoted_data_char) {
+        Some(value.to_string())
+    } else if value.contains('"') {
+        Some(format!("'{value}'"))
+    } else {
+        Some(format!("\"{value}\""))
+    }
+}
+
+fn read_tables(conn: &Connection, sqlite_file: &str) -> Result<Vec<SqliteTable>, DatabaseValidationError> {
+    let import_error = |sqlite_table: &str, e: rusqlite::Error| DatabaseValidationError::SqliteImportError {
+        sqlite_file: sqlite_file.to_string(),
+        sqlite_table: sqlite_table.to_string(),
+        error: e.to_string(),
+    };
+
+    let table_names: Vec<String> = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get(0))
+                .and_then(|rows| rows.collect())
+        })
+        .map_err(|e| import_error("sqlite_master", e))?;
+
+    let mut tables = Vec::with_capacity(table_names.len());
+    let mut taken_table_names = HashSet::new();
+    for name in table_names {
+        let mut columns = Vec::new();
+        let mut taken_column_names = HashSet::new();
+        let mut pk_columns = Vec::new();
+        let columns_info: Vec<(String, String, i64)> = conn
+            .prepare(&format!("PRAGMA table_info({})", quote_identifier(&name)))
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get(1)?, row.get(2)?, row.get(5)?)))
+                    .and_then(|rows| rows.collect())
+            })
+            .map_err(|e| import_error(&name, e))?;
+
+        for (idx, (column_name, declared_type, pk)) in columns_info.into_iter().enumerate() {
+            if pk > 0 {
+                pk_columns.push(idx);
+            }
+            let has_nulls: bool = conn
+                .query_row(
+                    &format!(
+                        "SELECT EXISTS(SELECT 1 FROM {} WHERE {} IS NULL)",
+                        quote_identifier(&name),
+                        quote_identifier(&column_name)
+                    ),
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(|e| import_error(&name, e))?;
+            // sqlite doesn't enforce declared types, stored values which
+            // don't match declared type are imported as text
+            let mut col_type = edl_type(&declared_type);
+            let mut comment = None;
+            let has_other_values: bool = conn
+                .query_row(
+                    &format!(
+                        "SELECT EXISTS(SELECT 1 FROM {} WHERE typeof({}) NOT IN ({}))",
+                        quote_identifier(&name),
+                        quote_identifier(&column_name),
+                        storage_classes(col_type),
+                    ),
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(|e| import_error(&name, e))?;
+            if has_other_values && col_type != "TEXT" {
+                comment = Some(format!("declared {declared_type}, has values which are not {col_type}"));
+                col_type = "TEXT";
+            }
+            columns.push(SqliteColumn {
+                edl_name: unique_identifier(edl_column_identifier(&column_name), &mut taken_column_names),
+                name: column_name,
+                edl_type: col_type,
+                has_nulls,
+                reference: None,
+                comment,
+            });
+        }
+
+        // composite primary keys and single column keys which can't be
+        // edl primary keys, like BOOL, become unique constraints
+        let mut uniq_constraints = Vec::new();
+        let primary_key = match pk_columns.as_slice() {
+            [pk] if !columns[*pk].has_nulls && matches!(columns[*pk].edl_type, "INT" | "TEXT") => Some(*pk),
+            [pk] => {
+                if !columns[*pk].has_nulls && columns[*pk].edl_type != "FLOAT" {
+                    uniq_constraints.push(pk_columns);
+                }
+                None
+            }
+            [] => None,
+            _ => {
+                uniq_constraints.push(pk_columns);
+                None
+            }
+        };
+
+        let indexes: Vec<(String, bool, String)> = conn
+            .prepare(&format!("PRAGMA index_list({})", quote_identifier(&name)))
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get(1)?, row.get(2)?, row.get(3)?)))
+                    .and_then(|rows| rows.collect())
+            })
+            .map_err(|e| import_error(&name, e))?;
+        for (index_name, is_unique, origin) in indexes {
+            if !is_unique || origin == "pk" {
+                continue;
+            }
+            let index_columns: Vec<String> = conn
+                .prepare(&format!("PRAGMA index_info({})", quote_identifier(&index_name)))
+                .and_then(|mut stmt| {
+                    stmt.query_map([], |row| row.get(2)).and_then(|rows| rows.collect())
+                })
+                .map_err(|e| import_error(&name, e))?;
+            let idxs: Vec<usize> = index_columns
+                .iter()
+                .filter_map(|c| columns.iter().position(|i| &i.name == c))
+                .collect();
+            let uniq_allowed = idxs.len() == index_columns.len()
+                && idxs.iter().all(|i| columns[*i].edl_type != "FLOAT" && Some(*i) != primary_key);
+            if uniq_allowed && !uniq_constraints.contains(&idxs) {
+                uniq_constraints.push(idxs);
+            }
+        }
+
+        tables.push(SqliteTable {
+            edl_name: unique_identifier(edl_identifier(&name), &mut taken_table_names),
+            name,
+            columns,
+            primary_key,
+            uniq_constraints,
+        });
+    }
+
+    // references are only possible to single column primary keys,
+    // values are edl table name and sqlite primary key column name
+    let primary_keys: BTreeMap<String, (String, String)> = tables
+        .iter()
+        .filter_map(|t| {
+            t.primary_key.map(|pk| (t.name.clone(), (t.edl_name.clone(), t.columns[pk].name.clone())))
+        })
+        .collect();
+    for t in &mut tables {
+        let fkeys: Vec<(i64, String, String, Option<String>)> = conn
+            .prepare(&format!("PRAGMA foreign_key_list({})", quote_identifier(&t.name)))
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get(0)?, row.get(2)?, row.get(3)?, row.get(4)?)))
+                    .and_then(|rows| rows.collect())
+            })
+            .map_err(|e| import_error(&t.name, e))?;
+
+        for (fk_id, foreign_table, from, to) in &fkeys {
+            let Some(col) = t.columns.iter_mut().find(|c| &c.name == from) else {
+                continue;
+            };
+            let is_composite = fkeys.iter().filter(|i| i.0 == *fk_id).count() > 1;
+            let target = primary_keys.get(foreign_table);
+            match target {
+                Some((target_table, pk))
+                    if !is_composite && (to.is_none() || to.as_ref() == Some(pk)) && !col.has_nulls =>
+                {
+                    col.reference = Some(target_table.clone());
+                }
+                _ => {
+                    let fk_comment = format!("not a REF, foreign key to {foreign_table}");
+                    col.comment = Some(match col.comment.take() {
+                        Some(c) => format!("{c}, {fk_comment}"),
+                        None => fk_comment,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(tables)
+}
+
+fn push_table_data(
+    conn: &Connection,
+    sqlite_file: &str,
+    t: &SqliteTable,
+    output: &mut String,
+) -> Result<(), DatabaseValidationError> {
+    let import_error = |error: String| DatabaseValidationError::SqliteImportError {
+        sqlite_file: sqlite_file.to_string(),
+        sqlite_table: t.name.clone(),
+        error,
+    };
+
+    let query = format!(
+        "SELECT {} FROM {}",
+        t.columns.iter().map(|c| quote_identifier(&c.name)).collect::<Vec<_>>().join(", "),
+        quote_identifier(&t.name),
+    );
+    let mut stmt = conn.prepare(&query).map_err(|e| import_error(e.to_string()))?;
+    let mut rows = stmt.query([]).map_err(|e| import_error(e.to_string()))?;
+
+    let mut data = String::new();
+    while let Some(row) = rows.next().map_err(|e| import_error(e.to_string()))? {
+        let mut fields = Vec::with_capacity(t.columns.len());
+        for (idx, col) in t.columns.iter().enumerate() {
+            let value = match row.get_ref(idx).map_err(|e| import_error(e.to_string()))? {
+                // column has default value
+                ValueRef::Null => continue,
+                ValueRef::Integer(v) if col.edl_type == "BOOL" => (v != 0).to_string(),
+                ValueRef::Integer(v) => v.to_string(),
+                ValueRef::Real(v) => v.to_string(),
+                ValueRef::Text(v) => String::from_utf8_lossy(v).to_string(),
+                ValueRef::Blob(_) => {
+                    return Err(import_error(format!("column {} has blob values", col.name)));
+                }
+            };
+            let value = edl_value(&value).ok_or_else(|| {
+                import_error(format!("value {value:?} of column {} cannot be represented in edl", col.name))
+            })?;
+            fields.push(format!("{}: {}", col.edl_name, value));
+        }
+        // empty struct is not valid, row of NULLs has explicit defaults
+        if fields.is_empty() {
+            for col in &t.columns {
+                fields.push(format!("{}: {}", col.edl_name, default_value(col.edl_type)));
+            }
+        }
+        data += &format!("  {{ {} }},\n", fields.join(", "));
+    }
+
+    if !data.is_empty() {
+        *output += &format!("\nDATA STRUCT {} [\n", t.edl_name);
+        *output += &data;
+        *output += "]\n";
+    }
+
+    Ok(())
+}
+
+/// Generates `TABLE` definitions and `DATA STRUCT` blocks from sqlite database.
+/// Columns with NULL values get type default values and NULLs are omitted from
+/// data, foreign keys which are not single column primary keys stay plain columns.
+/// Only INT and TEXT columns can be primary keys, columns with values that don't
+/// match declared type become TEXT.
+/// Names are sanitized to unique EDL identifiers, names which start with a digit
+/// or are reserved get `sqlite_` prefix.
+pub fn edl_from_sqlite(sqlite_file: &str) -> Result<String, DatabaseValidationError> {
+    let conn = Connection::open_with_flags(sqlite_file, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|e| {
+        DatabaseValidationError::SqliteImportError {
+            sqlite_file: sqlite_file.to_string(),
+            sqlite_table: String::new(),
+            error: e.to_string(),
+        }
+    })?;
+
+    let tables = read_tables(&conn, sqlite_file)?;
+    let mut output = String::new();
+
+    for t in &tables {
+        if !output.is_empty() {
+            output += "\n";
+        }
+        output += &format!("TABLE {} {{\n", t.edl_name);
+        for (idx, col) in t.columns.iter().enumerate() {
+            if let Some(comment) = &col.comment {
+                output += &format!("  // {comment}\n");
+            }
+            output += &format!("  {} ", col.edl_name);
+            match &col.reference {
+                Some(foreign_table) => output += &format!("REF {foreign_table}"),
+                None => output += col.edl_type,
+            }
+            if t.primary_key == Some(idx) {
+                output += " PRIMARY KEY";
+            } else if col.has_nulls {
+                output += &format!(" DEFAULT {}", default_value(col.edl_type));
+            }
+            output += ",\n";
+        }
+        for uc in &t.uniq_constraints {
+            let fields = uc.iter().map(|i| t.columns[*i].edl_name.as_str()).collect::<Vec<_>>();
+            output += &format!("  UNIQUE({}),\n", fields.join(", "));
+        }
+        output += "}\n";
+
+        push_table_data(&conn, sqlite_file, t, &mut output)?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+fn sqlite_import_roundtrip(sqlite_init: &str) -> (String, serde_json::Value) {
+    use crate::checker::logic::AllData;
+    use crate::db_parser::{self, InputSource};
+
+    let tmp_dir = crate::checker::tests::common::random_test_dir();
+    let db_path = tmp_dir.join("legacy.db");
+    let conn = Connection::open(&db_path).unwrap();
+    conn.execute_batch(sqlite_init).unwrap();
+
+    let source = edl_from_sqlite(db_path.to_str().unwrap()).unwrap();
+    let inputs = &mut [InputSource {
+        path: "test".to_string(),
+        contents: Some(source.clone()),
+        source_dir: None,
+        line_comments: Vec::new(),
+    }];
+    let sources = db_parser::parse_sources(inputs.as_mut_slice()).unwrap();
+    let data = AllData::new(sources).unwrap();
+    (source, data.data_as_json())
+}
+
+#[test]
+fn test_edl_from_sqlite_roundtrip() {
+    let (source, json) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE datacenter(name TEXT PRIMARY KEY);
+CREATE TABLE hosts(
+    "Host Name" TEXT PRIMARY KEY,
+    memory INTEGER NOT NULL,
+    load REAL,
+    is_vm BOOLEAN,
+    dc TEXT REFERENCES datacenter(name),
+    rack TEXT,
+    slot INTEGER,
+    UNIQUE(rack, slot)
+);
+INSERT INTO datacenter VALUES ('dc1');
+INSERT INTO hosts VALUES ('srv-a', 4096, 0.5, 1, 'dc1', 'r1', 1);
+INSERT INTO hosts VALUES ('srv b', 2048, NULL, 0, 'dc1', 'r1', 2);
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE datacenter {
+  name TEXT PRIMARY KEY,
+}
+
+DATA STRUCT datacenter [
+  { name: dc1 },
+]
+
+TABLE hosts {
+  host_name TEXT PRIMARY KEY,
+  memory INT,
+  load FLOAT DEFAULT 0.0,
+  is_vm BOOL,
+  dc REF datacenter,
+  rack TEXT,
+  slot INT,
+  UNIQUE(rack, slot),
+}
+
+DATA STRUCT hosts [
+  { host_name: srv-a, memory: 4096, load: 0.5, is_vm: true, dc: dc1, rack: r1, slot: 1 },
+  { host_name: "srv b", memory: 2048, is_vm: false, dc: dc1, rack: r1, slot: 2 },
+]
+"#
+    );
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "datacenter": [{"name": "dc1"}],
+            "hosts": [
+                {"host_name": "srv-a", "memory": 4096.0, "load": 0.5, "is_vm": true, "dc": "dc1", "rack": "r1", "slot": 1.0},
+                {"host_name": "srv b", "memory": 2048.0, "load": 0.0, "is_vm": false, "dc": "dc1", "rack": "r1", "slot": 2.0},
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_edl_from_sqlite_non_primary_key_references() {
+    let (source, _) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE item(a INTEGER, b INTEGER, label TEXT UNIQUE, PRIMARY KEY(a, b));
+CREATE TABLE tag(
+    name TEXT,
+    item_label TEXT REFERENCES item(label),
+    item_a INTEGER,
+    item_b INTEGER,
+    FOREIGN KEY(item_a, item_b) REFERENCES item(a, b)
+);
+INSERT INTO item VALUES (1, 2, 'x');
+INSERT INTO tag VALUES ('t', 'x', 1, 2);
+INSERT INTO tag VALUES ('u', NULL, NULL, NULL);
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE item {
+  a INT,
+  b INT,
+  label TEXT,
+  UNIQUE(a, b),
+  UNIQUE(label),
+}
+
+DATA STRUCT item [
+  { a: 1, b: 2, label: x },
+]
+
+TABLE tag {
+  name TEXT,
+  // not a REF, foreign key to item
+  item_label TEXT DEFAULT '',
+  // not a REF, foreign key to item
+  item_a INT DEFAULT 0,
+  // not a REF, foreign key to item
+  item_b INT DEFAULT 0,
+}
+
+DATA STRUCT tag [
+  { name: t, item_label: x, item_a: 1, item_b: 2 },
+  { name: u },
+]
+"#
+    );
+}
+
+#[test]
+fn test_edl_from_sqlite_leading_digit_names() {
+    let (source, _) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE "2fa_keys"("1st" TEXT PRIMARY KEY, "2nd" INTEGER);
+INSERT INTO "2fa_keys" VALUES ('a', 1);
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE sqlite_2fa_keys {
+  sqlite_1st TEXT PRIMARY KEY,
+  sqlite_2nd INT,
+}
+
+DATA STRUCT sqlite_2fa_keys [
+  { sqlite_1st: a, sqlite_2nd: 1 },
+]
+"#
+    );
+}
+
+#[test]
+fn test_edl_from_sqlite_reserved_column_names() {
+    let (source, _) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE node(name TEXT PRIMARY KEY, parent TEXT, "with" INTEGER);
+INSERT INTO node VALUES ('a', 'root', 1);
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE node {
+  name TEXT PRIMARY KEY,
+  sqlite_parent TEXT,
+  sqlite_with INT,
+}
+
+DATA STRUCT node [
+  { name: a, sqlite_parent: root, sqlite_with: 1 },
+]
+"#
+    );
+}
+
+#[test]
+fn test_edl_from_sqlite_reserved_column_prefixes() {
+    let (source, _) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE node(name TEXT PRIMARY KEY, children_count INTEGER, referrers_list TEXT);
+INSERT INTO node VALUES ('a', 2, 'b');
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE node {
+  name TEXT PRIMARY KEY,
+  sqlite_children_count INT,
+  sqlite_referrers_list TEXT,
+}
+
+DATA STRUCT node [
+  { name: a, sqlite_children_count: 2, sqlite_referrers_list: b },
+]
+"#
+    );
+}
+
+#[test]
+fn test_edl_from_sqlite_sanitized_name_collisions() {
+    let (source, _) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE "host-list"(name TEXT PRIMARY KEY);
+CREATE TABLE "host list"(
+    "Host Name" TEXT PRIMARY KEY,
+    host_name INTEGER,
+    "host-name" INTEGER,
+    owner TEXT REFERENCES "host-list"(name)
+);
+INSERT INTO "host-list" VALUES ('x');
+INSERT INTO "host list" VALUES ('a', 1, 2, 'x');
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE host_list {
+  host_name TEXT PRIMARY KEY,
+  host_name_2 INT,
+  host_name_3 INT,
+  owner REF host_list_2,
+}
+
+DATA STRUCT host_list [
+  { host_name: a, host_name_2: 1, host_name_3: 2, owner: x },
+]
+
+TABLE host_list_2 {
+  name TEXT PRIMARY KEY,
+}
+
+DATA STRUCT host_list_2 [
+  { name: x },
+]
+"#
+    );
+}
+
+#[test]
+fn test_edl_from_sqlite_float_and_bool_primary_keys() {
+    let (source, json) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE price(v REAL PRIMARY KEY);
+CREATE TABLE flag(b BOOLEAN PRIMARY KEY);
+INSERT INTO price VALUES (1.5);
+INSERT INTO flag VALUES (1);
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE flag {
+  b BOOL,
+  UNIQUE(b),
+}
+
+DATA STRUCT flag [
+  { b: true },
+]
+
+TABLE price {
+  v FLOAT,
+}
+
+DATA STRUCT price [
+  { v: 1.5 },
+]
+"#
+    );
+
+    assert_eq!(json, serde_json::json!({"flag": [{"b": true}], "price": [{"v": 1.5}]}));
+}
+
+#[test]
+fn test_edl_from_sqlite_all_null_row() {
+    let (source, json) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE t(a TEXT, n INTEGER);
+INSERT INTO t VALUES ('x', 1);
+INSERT INTO t VALUES (NULL, NULL);
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE t {
+  a TEXT DEFAULT '',
+  n INT DEFAULT 0,
+}
+
+DATA STRUCT t [
+  { a: x, n: 1 },
+  { a: '', n: 0 },
+]
+"#
+    );
+
+    assert_eq!(json, serde_json::json!({"t": [{"a": "x", "n": 1.0}, {"a": "", "n": 0.0}]}));
+}
+
+#[test]
+fn test_edl_from_sqlite_values_not_matching_declared_type() {
+    let (source, json) = sqlite_import_roundtrip(
+        r#"
+CREATE TABLE t(id INTEGER PRIMARY KEY, n INTEGER, f REAL);
+INSERT INTO t VALUES (1, 7, 2);
+INSERT INTO t VALUES (2, 'oops', 'nan?');
+"#,
+    );
+
+    pretty_assertions::assert_eq!(
+        source,
+        r#"TABLE t {
+  id INT PRIMARY KEY,
+  // declared INTEGER, has values which are not INT
+  n TEXT,
+  // declared REAL, has values which are not FLOAT
+  f TEXT,
+}
+
+DATA STRUCT t [
+  { id: 1, n: 7, f: 2 },
+  { id: 2, n: oops, f: "nan?" },
+]
+"#
+    );
+
+    assert_eq!(
+        json,
+        serde_json::json!({"t": [{"id": 1.0, "n": "7", "f": "2"}, {"id": 2.0, "n": "oops", "f": "nan?"}]})
+    );
+}